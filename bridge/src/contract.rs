@@ -0,0 +1,8887 @@
+use cosmwasm_std::{
+    log, to_binary, Api, Binary, CosmosMsg, Env, Extern, HandleResponse, HumanAddr, InitResponse,
+    Querier, StdResult, Storage, Uint128, WasmMsg,
+};
+use std::convert::TryInto;
+
+use crate::error;
+use crate::msg::{
+    status_level_to_u8, u8_to_status_level, ContractStatusLevel, HandleMsg, HandleResult, InitMsg,
+    QueryMsg,
+};
+use crate::query_messages::QueryResponse;
+use crate::query_messages::PendingActionSummary;
+use crate::state::{
+    BlockedDestinationsStore, BlockedSendersStore, Config, Constants, MintRecordStore, MoneroProof,
+    MoneroProofsStore, OracleAttestationsStore, PendingActionStore, ReadonlyConfig,
+    ReadonlySwapDetailsStore, ReceiptIndexStore, SwapDetails, SwapDetailsStore, TimelockedAction,
+    TokenInfo, WhitelistedDestinationsStore,
+};
+
+pub fn init<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    _env: Env,
+    msg: InitMsg,
+) -> StdResult<InitResponse> {
+    let admin = deps.api.canonical_address(&_env.message.sender)?;
+    let sxmr = TokenInfo {
+        address: deps.api.canonical_address(&msg.sxmr_address)?,
+        contract_hash: msg.sxmr_hash,
+        decimals: msg.sxmr_decimals,
+    };
+    let bridge_minter = deps.api.canonical_address(&msg.bridge_minter)?;
+    let emergency_admin = match msg.emergency_admin {
+        Some(addr) => deps.api.canonical_address(&addr)?,
+        None => admin.clone(),
+    };
+
+    // Secret Network mainnet's chain id; see `InitMsg::testnet_mode`'s doc
+    // comment for why this check, not an admin-facing toggle, is what keeps
+    // `TestMint` off mainnet.
+    const MAINNET_CHAIN_ID: &str = "secret-4";
+    if msg.testnet_mode && _env.block.chain_id == MAINNET_CHAIN_ID {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "testnet_mode cannot be enabled on Secret Network mainnet",
+        ));
+    }
+
+    if msg.minters.is_empty() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "minters must not be empty",
+        ));
+    }
+    let minters = msg
+        .minters
+        .iter()
+        .map(|m| deps.api.canonical_address(m))
+        .collect::<StdResult<Vec<_>>>()?;
+    let max_minters = ReadonlyConfig::from_storage(&deps.storage).max_minters();
+    if minters.len() as u32 > max_minters {
+        return Err(cosmwasm_std::StdError::generic_err(format!(
+            "minter list exceeds the cap of {}",
+            max_minters
+        )));
+    }
+
+    let constants = Constants {
+        admin,
+        sxmr,
+        bridge_minter,
+        monero_wallets: msg.monero_wallets,
+        min_swap_amount: msg.min_swap_amount,
+        prng_seed: msg.prng_seed.into_bytes(),
+        emergency_admin,
+        testnet_mode: msg.testnet_mode,
+        bridge_address: deps.api.canonical_address(&_env.contract.address)?,
+    };
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    config.set_constants(&constants)?;
+    config.set_contract_status(ContractStatusLevel::Running);
+    config.set_fee_collector(&constants.admin);
+    config.set_sxmr_decimals(msg.sxmr_decimals);
+    config.set_minters(minters);
+
+    Ok(InitResponse::default())
+}
+
+pub fn handle<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    msg: HandleMsg,
+) -> StdResult<HandleResponse> {
+    let contract_status = ReadonlyConfig::from_storage(&deps.storage).contract_status();
+    match contract_status {
+        ContractStatusLevel::Paused | ContractStatusLevel::Emergency => match msg {
+            HandleMsg::SetContractStatus { .. }
+            | HandleMsg::AcceptAdmin {}
+            | HandleMsg::CancelAdminTransfer {} => {}
+            _ => return Err(error::contract_paused()),
+        },
+        ContractStatusLevel::StopMinting => {
+            if let HandleMsg::MintSecretMonero { .. } = msg {
+                return Err(error::contract_paused());
+            }
+        }
+        ContractStatusLevel::StopSwaps => {
+            if let HandleMsg::Receive { .. } = msg {
+                return Err(error::contract_paused());
+            }
+        }
+        ContractStatusLevel::Running => {}
+    }
+
+    match msg {
+        HandleMsg::MintSecretMonero {
+            proof,
+            recipient,
+            amount,
+        } => mint_sxmr(deps, env, proof, recipient, amount),
+        HandleMsg::BatchMint { mints } => batch_mint(deps, env, mints),
+        HandleMsg::Receive {
+            sender,
+            from,
+            amount,
+            msg,
+        } => burn_sxmr(deps, env, sender, from, amount, msg),
+        HandleMsg::SetViewingKey { key, .. } => set_viewing_key(deps, env, key),
+        HandleMsg::CreateViewingKey { entropy } => create_viewing_key(deps, env, entropy),
+        HandleMsg::SetMinters { minters } => set_minters(deps, env, minters),
+        HandleMsg::AddMinters { minters } => add_minters(deps, env, minters),
+        HandleMsg::RemoveMinters { minters } => remove_minters(deps, env, minters),
+        HandleMsg::SetFeeCollector { address } => set_fee_collector(deps, env, address),
+        HandleMsg::SweepFees {} => sweep_fees(deps, env),
+        HandleMsg::SetFee { fee_bps } => set_fee(deps, env, fee_bps),
+        HandleMsg::SetMaxSwap { amount } => set_max_swap(deps, env, amount),
+        HandleMsg::SetMintBounds { min, max } => set_mint_bounds(deps, env, min, max),
+        HandleMsg::SetUnitGranularity { granularity } => {
+            set_unit_granularity(deps, env, granularity)
+        }
+        HandleMsg::SetMintRateLimit { limit, window_blocks } => {
+            set_mint_rate_limit(deps, env, limit, window_blocks)
+        }
+        HandleMsg::SetMintThreshold { threshold } => set_mint_threshold(deps, env, threshold),
+        HandleMsg::SetMaxDestinationsPerBurn { max } => {
+            set_max_destinations_per_burn(deps, env, max)
+        }
+        HandleMsg::SetSwapLabel { nonce, label } => set_swap_label(deps, env, nonce, label),
+        HandleMsg::AttachPayoutTx { owner, nonce, monero_tx_id } => {
+            attach_payout_tx(deps, env, owner, nonce, monero_tx_id)
+        }
+        HandleMsg::CompleteSwap { owner, nonce, monero_tx_id } => {
+            complete_swap(deps, env, owner, nonce, monero_tx_id)
+        }
+        HandleMsg::ConsolidateSwaps {
+            nonces,
+            to_monero_address,
+        } => consolidate_swaps(deps, env, nonces, to_monero_address),
+        HandleMsg::RefundSwap { owner, nonce } => refund_swap(deps, env, owner, nonce),
+        HandleMsg::MarkSwapProcessing { owner, nonce } => {
+            mark_swap_processing(deps, env, owner, nonce)
+        }
+        HandleMsg::CancelSwap { nonce } => cancel_swap(deps, env, nonce),
+        HandleMsg::WhitelistDestination { to_monero_address } => {
+            whitelist_destination(deps, env, to_monero_address)
+        }
+        HandleMsg::SweepExpiredSwaps { limit } => sweep_expired_swaps(deps, env, limit),
+        HandleMsg::ReRegisterReceive {} => re_register_receive(deps, env),
+        HandleMsg::SetMaintenanceWindow {
+            start_block,
+            end_block,
+        } => set_maintenance_window(deps, env, start_block, end_block),
+        HandleMsg::ChangeAdmin { address } => change_admin(deps, env, address),
+        HandleMsg::ProposeAdmin { address } => propose_admin(deps, env, address),
+        HandleMsg::AcceptAdmin {} => accept_admin(deps, env),
+        HandleMsg::CancelAdminTransfer {} => cancel_admin_transfer(deps, env),
+        HandleMsg::SetContractStatus { level } => set_contract_status(deps, env, level),
+        HandleMsg::SetOracle { address } => set_oracle(deps, env, address),
+        HandleMsg::SubmitOracleAttestation { tx_id } => submit_oracle_attestation(deps, env, tx_id),
+        HandleMsg::RevertMint { tx_id, output_index } => revert_mint(deps, env, tx_id, output_index),
+        HandleMsg::ImportProofs { entries } => import_proofs(deps, env, entries),
+        HandleMsg::TestMint { recipient, amount } => test_mint(deps, env, recipient, amount),
+        HandleMsg::SetTimelockBlocks { blocks } => set_timelock_blocks(deps, env, blocks),
+        HandleMsg::QueueSetMinters { minters } => queue_set_minters(deps, env, minters),
+        HandleMsg::QueueSetSxmrToken {
+            address,
+            code_hash,
+            decimals,
+        } => queue_set_sxmr_token(deps, env, address, code_hash, decimals),
+        HandleMsg::QueueSetMoneroWallets { wallets } => {
+            queue_set_monero_wallets(deps, env, wallets)
+        }
+        HandleMsg::ExecutePendingAction { id } => execute_pending_action(deps, env, id),
+        HandleMsg::CancelPendingAction { id } => cancel_pending_action(deps, env, id),
+        HandleMsg::SetBridgeViewingKey { key } => set_bridge_viewing_key(deps, env, key),
+        HandleMsg::RetireBridgeViewingKey {} => retire_bridge_viewing_key(deps, env),
+        HandleMsg::ChangeViewingKey { key, .. } => change_viewing_key(deps, env, key),
+    }
+}
+
+/// Mints sXMR against a proven Monero deposit. Note: the mint message below
+/// is a fire-and-forget `CosmosMsg`, not a submessage — `secret-cosmwasm-std`
+/// 0.10 predates the `Reply`/`SubMsg` mechanism, so there is no reply payload
+/// from the token to validate here. All checks against `amount` (zero-amount,
+/// dust, duplicate proof, known-recipient) happen before this function
+/// records the proof, which is the closest equivalent safeguard available on
+/// this CosmWasm version.
+///
+/// For the same reason, a "reconciliation" follow-up that reads the
+/// recipient's post-mint balance from inside a reply is not implementable
+/// here: there is no reply to run it in, and issuing a second, synchronous
+/// query against the token mid-handler would observe pre-mint state anyway,
+/// since the mint message above hasn't executed yet when `mint_sxmr`
+/// returns. The nearest honest equivalent already exists — everything
+/// logged in the `HandleResponse` below (`amount`, `recipient`, `tx_id`) is
+/// what a relayer would reconcile against once the mint message executes.
+fn mint_sxmr<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    proof: MoneroProof,
+    recipient: HumanAddr,
+    amount: Uint128,
+) -> StdResult<HandleResponse> {
+    if amount.u128() == 0 {
+        return Err(error::zero_amount());
+    }
+
+    let mint_bounds_config = ReadonlyConfig::from_storage(&deps.storage);
+    let min_mint_amount = mint_bounds_config.min_mint_amount();
+    if min_mint_amount.u128() > 0 && amount < min_mint_amount {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "mint amount is below the minimum mint amount",
+        ));
+    }
+    let max_mint_amount = mint_bounds_config.max_mint_amount();
+    if max_mint_amount.u128() > 0 && amount > max_mint_amount {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "mint amount exceeds the maximum mint amount",
+        ));
+    }
+    crate::state::validate_unit_granularity(amount, mint_bounds_config.unit_granularity())?;
+
+    let decimal_config = ReadonlyConfig::from_storage(&deps.storage);
+    if decimal_config.enforce_decimal_alignment()
+        && !crate::state::is_decimal_aligned(amount, decimal_config.sxmr_decimals())
+    {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "mint amount does not align with the token's decimals; would create dust",
+        ));
+    }
+
+    if let Some(xmr_atomic_amount) = proof.xmr_atomic_amount {
+        let expected = crate::state::scale_xmr_to_sxmr(xmr_atomic_amount, decimal_config.sxmr_decimals());
+        if amount != expected {
+            return Err(cosmwasm_std::StdError::generic_err(
+                "mint amount does not match the proof's XMR amount scaled to sXMR decimals",
+            ));
+        }
+    }
+
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let is_authorized_minter = sender == constants.bridge_minter
+        || ReadonlyConfig::from_storage(&deps.storage).minters().contains(&sender);
+    if !is_authorized_minter {
+        return Err(error::unauthorized());
+    }
+
+    if MoneroProofsStore::is_duplicate(&deps.storage, &proof.tx_id, proof.output_index)? {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "this Monero transaction has already been used to mint sXMR",
+        ));
+    }
+
+    // `proof.address` is the Monero wallet the relayer claims the deposit
+    // landed in; without checking it against `monero_wallets`, a minter
+    // could submit an otherwise-valid proof for a deposit into some
+    // unrelated wallet the bridge never controlled.
+    if !constants.monero_wallets.iter().any(|w| w == &proof.address) {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "proof address is not one of the configured bridge Monero wallets",
+        ));
+    }
+
+    if ReadonlyConfig::from_storage(&deps.storage).oracle().is_some()
+        && !OracleAttestationsStore::has_attestation(&deps.storage, &proof.tx_id)
+    {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "this deposit has not been attested by the oracle",
+        ));
+    }
+
+    let config = ReadonlyConfig::from_storage(&deps.storage);
+    if config.enforce_monotonic_proof_order() && proof.block_height < config.last_proof_height() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "proof block height is older than the last processed proof",
+        ));
+    }
+
+    let recipient_canonical = deps.api.canonical_address(&recipient)?;
+    let self_address = deps.api.canonical_address(&env.contract.address)?;
+    if recipient_canonical == self_address || recipient_canonical == constants.sxmr.address {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "cannot mint to the bridge contract or the sXMR token contract itself",
+        ));
+    }
+    if ReadonlyConfig::from_storage(&deps.storage).require_known_recipient()
+        && !crate::state::is_known_recipient(&deps.storage, &recipient_canonical)
+    {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "recipient has never interacted with the bridge; refusing a cold-address mint",
+        ));
+    }
+
+    let mint_threshold = ReadonlyConfig::from_storage(&deps.storage).mint_threshold();
+    if mint_threshold > 1 {
+        let approvals = crate::state::MintApprovalsStore::record_approval(
+            &mut deps.storage,
+            &proof.tx_id,
+            &recipient_canonical,
+            amount,
+            &sender,
+        )?;
+        if approvals < mint_threshold {
+            return Ok(HandleResponse {
+                messages: vec![],
+                log: vec![
+                    log("action", "mint_approval_recorded"),
+                    log("tx_id", proof.tx_id.clone()),
+                    log("approvals", approvals),
+                    log("threshold", mint_threshold),
+                ],
+                data: Some(to_binary(&HandleResult::MintSecretMonero {
+                    status: "pending_approval".to_string(),
+                    token_address: None,
+                    token_contract_hash: None,
+                    auto_viewing_key: None,
+                    tx_id: proof.tx_id.clone(),
+                    recipient: recipient.clone(),
+                    amount,
+                })?),
+            });
+        }
+        crate::state::MintApprovalsStore::clear(&mut deps.storage, &proof.tx_id);
+    }
+
+    let mint_limit_per_window = ReadonlyConfig::from_storage(&deps.storage).mint_limit_per_window();
+    let mint_window_blocks = ReadonlyConfig::from_storage(&deps.storage).mint_window_blocks();
+    crate::state::MintLimitsStore::charge(
+        &mut deps.storage,
+        &recipient_canonical,
+        amount,
+        mint_limit_per_window,
+        mint_window_blocks,
+        env.block.height,
+    )?;
+
+    let mut proof = proof;
+    proof.amount = amount;
+    MoneroProofsStore::save(&mut deps.storage, &proof)?;
+    MintRecordStore::save(
+        &mut deps.storage,
+        &proof.tx_id,
+        proof.output_index,
+        &recipient_canonical,
+        amount,
+    )?;
+
+    let mut liability_config = Config::from_storage(&mut deps.storage);
+    let pending_liability = liability_config.pending_liability();
+    liability_config.set_pending_liability(pending_liability + amount);
+    liability_config.record_mint(amount)?;
+
+    if config.enforce_monotonic_proof_order() {
+        Config::from_storage(&mut deps.storage).set_last_proof_height(proof.block_height);
+    }
+
+    let mint_msg = snip20_mint_msg(&constants.sxmr, recipient.clone(), amount)?;
+    let (token_address, token_contract_hash) = token_info_for_result(deps, &constants.sxmr)?;
+    let auto_viewing_key = auto_viewing_key_for_mint(
+        deps,
+        &constants,
+        &recipient_canonical,
+        &proof.tx_id,
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![mint_msg],
+        log: vec![
+            log("action", "mint_secret_monero"),
+            log("tx_id", proof.tx_id.clone()),
+            log("recipient", recipient.clone()),
+            log("amount", amount),
+            log("block_height", proof.block_height),
+            log("output_index", proof.output_index),
+        ],
+        data: Some(to_binary(&HandleResult::MintSecretMonero {
+            status: "success".to_string(),
+            token_address,
+            token_contract_hash,
+            auto_viewing_key,
+            tx_id: proof.tx_id,
+            recipient,
+            amount,
+        })?),
+    })
+}
+
+/// Upper bound on `HandleMsg::BatchMint`'s `mints`, so one transaction can't
+/// be used to force an unbounded amount of work (and gas) onto a single
+/// block.
+const MAX_BATCH_MINT_SIZE: usize = 20;
+
+/// Mints every item in `mints` by calling `mint_sxmr` for each in order,
+/// after an up-front duplicate check across the whole batch (both against
+/// already-stored proofs and against each other) so an obviously-doomed
+/// batch fails before touching storage. Beyond that, atomicity falls out of
+/// `handle`'s existing all-or-nothing semantics: `?` on any item's
+/// `mint_sxmr` call aborts the whole transaction, discarding every state
+/// change this batch made so far, not just that item's.
+fn batch_mint<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    mints: Vec<crate::msg::MintItem>,
+) -> StdResult<HandleResponse> {
+    if mints.is_empty() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "batch mint must include at least one item",
+        ));
+    }
+    if mints.len() > MAX_BATCH_MINT_SIZE {
+        return Err(cosmwasm_std::StdError::generic_err(format!(
+            "batch mint is limited to {} items",
+            MAX_BATCH_MINT_SIZE
+        )));
+    }
+
+    for (i, item) in mints.iter().enumerate() {
+        if MoneroProofsStore::is_duplicate(
+            &deps.storage,
+            &item.proof.tx_id,
+            item.proof.output_index,
+        )? {
+            return Err(cosmwasm_std::StdError::generic_err(format!(
+                "item {} is a duplicate of an already-recorded proof",
+                i
+            )));
+        }
+        let repeated_within_batch = mints[..i].iter().any(|other| {
+            other.proof.tx_id == item.proof.tx_id
+                && other.proof.output_index == item.proof.output_index
+        });
+        if repeated_within_batch {
+            return Err(cosmwasm_std::StdError::generic_err(format!(
+                "item {} duplicates an earlier item in the same batch",
+                i
+            )));
+        }
+    }
+
+    let mut messages = vec![];
+    let mut log_entries = vec![log("action", "batch_mint"), log("count", mints.len())];
+    let mut tx_ids = vec![];
+    for item in mints {
+        let response = mint_sxmr(deps, env.clone(), item.proof, item.recipient, item.amount)?;
+        messages.extend(response.messages);
+        log_entries.extend(response.log);
+        if let Some(data) = response.data {
+            if let HandleResult::MintSecretMonero { tx_id, .. } = cosmwasm_std::from_binary(&data)? {
+                tx_ids.push(tx_id);
+            }
+        }
+    }
+
+    Ok(HandleResponse {
+        messages,
+        log: log_entries,
+        data: Some(to_binary(&HandleResult::BatchMint { tx_ids })?),
+    })
+}
+
+/// When `auto_vk_on_mint` is on and `recipient` has never set a viewing key,
+/// generates one deterministically from the bridge's `prng_seed` and the
+/// deposit's `tx_id` (so a retried mint of the same proof can't generate a
+/// different key), sets it on-chain, and returns the plaintext for inclusion
+/// in the mint result.
+///
+/// Privacy note: `MintSecretMonero` is submitted by the `bridge_minter`
+/// relayer, not the recipient, so the plaintext key returned here is visible
+/// to the relayer and to anyone who can read the transaction's response data
+/// (typically also the block explorer/node operators), not just the
+/// recipient. `secret-cosmwasm-std` 0.10 has no primitive for encrypting
+/// response data to an arbitrary recipient key, so this should only be
+/// enabled where the relayer is trusted, or is expected to relay the key to
+/// the recipient out-of-band over an already-private channel.
+fn auto_viewing_key_for_mint<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    constants: &Constants,
+    recipient: &cosmwasm_std::CanonicalAddr,
+    tx_id: &str,
+) -> StdResult<Option<String>> {
+    if !ReadonlyConfig::from_storage(&deps.storage).auto_vk_on_mint()
+        || crate::state::is_known_recipient(&deps.storage, recipient)
+    {
+        return Ok(None);
+    }
+
+    use crate::state::PREFIX_VIEWING_KEY;
+    use crate::viewing_key::ViewingKey;
+    use cosmwasm_storage::PrefixedStorage;
+
+    let vk = ViewingKey::new(&constants.prng_seed, tx_id.as_bytes());
+    let mut store = PrefixedStorage::new(PREFIX_VIEWING_KEY, &mut deps.storage);
+    store.set(recipient.as_slice(), &vk.to_hashed());
+
+    Ok(Some(vk.0))
+}
+
+/// When `include_token_info_in_result` is on, resolves `token`'s address and
+/// contract hash so clients can skip a separate `Config` query.
+fn token_info_for_result<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    token: &TokenInfo,
+) -> StdResult<(Option<HumanAddr>, Option<String>)> {
+    if !ReadonlyConfig::from_storage(&deps.storage).include_token_info_in_result() {
+        return Ok((None, None));
+    }
+    Ok((
+        Some(deps.api.human_address(&token.address)?),
+        Some(token.contract_hash.clone()),
+    ))
+}
+
+/// Longest a burn's memo may be, in bytes, once forwarded via
+/// `BurnDestination::WithMemo`. See `burn_sxmr`.
+const MAX_BURN_MEMO_LEN: usize = 256;
+
+/// Upper bound `query_export_swaps` clamps `limit` to, so a migration script
+/// can't force one query to walk an unbounded number of swaps.
+pub(crate) const MAX_EXPORT_PAGE_SIZE: u32 = 200;
+
+fn burn_sxmr<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    sender: HumanAddr,
+    from: HumanAddr,
+    amount: Uint128,
+    msg: Option<Binary>,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let token_address = deps.api.canonical_address(&env.message.sender)?;
+    if token_address != constants.sxmr.address {
+        return Err(error::unauthorized());
+    }
+
+    // Catches a reconfiguration that swapped `constants.sxmr` for a token of
+    // a different decimal scale without also updating the active
+    // `sxmr_decimals` (or vice versa) — without this, `amount` below would be
+    // interpreted at the wrong scale instead of being rejected outright.
+    if constants.sxmr.decimals != ReadonlyConfig::from_storage(&deps.storage).sxmr_decimals() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "the configured sXMR token's recorded decimals do not match the active sxmr_decimals setting",
+        ));
+    }
+
+    // On SNIP-20, `sender` (who invoked `Send`) and `from` (the token owner)
+    // can differ, e.g. an allowance-based transfer. Trusting `from` alone as
+    // the swap owner would let a third party record someone else's address
+    // as the owner of a burn they didn't initiate.
+    if ReadonlyConfig::from_storage(&deps.storage).require_sender_equals_from() && sender != from {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "sender must match from for this bridge's burn policy",
+        ));
+    }
+
+    if amount < constants.min_swap_amount {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "amount is below the minimum swap amount",
+        ));
+    }
+
+    let max_swap_amount = ReadonlyConfig::from_storage(&deps.storage).max_swap_amount();
+    if max_swap_amount.u128() > 0 && amount > max_swap_amount {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "amount exceeds the maximum swap amount",
+        ));
+    }
+
+    let fee_bps = ReadonlyConfig::from_storage(&deps.storage).fee_bps();
+    let fee_taken = Uint128(amount.u128() * fee_bps as u128 / 10_000);
+    let net_amount = Uint128(amount.u128() - fee_taken.u128());
+
+    if net_amount.is_zero() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "the net amount after the configured fee would be zero; increase the amount",
+        ));
+    }
+
+    crate::state::validate_unit_granularity(
+        net_amount,
+        ReadonlyConfig::from_storage(&deps.storage).unit_granularity(),
+    )?;
+
+    let sxmr_decimals = ReadonlyConfig::from_storage(&deps.storage).sxmr_decimals();
+    let xmr_atomic_amount = crate::state::scale_sxmr_to_xmr(net_amount, sxmr_decimals)?;
+
+    let dust_limit = ReadonlyConfig::from_storage(&deps.storage).monero_dust_limit();
+    if dust_limit.u128() > 0 && net_amount < dust_limit {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "net deliverable amount is below the Monero dust limit",
+        ));
+    }
+
+    if let Some((start_block, end_block)) =
+        ReadonlyConfig::from_storage(&deps.storage).maintenance_window()
+    {
+        if env.block.height >= start_block && env.block.height <= end_block {
+            return Err(cosmwasm_std::StdError::generic_err(format!(
+                "burns are paused for scheduled maintenance until block {}",
+                end_block
+            )));
+        }
+    }
+
+    let destination: crate::msg::BurnDestination = match msg {
+        Some(bin) => cosmwasm_std::from_binary(&bin)?,
+        None => return Err(cosmwasm_std::StdError::generic_err("missing destination address")),
+    };
+
+    let (destination, memo) = match destination {
+        crate::msg::BurnDestination::WithMemo { destination, memo } => {
+            if matches!(*destination, crate::msg::BurnDestination::WithMemo { .. }) {
+                return Err(cosmwasm_std::StdError::generic_err(
+                    "WithMemo cannot wrap another WithMemo",
+                ));
+            }
+            let memo = sxmr_token::memo::validate_and_normalize_memo(&memo, 0, MAX_BURN_MEMO_LEN)?;
+            (*destination, Some(memo))
+        }
+        other => (other, None),
+    };
+
+    let (to_monero_address, destinations, payment_id) = match destination {
+        crate::msg::BurnDestination::Single(addr) => (addr, vec![], None),
+        crate::msg::BurnDestination::SingleWithPaymentId { address, payment_id } => {
+            if is_integrated_monero_address(&address) {
+                return Err(cosmwasm_std::StdError::generic_err(
+                    "address is already an integrated address; it cannot also carry a separate payment_id",
+                ));
+            }
+            validate_payment_id(&payment_id)?;
+            (address, vec![], Some(payment_id))
+        }
+        crate::msg::BurnDestination::Multi(dests) => {
+            if dests.is_empty() {
+                return Err(cosmwasm_std::StdError::generic_err(
+                    "multi-destination burn must list at least one destination",
+                ));
+            }
+            let max_destinations = ReadonlyConfig::from_storage(&deps.storage).max_destinations_per_burn();
+            if max_destinations > 0 && dests.len() as u32 > max_destinations {
+                return Err(cosmwasm_std::StdError::generic_err(format!(
+                    "multi-destination burn lists more than the maximum of {} destinations",
+                    max_destinations
+                )));
+            }
+            if dust_limit.u128() > 0 {
+                if let Some((_, amt)) = dests.iter().find(|(_, amt)| *amt < dust_limit) {
+                    return Err(cosmwasm_std::StdError::generic_err(format!(
+                        "destination amount {} is below the Monero dust limit",
+                        amt
+                    )));
+                }
+            }
+            let sum = dests
+                .iter()
+                .try_fold(0u128, |acc, (_, amt)| acc.checked_add(amt.u128()))
+                .ok_or_else(|| cosmwasm_std::StdError::generic_err("destination amounts overflow"))?;
+            if sum != net_amount.u128() {
+                return Err(cosmwasm_std::StdError::generic_err(
+                    "destination amounts must sum to the burned net amount",
+                ));
+            }
+            let first = dests[0].0.clone();
+            (first, dests, None)
+        }
+    };
+
+    let from_canonical = deps.api.canonical_address(&from)?;
+
+    if BlockedDestinationsStore::is_blocked(&deps.storage, &to_monero_address) {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "destination address is blocked",
+        ));
+    }
+    if !WhitelistedDestinationsStore::is_whitelisted(&deps.storage, &from_canonical, &to_monero_address)
+    {
+        validate_monero_address(&to_monero_address)?;
+    }
+
+    let swap = SwapDetails {
+        from_secret_address: from_canonical,
+        to_monero_address: to_monero_address.clone(),
+        payment_id,
+        memo,
+        amount: net_amount,
+        xmr_atomic_amount,
+        fee_taken,
+        monero_tx_id: None,
+        destinations,
+        label: None,
+        created_at: env.block.time,
+        resolved: false,
+        nonce: 0,            // overwritten by SwapDetailsStore::save
+        swap_id: String::new(), // overwritten by SwapDetailsStore::save
+        fee_bps_at_creation: 0, // overwritten by SwapDetailsStore::save
+        scale_at_creation: 0,   // overwritten by SwapDetailsStore::save
+        status: crate::state::SwapStatus::Pending,
+    };
+    let nonce = SwapDetailsStore::save(&mut deps.storage, &swap)?;
+    ReceiptIndexStore::save(&mut deps.storage, &swap.receipt_hash(), &swap.from_secret_address, nonce)?;
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    let mut counts = config.swap_counts();
+    counts.pending += 1;
+    config.set_swap_counts(&counts);
+    let pending_liability = config.pending_liability();
+    config.set_pending_liability(Uint128(pending_liability.u128().saturating_sub(net_amount.u128())));
+    let accumulated_fees = config.accumulated_fees();
+    config.set_accumulated_fees(Uint128(accumulated_fees.u128() + fee_taken.u128()));
+    config.record_burn(net_amount)?;
+    drop(config);
+
+    let mut messages = vec![snip20_burn_msg(&constants.sxmr, net_amount)?];
+    if let Some(sweep_msg) = maybe_sweep_fees(deps)? {
+        messages.push(sweep_msg);
+    }
+    let (token_address, token_contract_hash) = token_info_for_result(deps, &constants.sxmr)?;
+
+    let mut logs = vec![
+        log("action", "burn_secret_monero"),
+        log("nonce", nonce),
+        log("to_monero_address", to_monero_address),
+        log("amount", net_amount),
+        log("fee", fee_taken),
+    ];
+    if !ReadonlyConfig::from_storage(&deps.storage).require_sender_equals_from() {
+        logs.push(log("sender", sender));
+        logs.push(log("from", from));
+    }
+
+    Ok(HandleResponse {
+        messages,
+        log: logs,
+        data: Some(to_binary(&HandleResult::Receive {
+            status: "success".to_string(),
+            token_address,
+            token_contract_hash,
+        })?),
+    })
+}
+
+/// When accumulated fees cross `fee_sweep_threshold`, emits a `Transfer` of
+/// the full accumulated amount (already sitting in the bridge's own sXMR
+/// balance, withheld from each burn's gross amount) to the `fee_collector`
+/// and resets the accrual, so operators don't have to manually sweep. A
+/// threshold of zero disables auto-sweeping.
+fn maybe_sweep_fees<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+) -> StdResult<Option<CosmosMsg>> {
+    let mut config = Config::from_storage(&mut deps.storage);
+    let threshold = config.fee_sweep_threshold();
+    if threshold.u128() == 0 {
+        return Ok(None);
+    }
+    let accumulated = config.accumulated_fees();
+    if accumulated < threshold {
+        return Ok(None);
+    }
+    let collector = match config.fee_collector() {
+        Some(c) => c,
+        None => return Ok(None),
+    };
+    config.set_accumulated_fees(Uint128::zero());
+    drop(config);
+
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let collector_human = deps.api.human_address(&collector)?;
+    Ok(Some(snip20_transfer_msg(&constants.sxmr, collector_human, accumulated)?))
+}
+
+fn set_viewing_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    key: String,
+) -> StdResult<HandleResponse> {
+    use crate::state::PREFIX_VIEWING_KEY;
+    use crate::viewing_key::ViewingKey;
+    use cosmwasm_storage::PrefixedStorage;
+
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let vk = ViewingKey(key);
+    let mut store = PrefixedStorage::new(PREFIX_VIEWING_KEY, &mut deps.storage);
+    store.set(sender.as_slice(), &vk.to_hashed());
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetViewingKey {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Rotates the caller's own stored viewing key, distinct from the
+/// admin-only, contract-wide `bridge_viewing_key` path (`SetBridgeViewingKey`
+/// / `RetireBridgeViewingKey`). Stores the hash exactly like
+/// `set_viewing_key`; the two exist as separate messages only because
+/// SNIP-20 clients expect both, not because the underlying effect differs.
+fn change_viewing_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    key: String,
+) -> StdResult<HandleResponse> {
+    use crate::state::PREFIX_VIEWING_KEY;
+    use crate::viewing_key::ViewingKey;
+    use cosmwasm_storage::PrefixedStorage;
+
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let vk = ViewingKey(key);
+    let mut store = PrefixedStorage::new(PREFIX_VIEWING_KEY, &mut deps.storage);
+    store.set(sender.as_slice(), &vk.to_hashed());
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::ChangeViewingKey {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Derives a viewing key on-chain instead of requiring the caller to invent
+/// one, mirroring SNIP-20's `CreateViewingKey`. Mixes the bridge's
+/// `prng_seed`, the sender's address, `env.block`'s height and time, and the
+/// caller-supplied `entropy` into `ViewingKey::new`'s entropy input, so two
+/// callers (or two calls from the same caller in different blocks) can't
+/// collide even with the same `entropy` string.
+fn create_viewing_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    entropy: String,
+) -> StdResult<HandleResponse> {
+    use crate::state::PREFIX_VIEWING_KEY;
+    use crate::viewing_key::ViewingKey;
+    use cosmwasm_storage::PrefixedStorage;
+
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+
+    let mut seed_material = sender.as_slice().to_vec();
+    seed_material.extend_from_slice(&env.block.height.to_be_bytes());
+    seed_material.extend_from_slice(&env.block.time.to_be_bytes());
+    seed_material.extend_from_slice(entropy.as_bytes());
+
+    let vk = ViewingKey::new(&constants.prng_seed, &seed_material);
+    let mut store = PrefixedStorage::new(PREFIX_VIEWING_KEY, &mut deps.storage);
+    store.set(sender.as_slice(), &vk.to_hashed());
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::CreateViewingKey {
+            key: vk.to_string(),
+        })?),
+    })
+}
+
+fn set_minters<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    minters: Vec<HumanAddr>,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let minters_canonical = minters
+        .iter()
+        .map(|m| deps.api.canonical_address(m))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let max_minters = ReadonlyConfig::from_storage(&deps.storage).max_minters();
+    if minters_canonical.len() as u32 > max_minters {
+        return Err(cosmwasm_std::StdError::generic_err(format!(
+            "minter list exceeds the cap of {}",
+            max_minters
+        )));
+    }
+
+    Config::from_storage(&mut deps.storage).set_minters(minters_canonical);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetMinters {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: adds `minters` to `Config::minters()` without disturbing the
+/// rest of the list. See `HandleMsg::AddMinters`.
+fn add_minters<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    minters: Vec<HumanAddr>,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let mut current = ReadonlyConfig::from_storage(&deps.storage).minters();
+    for minter in &minters {
+        let canonical = deps.api.canonical_address(minter)?;
+        if !current.contains(&canonical) {
+            current.push(canonical);
+        }
+    }
+
+    let max_minters = ReadonlyConfig::from_storage(&deps.storage).max_minters();
+    if current.len() as u32 > max_minters {
+        return Err(cosmwasm_std::StdError::generic_err(format!(
+            "minter list exceeds the cap of {}",
+            max_minters
+        )));
+    }
+
+    Config::from_storage(&mut deps.storage).set_minters(current);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::AddMinters {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: removes `minters` from `Config::minters()`, leaving any
+/// address not currently in the list untouched. See
+/// `HandleMsg::RemoveMinters`.
+fn remove_minters<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    minters: Vec<HumanAddr>,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let to_remove = minters
+        .iter()
+        .map(|m| deps.api.canonical_address(m))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let current = ReadonlyConfig::from_storage(&deps.storage).minters();
+    let remaining = current
+        .into_iter()
+        .filter(|m| !to_remove.contains(m))
+        .collect();
+
+    Config::from_storage(&mut deps.storage).set_minters(remaining);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::RemoveMinters {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only; queues `action` to take effect `timelock_blocks` from now
+/// (immediately, if the timelock is disabled). Shared by every `Queue*`
+/// handler below.
+fn queue_timelocked_action<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: &Env,
+    action: TimelockedAction,
+) -> StdResult<u32> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let timelock_blocks = ReadonlyConfig::from_storage(&deps.storage).timelock_blocks();
+    let ready_at_block = env.block.height + timelock_blocks;
+    PendingActionStore::queue(&mut deps.storage, action, ready_at_block)
+}
+
+fn set_timelock_blocks<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    blocks: u64,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    Config::from_storage(&mut deps.storage).set_timelock_blocks(blocks);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetTimelockBlocks {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn queue_set_minters<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    minters: Vec<HumanAddr>,
+) -> StdResult<HandleResponse> {
+    let max_minters = ReadonlyConfig::from_storage(&deps.storage).max_minters();
+    if minters.len() as u32 > max_minters {
+        return Err(cosmwasm_std::StdError::generic_err(format!(
+            "minters would exceed the configured cap of {}",
+            max_minters
+        )));
+    }
+    let minters_canonical = minters
+        .iter()
+        .map(|m| deps.api.canonical_address(m))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let id = queue_timelocked_action(
+        deps,
+        &env,
+        TimelockedAction::SetMinters {
+            minters: minters_canonical,
+        },
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "queue_set_minters"), log("id", id)],
+        data: Some(to_binary(&HandleResult::QueueSetMinters { id })?),
+    })
+}
+
+fn queue_set_sxmr_token<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    address: HumanAddr,
+    code_hash: String,
+    decimals: u8,
+) -> StdResult<HandleResponse> {
+    let canonical = deps.api.canonical_address(&address)?;
+    let id = queue_timelocked_action(
+        deps,
+        &env,
+        TimelockedAction::SetSxmrToken {
+            address: canonical,
+            code_hash,
+            decimals,
+        },
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "queue_set_sxmr_token"), log("id", id)],
+        data: Some(to_binary(&HandleResult::QueueSetSxmrToken { id })?),
+    })
+}
+
+fn queue_set_monero_wallets<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    wallets: Vec<String>,
+) -> StdResult<HandleResponse> {
+    if wallets.is_empty() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "monero_wallets must not be empty",
+        ));
+    }
+
+    let id = queue_timelocked_action(
+        deps,
+        &env,
+        TimelockedAction::SetMoneroWallets { wallets },
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "queue_set_monero_wallets"), log("id", id)],
+        data: Some(to_binary(&HandleResult::QueueSetMoneroWallets { id })?),
+    })
+}
+
+fn execute_pending_action<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    id: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let pending = PendingActionStore::get(&deps.storage, id)
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("no pending action with that id"))?;
+    if env.block.height < pending.ready_at_block {
+        return Err(cosmwasm_std::StdError::generic_err(format!(
+            "timelock has not elapsed; executable at block {}",
+            pending.ready_at_block
+        )));
+    }
+
+    match pending.action {
+        TimelockedAction::SetMinters { minters } => {
+            Config::from_storage(&mut deps.storage).set_minters(minters);
+        }
+        TimelockedAction::SetSxmrToken {
+            address,
+            code_hash,
+            decimals,
+        } => {
+            // The old token's swaps recorded their `scale_at_creation` from
+            // the scale being replaced here; letting them resolve afterward
+            // under the new scale would mis-scale their payout.
+            if ReadonlyConfig::from_storage(&deps.storage).swap_counts().pending > 0 {
+                return Err(cosmwasm_std::StdError::generic_err(
+                    "cannot change the sXMR token while swaps are pending; their recorded scale would no longer match",
+                ));
+            }
+
+            let mut config = Config::from_storage(&mut deps.storage);
+            let mut constants = config.constants()?;
+            constants.sxmr = TokenInfo {
+                address,
+                contract_hash: code_hash,
+                decimals,
+            };
+            config.set_constants(&constants)?;
+            // Keeps the active scale in lockstep with the token just set, so
+            // `burn_sxmr`'s decimals-drift check (see `TokenInfo::decimals`)
+            // never fires from this path itself.
+            config.set_sxmr_decimals(decimals);
+        }
+        TimelockedAction::SetMoneroWallets { wallets } => {
+            let mut config = Config::from_storage(&mut deps.storage);
+            let mut constants = config.constants()?;
+            constants.monero_wallets = wallets;
+            config.set_constants(&constants)?;
+        }
+    }
+    PendingActionStore::remove(&mut deps.storage, id);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "execute_pending_action"), log("id", id)],
+        data: Some(to_binary(&HandleResult::ExecutePendingAction {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn cancel_pending_action<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    id: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    if PendingActionStore::get(&deps.storage, id).is_none() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "no pending action with that id",
+        ));
+    }
+    PendingActionStore::remove(&mut deps.storage, id);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "cancel_pending_action"), log("id", id)],
+        data: Some(to_binary(&HandleResult::CancelPendingAction {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// How many blocks after a `SetBridgeViewingKey` rotation the previous key
+/// stays acceptable as a `query_sxmr_balance` fallback. The `SetViewingKey`
+/// message pushed to the token by this same handler only takes effect once
+/// its `CosmosMsg` executes, so a `SolvencyCheck` run in that gap would
+/// otherwise see the new key rejected by the token before it lands.
+const BRIDGE_VIEWING_KEY_OVERLAP_BLOCKS: u64 = 100;
+
+fn set_bridge_viewing_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    key: String,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    if let Some(old_key) = config.bridge_viewing_key() {
+        config.set_prev_bridge_viewing_key(Some((
+            old_key,
+            env.block.height + BRIDGE_VIEWING_KEY_OVERLAP_BLOCKS,
+        )));
+    }
+    config.set_bridge_viewing_key(&key);
+
+    let set_key_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: HumanAddr::from(constants.sxmr.address.to_string()),
+        callback_code_hash: constants.sxmr.contract_hash.clone(),
+        msg: to_binary(&sxmr_token::msg::HandleMsg::SetViewingKey { key, padding: None })?,
+        send: vec![],
+    });
+
+    Ok(HandleResponse {
+        messages: vec![set_key_msg],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetBridgeViewingKey {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: drops the fallback key `SetBridgeViewingKey` keeps during its
+/// overlap window. Callable at any time; the window is a courtesy for
+/// in-flight queries, not a security boundary, so nothing checks
+/// `env.block.height` against the stored `retire_at_block` here.
+fn retire_bridge_viewing_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    Config::from_storage(&mut deps.storage).set_prev_bridge_viewing_key(None);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::RetireBridgeViewingKey {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn set_fee_collector<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    address: HumanAddr,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    if address.as_str().is_empty() {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "fee collector address must not be empty",
+        ));
+    }
+    let collector = deps.api.canonical_address(&address)?;
+    Config::from_storage(&mut deps.storage).set_fee_collector(&collector);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetFeeCollector {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: forces `maybe_sweep_fees`'s transfer regardless of
+/// `fee_sweep_threshold`, for an operator who doesn't want to wait for the
+/// next burn to cross it. A no-op (rather than an error) when there's
+/// nothing accumulated or no collector configured, mirroring
+/// `maybe_sweep_fees`'s own early-outs.
+fn sweep_fees<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    let accumulated = config.accumulated_fees();
+    let collector = config.fee_collector();
+    let messages = match collector {
+        Some(collector) if accumulated.u128() > 0 => {
+            config.set_accumulated_fees(Uint128::zero());
+            drop(config);
+            let collector_human = deps.api.human_address(&collector)?;
+            vec![snip20_transfer_msg(&constants.sxmr, collector_human, accumulated)?]
+        }
+        _ => vec![],
+    };
+    let swept = if messages.is_empty() {
+        Uint128::zero()
+    } else {
+        accumulated
+    };
+
+    Ok(HandleResponse {
+        messages,
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SweepFees { swept })?),
+    })
+}
+
+fn set_fee<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    fee_bps: u16,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    if fee_bps > crate::state::MAX_FEE_BPS {
+        return Err(cosmwasm_std::StdError::generic_err(format!(
+            "fee_bps must not exceed {}",
+            crate::state::MAX_FEE_BPS
+        )));
+    }
+    Config::from_storage(&mut deps.storage).set_fee_bps(fee_bps);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetFee {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: sets `ReadonlyConfig::max_swap_amount`, the upper bound
+/// `burn_sxmr` enforces on a burn's gross amount. `0` (the default) means no
+/// cap. Existing `Pending` swaps are never rejected or mutated by this — the
+/// response's `stale_pending_count` is only a heads-up for how many of them
+/// now fall outside `[min_swap_amount, amount]`. `min_swap_amount` has no
+/// equivalent setter in this contract (it's fixed at `init`), so there's no
+/// analogous heads-up to add there.
+fn set_max_swap<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    amount: Uint128,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let stale_pending_count = ReadonlySwapDetailsStore::count_pending_outside_bounds(
+        &deps.storage,
+        constants.min_swap_amount,
+        amount,
+    )?;
+
+    Config::from_storage(&mut deps.storage).set_max_swap_amount(amount);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetMaxSwap {
+            status: "success".to_string(),
+            stale_pending_count,
+        })?),
+    })
+}
+
+/// Admin-only: sets `Config::min_mint_amount`/`Config::max_mint_amount`, the
+/// circuit breaker `mint_sxmr` enforces on a single mint's amount,
+/// independent of the swap-side `min_swap_amount`/`max_swap_amount`. A zero
+/// `max` means unbounded.
+fn set_mint_bounds<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    min: Uint128,
+    max: Uint128,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    if max.u128() > 0 && min > max {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "min mint amount must not exceed the max mint amount",
+        ));
+    }
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    config.set_min_mint_amount(min);
+    config.set_max_mint_amount(max);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetMintBounds {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: sets `Config::unit_granularity`. See
+/// `HandleMsg::SetUnitGranularity`.
+fn set_unit_granularity<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    granularity: Uint128,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    Config::from_storage(&mut deps.storage).set_unit_granularity(granularity);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetUnitGranularity {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: sets `Config::mint_limit_per_window`/`Config::mint_window_blocks`.
+/// See `HandleMsg::SetMintRateLimit`.
+fn set_mint_rate_limit<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    limit: Uint128,
+    window_blocks: u64,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    config.set_mint_limit_per_window(limit);
+    config.set_mint_window_blocks(window_blocks);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetMintRateLimit {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: sets `Config::mint_threshold`. See `HandleMsg::SetMintThreshold`.
+fn set_mint_threshold<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    threshold: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+    if threshold == 0 {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "mint threshold must be at least 1",
+        ));
+    }
+
+    Config::from_storage(&mut deps.storage).set_mint_threshold(threshold);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetMintThreshold {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: sets `ReadonlyConfig::max_destinations_per_burn`, the cap
+/// `burn_sxmr` enforces on a `BurnDestination::Multi` burn's destination
+/// count. `0` (the default) means no cap.
+fn set_max_destinations_per_burn<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    max: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    Config::from_storage(&mut deps.storage).set_max_destinations_per_burn(max);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetMaxDestinationsPerBurn {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn set_maintenance_window<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    start_block: u64,
+    end_block: u64,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    if start_block > end_block {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "start_block must not be after end_block",
+        ));
+    }
+
+    Config::from_storage(&mut deps.storage).set_maintenance_window(Some((start_block, end_block)));
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetMaintenanceWindow {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// A loose plausibility check on a Monero address: non-empty and not
+/// absurdly long. Deliberately permissive so callers can run this bridge
+/// against testnets and local regtest wallets with short mock addresses.
+fn validate_monero_address(address: &str) -> StdResult<()> {
+    if address.is_empty() || address.len() > 128 {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "destination address is not a plausible Monero address",
+        ));
+    }
+    Ok(())
+}
+
+/// An integrated Monero address is a standard address with an 8-byte payment
+/// id baked into its public key bytes, which bumps its base58 length from
+/// the standard 95 characters to 106. No Monero address codec is vendored
+/// here to decode this properly, so (consistent with `validate_monero_address`'s
+/// own simplistic plausibility check) this is a length heuristic, not a real
+/// base58 decode.
+fn is_integrated_monero_address(address: &str) -> bool {
+    address.len() > 99
+}
+
+/// Monero payment ids are 8 raw bytes, conventionally hex-encoded.
+fn validate_payment_id(payment_id: &str) -> StdResult<()> {
+    if payment_id.len() != 16 || !payment_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "payment_id must be 16 hex characters (8 bytes)",
+        ));
+    }
+    Ok(())
+}
+
+fn whitelist_destination<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    to_monero_address: String,
+) -> StdResult<HandleResponse> {
+    validate_monero_address(&to_monero_address)?;
+    let owner = deps.api.canonical_address(&env.message.sender)?;
+    WhitelistedDestinationsStore::whitelist(&mut deps.storage, &owner, &to_monero_address);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::WhitelistDestination {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn set_swap_label<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    nonce: u32,
+    label: String,
+) -> StdResult<HandleResponse> {
+    let owner = deps.api.canonical_address(&env.message.sender)?;
+    SwapDetailsStore::set_label(&mut deps.storage, &owner, nonce, label)?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetSwapLabel {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Backfills `monero_tx_id` onto `owner`'s swap at `nonce`. Callable by the
+/// admin or the `bridge_minter` relayer; only the admin may overwrite a hash
+/// that's already attached.
+fn attach_payout_tx<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    owner: HumanAddr,
+    nonce: u32,
+    monero_tx_id: String,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin && sender != constants.bridge_minter {
+        return Err(error::unauthorized());
+    }
+    let is_admin = sender == constants.admin;
+
+    let owner = deps.api.canonical_address(&owner)?;
+    let newly_resolved =
+        SwapDetailsStore::attach_payout_tx(&mut deps.storage, &owner, nonce, monero_tx_id, is_admin)?;
+
+    if newly_resolved {
+        let mut config = Config::from_storage(&mut deps.storage);
+        let mut counts = config.swap_counts();
+        counts.pending = counts.pending.saturating_sub(1);
+        counts.fulfilled += 1;
+        config.set_swap_counts(&counts);
+    }
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::AttachPayoutTx {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Flips `owner`'s swap at `nonce` to `SwapStatus::Completed`. Callable by
+/// the admin or the `bridge_minter` relayer; errors if the swap is already
+/// completed.
+fn complete_swap<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    owner: HumanAddr,
+    nonce: u32,
+    monero_tx_id: String,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin && sender != constants.bridge_minter {
+        return Err(error::unauthorized());
+    }
+
+    let owner = deps.api.canonical_address(&owner)?;
+    let newly_resolved = SwapDetailsStore::complete_swap(&mut deps.storage, &owner, nonce, monero_tx_id)?;
+
+    if newly_resolved {
+        let mut config = Config::from_storage(&mut deps.storage);
+        let mut counts = config.swap_counts();
+        counts.pending = counts.pending.saturating_sub(1);
+        counts.fulfilled += 1;
+        config.set_swap_counts(&counts);
+    }
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::CompleteSwap {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Merges the caller's own `Pending` swaps at `nonces`, which must all target
+/// `to_monero_address`, into one new swap for their summed amount.
+/// Owner-only; callable on the caller's own swaps only.
+fn consolidate_swaps<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    nonces: Vec<u32>,
+    to_monero_address: String,
+) -> StdResult<HandleResponse> {
+    let owner = deps.api.canonical_address(&env.message.sender)?;
+    let merged_count = nonces.len() as u64;
+    let consolidated = SwapDetailsStore::consolidate(
+        &mut deps.storage,
+        &owner,
+        &nonces,
+        to_monero_address,
+        env.block.time,
+    )?;
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    let mut counts = config.swap_counts();
+    counts.pending = counts.pending.saturating_sub(merged_count - 1);
+    config.set_swap_counts(&counts);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![
+            log("action", "consolidate_swaps"),
+            log("new_nonce", consolidated.nonce),
+            log("amount", consolidated.amount),
+        ],
+        data: Some(to_binary(&HandleResult::ConsolidateSwaps {
+            status: "success".to_string(),
+            new_nonce: consolidated.nonce,
+        })?),
+    })
+}
+
+/// Re-mints `owner`'s swap at `nonce` back to sXMR and flips it to
+/// `SwapStatus::Refunded`, for a swap the bridge can't deliver XMR for (e.g.
+/// an invalid address that slipped past validation, or a liquidity issue).
+/// Callable by the admin or the `bridge_minter` relayer.
+fn refund_swap<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    owner: HumanAddr,
+    nonce: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin && sender != constants.bridge_minter {
+        return Err(error::unauthorized());
+    }
+
+    let owner_canonical = deps.api.canonical_address(&owner)?;
+    let swap = SwapDetailsStore::refund_swap(&mut deps.storage, &owner_canonical, nonce)?;
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    let mut counts = config.swap_counts();
+    counts.pending = counts.pending.saturating_sub(1);
+    counts.refunded += 1;
+    config.set_swap_counts(&counts);
+    config.record_refund(swap.amount)?;
+    let pending_liability = config.pending_liability();
+    config.set_pending_liability(pending_liability + swap.amount);
+
+    let mint_msg = snip20_mint_msg(&constants.sxmr, owner, swap.amount)?;
+
+    Ok(HandleResponse {
+        messages: vec![mint_msg],
+        log: vec![
+            log("action", "refund_swap"),
+            log("nonce", nonce),
+            log("amount", swap.amount),
+        ],
+        data: Some(to_binary(&HandleResult::RefundSwap {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin/relayer-only: flips `owner`'s `Pending` swap at `nonce` to
+/// `SwapStatus::Processing`. See `SwapDetailsStore::mark_processing`.
+fn mark_swap_processing<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    owner: HumanAddr,
+    nonce: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin && sender != constants.bridge_minter {
+        return Err(error::unauthorized());
+    }
+
+    let owner_canonical = deps.api.canonical_address(&owner)?;
+    SwapDetailsStore::mark_processing(&mut deps.storage, &owner_canonical, nonce)?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "mark_swap_processing"), log("nonce", nonce)],
+        data: Some(to_binary(&HandleResult::MarkSwapProcessing {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Self-cancel: refunds the caller's own `Pending` swap at `nonce` back to
+/// sXMR. See `SwapDetailsStore::cancel_swap`.
+fn cancel_swap<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    nonce: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let owner = env.message.sender.clone();
+    let owner_canonical = deps.api.canonical_address(&owner)?;
+    let swap = SwapDetailsStore::cancel_swap(&mut deps.storage, &owner_canonical, nonce)?;
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    let mut counts = config.swap_counts();
+    counts.pending = counts.pending.saturating_sub(1);
+    counts.refunded += 1;
+    config.set_swap_counts(&counts);
+    config.record_refund(swap.amount)?;
+    let pending_liability = config.pending_liability();
+    config.set_pending_liability(pending_liability + swap.amount);
+
+    let mint_msg = snip20_mint_msg(&constants.sxmr, owner, swap.amount)?;
+
+    Ok(HandleResponse {
+        messages: vec![mint_msg],
+        log: vec![
+            log("action", "cancel_swap"),
+            log("nonce", nonce),
+            log("amount", swap.amount),
+        ],
+        data: Some(to_binary(&HandleResult::CancelSwap {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn change_admin<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    address: HumanAddr,
+) -> StdResult<HandleResponse> {
+    let mut config = Config::from_storage(&mut deps.storage);
+    let mut constants = config.constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    constants.admin = deps.api.canonical_address(&address)?;
+    config.set_constants(&constants)?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::ChangeAdmin {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn propose_admin<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    address: HumanAddr,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let pending_admin = deps.api.canonical_address(&address)?;
+    Config::from_storage(&mut deps.storage).set_pending_admin(Some(&pending_admin));
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::ProposeAdmin {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn accept_admin<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+) -> StdResult<HandleResponse> {
+    let mut config = Config::from_storage(&mut deps.storage);
+    let pending_admin = config
+        .pending_admin()
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("no admin transfer is pending"))?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != pending_admin {
+        return Err(error::unauthorized());
+    }
+
+    let mut constants = config.constants()?;
+    constants.admin = pending_admin;
+    config.set_constants(&constants)?;
+    config.set_pending_admin(None);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::AcceptAdmin {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn cancel_admin_transfer<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    Config::from_storage(&mut deps.storage).set_pending_admin(None);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::CancelAdminTransfer {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn set_contract_status<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    level: ContractStatusLevel,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let current = ReadonlyConfig::from_storage(&deps.storage).contract_status();
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+
+    // Entering or exiting the hard lockdown is gated on the distinct
+    // emergency_admin; every other transition uses the routine admin.
+    let entering_or_exiting_emergency =
+        matches!(level, ContractStatusLevel::Emergency) || matches!(current, ContractStatusLevel::Emergency);
+    if entering_or_exiting_emergency {
+        if sender != constants.emergency_admin {
+            return Err(error::unauthorized());
+        }
+    } else if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let _ = status_level_to_u8(level.clone());
+    Config::from_storage(&mut deps.storage).set_contract_status(level);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetContractStatus {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn set_oracle<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    address: HumanAddr,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let oracle = deps.api.canonical_address(&address)?;
+    Config::from_storage(&mut deps.storage).set_oracle(&oracle);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::SetOracle {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn submit_oracle_attestation<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    tx_id: String,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let oracle = constants.admin.clone();
+    let configured_oracle = ReadonlyConfig::from_storage(&deps.storage)
+        .oracle()
+        .unwrap_or(oracle);
+    if sender != configured_oracle {
+        return Err(error::unauthorized());
+    }
+
+    OracleAttestationsStore::save(&mut deps.storage, &tx_id)?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "submit_oracle_attestation"), log("tx_id", tx_id)],
+        data: Some(to_binary(&HandleResult::SubmitOracleAttestation {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Writes off a single output's deposit after a Monero reorg orphans the
+/// transaction a mint was already issued against. Scoped to
+/// `(tx_id, output_index)`, not the whole `tx_id`, because one Monero tx can
+/// fund the bridge with several outputs and a reorg may only orphan one of
+/// them — the others are still genuine, already-minted deposits and must
+/// stay both non-reusable (`is_duplicate`) and out of `shortfall_debt`.
+/// Recovery assumption: the sXMR token exposes no admin/forced-burn message
+/// (only self-burn), so the bridge cannot unilaterally reclaim sXMR already
+/// sitting in the recipient's balance. This always records the amount as
+/// `shortfall_debt` rather than attempting a clawback; it still frees this
+/// output for reuse and removes the deposit from `pending_liability`, since
+/// the bridge no longer treats it as backed. `MintRecordStore` (the
+/// recipient/amount written at mint time) is what makes the writeoff amount
+/// known here.
+fn revert_mint<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    tx_id: String,
+    output_index: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let oracle = ReadonlyConfig::from_storage(&deps.storage).oracle();
+    if sender != constants.admin && Some(sender) != oracle {
+        return Err(error::unauthorized());
+    }
+
+    if MoneroProofsStore::is_revoked(&deps.storage, &tx_id, output_index) {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "this deposit has already been reverted",
+        ));
+    }
+
+    let (_recipient, amount) = MintRecordStore::lookup(&deps.storage, &tx_id, output_index)
+        .ok_or_else(|| {
+            cosmwasm_std::StdError::generic_err("no mint record found for tx_id/output_index")
+        })?;
+
+    MoneroProofsStore::revoke(&mut deps.storage, &tx_id, output_index);
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    let pending_liability = config.pending_liability();
+    config.set_pending_liability(Uint128(pending_liability.u128().saturating_sub(amount.u128())));
+    let shortfall_debt = config.shortfall_debt();
+    config.set_shortfall_debt(shortfall_debt + amount);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![
+            log("action", "revert_mint"),
+            log("tx_id", tx_id),
+            log("output_index", output_index),
+            log("amount", amount),
+        ],
+        data: Some(to_binary(&HandleResult::RevertMint {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Admin-only: seeds the `(tx_id, output_index)` replay-protection keys
+/// paged out of a predecessor contract's `ExportProofSet` query, so
+/// migrating to a successor contract can't be bypassed by replaying a
+/// deposit already minted against on the old one.
+fn import_proofs<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    entries: Vec<(String, u32)>,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    for (tx_id, output_index) in &entries {
+        MoneroProofsStore::import_key(&mut deps.storage, tx_id, *output_index);
+    }
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "import_proofs")],
+        data: Some(to_binary(&HandleResult::ImportProofs {
+            imported: entries.len() as u32,
+        })?),
+    })
+}
+
+/// Admin-only: mints `amount` to `recipient` without a Monero proof, for
+/// exercising the mint path against a live testnet deployment. Gated by
+/// `constants.testnet_mode`, which `init` refuses to set on Secret Network
+/// mainnet and which nothing in `handle` can flip afterward — see
+/// `InitMsg::testnet_mode`'s doc comment.
+///
+/// Deliberately bypasses every other `mint_sxmr` check (duplicate proof,
+/// oracle attestation, known recipient): there is no proof to check any of
+/// those against. It does still add to `pending_liability`, so a testnet
+/// contract's liability accounting stays internally consistent.
+fn test_mint<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    recipient: HumanAddr,
+    amount: Uint128,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+    if !constants.testnet_mode {
+        return Err(cosmwasm_std::StdError::generic_err(
+            "TestMint is disabled; this contract was not initialized with testnet_mode",
+        ));
+    }
+    if amount.u128() == 0 {
+        return Err(error::zero_amount());
+    }
+
+    let mut liability_config = Config::from_storage(&mut deps.storage);
+    let pending_liability = liability_config.pending_liability();
+    liability_config.set_pending_liability(pending_liability + amount);
+
+    let mint_msg = snip20_mint_msg(&constants.sxmr, recipient.clone(), amount)?;
+
+    Ok(HandleResponse {
+        messages: vec![mint_msg],
+        log: vec![
+            log("action", "test_mint"),
+            log("recipient", recipient),
+            log("amount", amount),
+        ],
+        data: Some(to_binary(&HandleResult::TestMint {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Re-mints the net amount of each swap that's been pending longer than
+/// `swap_ttl_seconds` back to its owner and marks it `Expired`, up to
+/// `limit` per call. Callable by the admin or the `bridge_minter` relayer.
+fn sweep_expired_swaps<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    limit: u32,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin && sender != constants.bridge_minter {
+        return Err(error::unauthorized());
+    }
+
+    let ttl_seconds = ReadonlyConfig::from_storage(&deps.storage).swap_ttl_seconds();
+    let swept = SwapDetailsStore::sweep_expired(&mut deps.storage, env.block.time, ttl_seconds, limit)?;
+
+    let mut messages = vec![];
+    for (_, swap) in swept.iter() {
+        let owner = deps.api.human_address(&swap.from_secret_address)?;
+        messages.push(snip20_mint_msg(&constants.sxmr, owner, swap.amount)?);
+    }
+
+    let processed = swept.len() as u32;
+    if processed > 0 {
+        let refunded: Uint128 = swept.iter().fold(Uint128::zero(), |total, (_, swap)| total + swap.amount);
+
+        let mut config = Config::from_storage(&mut deps.storage);
+        let mut counts = config.swap_counts();
+        counts.pending = counts.pending.saturating_sub(processed as u64);
+        counts.expired += processed as u64;
+        config.set_swap_counts(&counts);
+        let pending_liability = config.pending_liability();
+        config.set_pending_liability(pending_liability + refunded);
+    }
+
+    Ok(HandleResponse {
+        messages,
+        log: vec![log("action", "sweep_expired_swaps"), log("processed", processed)],
+        data: Some(to_binary(&HandleResult::SweepExpiredSwaps { processed })?),
+    })
+}
+
+fn re_register_receive<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+) -> StdResult<HandleResponse> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    if sender != constants.admin {
+        return Err(error::unauthorized());
+    }
+
+    let code_hash = env.contract_code_hash;
+    Config::from_storage(&mut deps.storage).set_registered_code_hash(&code_hash);
+    let register_msg = register_receive_msg(code_hash, &constants.sxmr)?;
+
+    Ok(HandleResponse {
+        messages: vec![register_msg],
+        log: vec![],
+        data: Some(to_binary(&HandleResult::ReRegisterReceive {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn register_receive_msg(code_hash: String, sxmr: &TokenInfo) -> StdResult<CosmosMsg> {
+    let msg = sxmr_token::msg::HandleMsg::RegisterReceive {
+        code_hash,
+        padding: None,
+    };
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: HumanAddr::from(sxmr.address.to_string()),
+        callback_code_hash: sxmr.contract_hash.clone(),
+        msg: to_binary(&msg)?,
+        send: vec![],
+    }))
+}
+
+fn snip20_mint_msg(sxmr: &TokenInfo, recipient: HumanAddr, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = sxmr_token::msg::HandleMsg::Mint {
+        recipient,
+        amount,
+        memo: None,
+        padding: None,
+    };
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: HumanAddr::from(sxmr.address.to_string()),
+        callback_code_hash: sxmr.contract_hash.clone(),
+        msg: to_binary(&msg)?,
+        send: vec![],
+    }))
+}
+
+/// Destroys `amount` of the sXMR the bridge is holding, called against the
+/// net amount of what `burn_sxmr` just received via `Receive` (the fee part
+/// stays in the bridge's balance until it is swept) so a redeemed swap
+/// doesn't leave its sXMR stuck in the bridge's own balance forever. Burns
+/// from the caller's balance, and the bridge is the caller here.
+fn snip20_burn_msg(sxmr: &TokenInfo, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = sxmr_token::msg::HandleMsg::Burn {
+        amount,
+        memo: None,
+        padding: None,
+    };
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: HumanAddr::from(sxmr.address.to_string()),
+        callback_code_hash: sxmr.contract_hash.clone(),
+        msg: to_binary(&msg)?,
+        send: vec![],
+    }))
+}
+
+/// Pays a fee sweep out of the sXMR the bridge is already holding (the fee
+/// portion withheld from each burn's gross amount), instead of minting a
+/// fresh copy of it. See `maybe_sweep_fees`/`sweep_fees`.
+fn snip20_transfer_msg(sxmr: &TokenInfo, recipient: HumanAddr, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = sxmr_token::msg::HandleMsg::Transfer {
+        recipient,
+        amount,
+        memo: None,
+        padding: None,
+    };
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: HumanAddr::from(sxmr.address.to_string()),
+        callback_code_hash: sxmr.contract_hash.clone(),
+        msg: to_binary(&msg)?,
+        send: vec![],
+    }))
+}
+
+pub fn query<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::ConfigSize {} => query_config_size(deps),
+        QueryMsg::Capabilities {} => query_capabilities(deps),
+        QueryMsg::MyRoles {
+            address,
+            viewing_key,
+        } => query_my_roles(deps, address, viewing_key),
+        QueryMsg::Statistics {} => query_statistics(deps),
+        QueryMsg::SwapDetails {
+            address,
+            nonce,
+            swap_id,
+            viewing_key,
+            encrypt_to,
+        } => query_swap_details(deps, address, nonce, swap_id, viewing_key, encrypt_to),
+        QueryMsg::SwapHistory {
+            address,
+            viewing_key,
+            page,
+            page_size,
+        } => query_swap_history(deps, address, viewing_key, page, page_size),
+        QueryMsg::SwapCounts {} => query_swap_counts(deps),
+        QueryMsg::SwapLimits {} => query_swap_limits(deps),
+        QueryMsg::VerifyReceipt {
+            address,
+            nonce,
+            swap_id,
+            viewing_key,
+            expected_hash,
+        } => query_verify_receipt(deps, address, nonce, swap_id, viewing_key, expected_hash),
+        QueryMsg::SwapByReceipt {
+            admin_viewing_key,
+            receipt_hash,
+        } => query_swap_by_receipt(deps, admin_viewing_key, receipt_hash),
+        QueryMsg::ProofsByBlockRange {
+            admin_viewing_key,
+            from,
+            to,
+            page,
+            page_size,
+        } => query_proofs_by_block_range(deps, admin_viewing_key, from, to, page, page_size),
+        QueryMsg::ProofByTxId {
+            admin_viewing_key,
+            tx_id,
+        } => query_proof_by_tx_id(deps, admin_viewing_key, tx_id),
+        QueryMsg::PendingLiabilityHuman {} => query_pending_liability_human(deps),
+        QueryMsg::ExportProofSet {
+            admin_viewing_key,
+            page,
+            page_size,
+        } => query_export_proof_set(deps, admin_viewing_key, page, page_size),
+        QueryMsg::IsDestinationBlocked { to_monero_address } => {
+            query_is_destination_blocked(deps, to_monero_address)
+        }
+        QueryMsg::IsSenderBlocked { address } => query_is_sender_blocked(deps, address),
+        QueryMsg::IsProofUsed { tx_id } => query_is_proof_used(deps, tx_id),
+        QueryMsg::PendingActions { admin_viewing_key } => {
+            query_pending_actions(deps, admin_viewing_key)
+        }
+        QueryMsg::SolvencyCheck { admin_viewing_key } => {
+            query_solvency_check(deps, admin_viewing_key)
+        }
+        QueryMsg::SimulateBurn {
+            from,
+            amount,
+            to_monero_address,
+            current_block_height,
+        } => query_simulate_burn(deps, from, amount, to_monero_address, current_block_height),
+        QueryMsg::FullConfig { admin_viewing_key } => query_full_config(deps, admin_viewing_key),
+        QueryMsg::AllPendingSwaps { viewing_key, page, page_size } => {
+            query_all_pending_swaps(deps, viewing_key, page, page_size)
+        }
+        QueryMsg::SwapsByStatus { viewing_key, status, page, page_size } => {
+            query_swaps_by_status(deps, viewing_key, status, page, page_size)
+        }
+        QueryMsg::ExportSwaps { viewing_key, start_nonce, limit } => {
+            query_export_swaps(deps, viewing_key, start_nonce, limit)
+        }
+        QueryMsg::WithPermit { permit, query } => query_with_permit(deps, permit, query),
+    }
+}
+
+/// Runs the subset of `burn_sxmr`'s checks that don't require the SNIP-20
+/// `Receive` envelope itself (token-address/sender-equals-from are about the
+/// `Receive` caller, not the amount or destination, so they're left out
+/// here). Kept in the same order as `burn_sxmr` so the two stay easy
+/// to compare when one changes.
+fn query_simulate_burn<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    from: HumanAddr,
+    amount: Uint128,
+    to_monero_address: String,
+    current_block_height: u64,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let readonly = ReadonlyConfig::from_storage(&deps.storage);
+
+    let fee_bps = readonly.fee_bps();
+    let net_amount = Uint128(amount.u128() - amount.u128() * fee_bps as u128 / 10_000);
+
+    let reason = if constants.sxmr.decimals != readonly.sxmr_decimals() {
+        Some(
+            "the configured sXMR token's recorded decimals do not match the active sxmr_decimals setting"
+                .to_string(),
+        )
+    } else if amount < constants.min_swap_amount {
+        Some("amount is below the minimum swap amount".to_string())
+    } else if readonly.max_swap_amount().u128() > 0 && amount > readonly.max_swap_amount() {
+        Some("amount exceeds the maximum swap amount".to_string())
+    } else if net_amount.is_zero() {
+        Some("the net amount after the configured fee would be zero; increase the amount".to_string())
+    } else if let Err(e) =
+        crate::state::validate_unit_granularity(net_amount, readonly.unit_granularity())
+    {
+        Some(e.to_string())
+    } else if readonly.monero_dust_limit().u128() > 0 && net_amount < readonly.monero_dust_limit() {
+        Some("net deliverable amount is below the Monero dust limit".to_string())
+    } else if readonly
+        .maintenance_window()
+        .map(|(start_block, end_block)| {
+            current_block_height >= start_block && current_block_height <= end_block
+        })
+        .unwrap_or(false)
+    {
+        Some("burns are paused for scheduled maintenance".to_string())
+    } else if BlockedDestinationsStore::is_blocked(&deps.storage, &to_monero_address) {
+        Some("destination address is blocked".to_string())
+    } else {
+        let from_canonical = deps.api.canonical_address(&from)?;
+        if !WhitelistedDestinationsStore::is_whitelisted(&deps.storage, &from_canonical, &to_monero_address)
+        {
+            validate_monero_address(&to_monero_address).err().map(|e| e.to_string())
+        } else {
+            None
+        }
+    };
+
+    to_binary(&QueryResponse::SimulateBurn {
+        accepted: reason.is_none(),
+        reason,
+    })
+}
+
+/// `actual` compared against `expected`, split into a `surplus` (the
+/// contract holds more than its ledger expects) and a `shortfall` (it holds
+/// less), so `QueryResponse::SolvencyCheck` never needs a signed integer.
+fn solvency_delta(expected: Uint128, actual: Uint128) -> (Uint128, Uint128) {
+    if actual > expected {
+        (Uint128(actual.u128() - expected.u128()), Uint128::zero())
+    } else {
+        (Uint128::zero(), Uint128(expected.u128() - actual.u128()))
+    }
+}
+
+fn query_actual_sxmr_balance<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    sxmr: &TokenInfo,
+    address: HumanAddr,
+    viewing_key: String,
+) -> StdResult<Uint128> {
+    let query_msg = sxmr_token::msg::QueryMsg::Balance {
+        address,
+        key: viewing_key,
+    };
+    let answer: sxmr_token::msg::QueryAnswer =
+        deps.querier
+            .query(&cosmwasm_std::QueryRequest::Wasm(cosmwasm_std::WasmQuery::Smart {
+                contract_addr: HumanAddr::from(sxmr.address.to_string()),
+                callback_code_hash: sxmr.contract_hash.clone(),
+                msg: to_binary(&query_msg)?,
+            }))?;
+    match answer {
+        sxmr_token::msg::QueryAnswer::Balance { amount } => Ok(amount),
+        _ => Err(cosmwasm_std::StdError::generic_err(
+            "unexpected response from sXMR balance query",
+        )),
+    }
+}
+
+/// Looks up the bridge's own sXMR balance, trying the current
+/// `bridge_viewing_key` first and falling back to the key it replaced (if
+/// any) so a query made during a `SetBridgeViewingKey` rotation's overlap
+/// window still succeeds. See `BRIDGE_VIEWING_KEY_OVERLAP_BLOCKS`.
+fn query_sxmr_balance<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    sxmr: &TokenInfo,
+    bridge_address: HumanAddr,
+) -> StdResult<Uint128> {
+    let config = ReadonlyConfig::from_storage(&deps.storage);
+    let current_key = config.bridge_viewing_key().ok_or_else(|| {
+        cosmwasm_std::StdError::generic_err(
+            "SetBridgeViewingKey has not been called; the bridge has no viewing key \
+             registered with the sXMR token yet",
+        )
+    })?;
+
+    match query_actual_sxmr_balance(deps, sxmr, bridge_address.clone(), current_key) {
+        Ok(balance) => Ok(balance),
+        Err(current_err) => match config.prev_bridge_viewing_key() {
+            Some((old_key, _retire_at_block)) => {
+                query_actual_sxmr_balance(deps, sxmr, bridge_address, old_key)
+            }
+            None => Err(current_err),
+        },
+    }
+}
+
+fn query_solvency_check<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    admin_viewing_key: String,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, admin_viewing_key)?;
+
+    let bridge_address = deps.api.human_address(&constants.bridge_address)?;
+
+    let expected = ReadonlyConfig::from_storage(&deps.storage).accumulated_fees();
+    let actual = query_sxmr_balance(deps, &constants.sxmr, bridge_address)?;
+    let (surplus, shortfall) = solvency_delta(expected, actual);
+
+    to_binary(&QueryResponse::SolvencyCheck {
+        expected,
+        actual,
+        surplus,
+        shortfall,
+    })
+}
+
+fn query_is_destination_blocked<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    to_monero_address: String,
+) -> StdResult<Binary> {
+    to_binary(&QueryResponse::IsDestinationBlocked {
+        blocked: BlockedDestinationsStore::is_blocked(&deps.storage, &to_monero_address),
+    })
+}
+
+fn query_is_sender_blocked<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+) -> StdResult<Binary> {
+    let canonical = deps.api.canonical_address(&address)?;
+    to_binary(&QueryResponse::IsSenderBlocked {
+        blocked: BlockedSendersStore::is_blocked(&deps.storage, &canonical),
+    })
+}
+
+fn query_is_proof_used<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    tx_id: String,
+) -> StdResult<Binary> {
+    to_binary(&QueryResponse::IsProofUsed {
+        used: MoneroProofsStore::fetch_by_tx_id(&deps.storage, &tx_id)?.is_some(),
+    })
+}
+
+fn describe_timelocked_action<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    action: &TimelockedAction,
+) -> StdResult<String> {
+    Ok(match action {
+        TimelockedAction::SetMinters { minters } => {
+            let minters = minters
+                .iter()
+                .map(|m| deps.api.human_address(m).map(|a| a.to_string()))
+                .collect::<StdResult<Vec<_>>>()?;
+            format!("set_minters({})", minters.join(", "))
+        }
+        TimelockedAction::SetSxmrToken {
+            address,
+            code_hash,
+            decimals,
+        } => format!(
+            "set_sxmr_token({}, {}, decimals={})",
+            deps.api.human_address(address)?,
+            code_hash,
+            decimals
+        ),
+        TimelockedAction::SetMoneroWallets { wallets } => {
+            format!("set_monero_wallets({})", wallets.join(", "))
+        }
+    })
+}
+
+fn query_pending_actions<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    admin_viewing_key: String,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, admin_viewing_key)?;
+
+    let next_id = ReadonlyConfig::from_storage(&deps.storage).next_pending_action_id();
+    let actions = PendingActionStore::all(&deps.storage, next_id)
+        .into_iter()
+        .map(|pending| {
+            Ok(PendingActionSummary {
+                id: pending.id,
+                ready_at_block: pending.ready_at_block,
+                description: describe_timelocked_action(deps, &pending.action)?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&QueryResponse::PendingActions { actions })
+}
+
+fn query_swap_counts<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let counts = ReadonlyConfig::from_storage(&deps.storage).swap_counts();
+    to_binary(&QueryResponse::SwapCounts {
+        pending: counts.pending,
+        fulfilled: counts.fulfilled,
+        refunded: counts.refunded,
+        expired: counts.expired,
+    })
+}
+
+fn query_swap_limits<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let max_swap_amount = ReadonlyConfig::from_storage(&deps.storage).max_swap_amount();
+    to_binary(&QueryResponse::SwapLimits {
+        min_swap_amount: constants.min_swap_amount,
+        min_swap_amount_human: crate::state::format_units(
+            constants.min_swap_amount,
+            crate::state::MONERO_DECIMALS,
+        ),
+        max_swap_amount,
+        max_swap_amount_human: crate::state::format_units(max_swap_amount, crate::state::MONERO_DECIMALS),
+    })
+}
+
+/// Admin-only: every adjustable parameter in one call. See
+/// `QueryMsg::FullConfig`'s doc comment for what's deliberately excluded.
+fn query_full_config<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    admin_viewing_key: String,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, admin_viewing_key)?;
+
+    let config = ReadonlyConfig::from_storage(&deps.storage);
+    let fee_collector = config.fee_collector().map(|c| deps.api.human_address(&c)).transpose()?;
+    let minters = config
+        .minters()
+        .into_iter()
+        .map(|m| deps.api.human_address(&m))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&QueryResponse::FullConfig {
+        full_config: crate::query_messages::FullConfig {
+            admin: deps.api.human_address(&constants.admin)?,
+            bridge_minter: deps.api.human_address(&constants.bridge_minter)?,
+            minters,
+            max_minters: config.max_minters(),
+            monero_wallets: constants.monero_wallets,
+            min_swap_amount: constants.min_swap_amount,
+            max_swap_amount: config.max_swap_amount(),
+            max_destinations_per_burn: config.max_destinations_per_burn(),
+            fee_bps: config.fee_bps(),
+            fee_collector,
+            fee_sweep_threshold: config.fee_sweep_threshold(),
+            monero_dust_limit: config.monero_dust_limit(),
+            require_known_recipient: config.require_known_recipient(),
+            enforce_monotonic_proof_order: config.enforce_monotonic_proof_order(),
+            include_token_info_in_result: config.include_token_info_in_result(),
+            swap_ttl_seconds: config.swap_ttl_seconds(),
+            auto_vk_on_mint: config.auto_vk_on_mint(),
+            enforce_decimal_alignment: config.enforce_decimal_alignment(),
+            sxmr_decimals: config.sxmr_decimals(),
+            require_sender_equals_from: config.require_sender_equals_from(),
+            maintenance_window: config.maintenance_window(),
+            timelock_blocks: config.timelock_blocks(),
+            testnet_mode: constants.testnet_mode,
+            min_mint_amount: config.min_mint_amount(),
+            max_mint_amount: config.max_mint_amount(),
+            unit_granularity: config.unit_granularity(),
+            mint_limit_per_window: config.mint_limit_per_window(),
+            mint_window_blocks: config.mint_window_blocks(),
+            mint_threshold: config.mint_threshold(),
+        },
+    })
+}
+
+/// Admin-only: pages every swap still `Pending` across all owners,
+/// oldest-first.
+fn query_all_pending_swaps<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewing_key: String,
+    page: u32,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, viewing_key)?;
+
+    let swaps = ReadonlySwapDetailsStore::fetch_all_pending(&deps.storage, page, page_size)?
+        .into_iter()
+        .map(|swap| {
+            Ok(crate::query_messages::PendingSwapSummary {
+                nonce: swap.nonce,
+                owner: deps.api.human_address(&swap.from_secret_address)?,
+                to_monero_address: swap.to_monero_address,
+                amount: swap.amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&QueryResponse::AllPendingSwaps { swaps })
+}
+
+/// Admin-only: pages the swaps currently tagged `status`, backed by
+/// `StatusIndex` rather than a scan over every swap ever created.
+fn query_swaps_by_status<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewing_key: String,
+    status: crate::state::SwapStatusFilter,
+    page: u32,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, viewing_key)?;
+
+    let swaps = ReadonlySwapDetailsStore::fetch_by_status(&deps.storage, status, page, page_size)?
+        .into_iter()
+        .map(|swap| crate::query_messages::SwapSummary {
+            nonce: swap.nonce,
+            to_monero_address: swap.to_monero_address,
+            amount: swap.amount,
+            fee: swap.fee_taken,
+            destinations: swap.destinations,
+            label: swap.label,
+            swap_id: swap.swap_id,
+            monero_tx_id: swap.monero_tx_id,
+            resolved: swap.resolved,
+        })
+        .collect();
+    to_binary(&QueryResponse::SwapsByStatus { swaps })
+}
+
+/// Admin-only: dumps swaps from `start_nonce` ascending for a migration
+/// script, via `ReadonlySwapDetailsStore::fetch_range_from_nonce` rather than
+/// `page`/`page_size`, so the caller can resume from the last nonce it saw
+/// regardless of how many swaps were created in between calls.
+fn query_export_swaps<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewing_key: String,
+    start_nonce: u32,
+    limit: u32,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, viewing_key)?;
+
+    let limit = limit.min(MAX_EXPORT_PAGE_SIZE);
+    let swaps = ReadonlySwapDetailsStore::fetch_range_from_nonce(&deps.storage, start_nonce, limit)?
+        .into_iter()
+        .map(|swap| {
+            Ok(crate::query_messages::ExportedSwap {
+                nonce: swap.nonce,
+                owner: deps.api.human_address(&swap.from_secret_address)?,
+                to_monero_address: swap.to_monero_address,
+                payment_id: swap.payment_id,
+                memo: swap.memo,
+                amount: swap.amount,
+                xmr_atomic_amount: swap.xmr_atomic_amount,
+                fee: swap.fee_taken,
+                destinations: swap.destinations,
+                label: swap.label,
+                swap_id: swap.swap_id,
+                monero_tx_id: swap.monero_tx_id,
+                status: swap.status,
+                resolved: swap.resolved,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&QueryResponse::ExportSwaps { swaps })
+}
+
+fn query_pending_liability_human<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+) -> StdResult<Binary> {
+    let raw = ReadonlyConfig::from_storage(&deps.storage).pending_liability();
+    to_binary(&QueryResponse::PendingLiabilityHuman {
+        raw,
+        human: crate::state::format_units(raw, crate::state::MONERO_DECIMALS),
+    })
+}
+
+fn query_capabilities<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let config = ReadonlyConfig::from_storage(&deps.storage);
+    let status = config.contract_status();
+    let running = status == ContractStatusLevel::Running;
+    let minting = running || status == ContractStatusLevel::StopSwaps;
+    let burning = running || status == ContractStatusLevel::StopMinting;
+    let capabilities = vec![
+        ("mint".to_string(), minting),
+        ("burn".to_string(), burning),
+        ("set_contract_status".to_string(), true),
+    ];
+    to_binary(&QueryResponse::Capabilities { capabilities })
+}
+
+fn query_my_roles<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    viewing_key: String,
+) -> StdResult<Binary> {
+    let owner = deps.api.canonical_address(&address)?;
+    authenticate_swap_owner(&deps.storage, &owner, viewing_key)?;
+
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let is_admin = owner == constants.admin;
+    let is_relayer = owner == constants.bridge_minter;
+    let is_minter = ReadonlyConfig::from_storage(&deps.storage)
+        .minters()
+        .contains(&owner);
+
+    to_binary(&QueryResponse::MyRoles {
+        is_admin,
+        is_minter,
+        is_relayer,
+    })
+}
+
+fn query_statistics<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let statistics = ReadonlyConfig::from_storage(&deps.storage).statistics();
+    to_binary(&QueryResponse::Statistics {
+        total_minted: statistics.total_minted,
+        total_burned: statistics.total_burned,
+        total_swap_count: statistics.total_swap_count,
+        total_refunded: statistics.total_refunded,
+    })
+}
+
+fn query_config_size<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let bytes = bincode2::serialize(&constants)
+        .map_err(|_| cosmwasm_std::StdError::generic_err("failed to serialize constants"))?
+        .len() as u64;
+    to_binary(&QueryResponse::ConfigSize { bytes })
+}
+
+fn query_config<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let fee_collector = ReadonlyConfig::from_storage(&deps.storage)
+        .fee_collector()
+        .map(|c| deps.api.human_address(&c))
+        .transpose()?;
+    let pending_admin = ReadonlyConfig::from_storage(&deps.storage)
+        .pending_admin()
+        .map(|a| deps.api.human_address(&a))
+        .transpose()?;
+    to_binary(&QueryResponse::Config {
+        admin: deps.api.human_address(&constants.admin)?,
+        pending_admin,
+        sxmr_address: deps.api.human_address(&constants.sxmr.address)?,
+        bridge_minter: deps.api.human_address(&constants.bridge_minter)?,
+        monero_wallets: constants.monero_wallets,
+        min_swap_amount: constants.min_swap_amount,
+        fee_collector,
+        min_mint_amount: ReadonlyConfig::from_storage(&deps.storage).min_mint_amount(),
+        max_mint_amount: ReadonlyConfig::from_storage(&deps.storage).max_mint_amount(),
+        unit_granularity: ReadonlyConfig::from_storage(&deps.storage).unit_granularity(),
+    })
+}
+
+fn authenticate_swap_owner<S: Storage>(
+    storage: &S,
+    owner: &cosmwasm_std::CanonicalAddr,
+    viewing_key: String,
+) -> StdResult<()> {
+    use crate::state::PREFIX_VIEWING_KEY;
+    use crate::viewing_key::ViewingKey;
+    use cosmwasm_storage::ReadonlyPrefixedStorage;
+
+    let store = ReadonlyPrefixedStorage::new(PREFIX_VIEWING_KEY, storage);
+    let hashed = store
+        .get(owner.as_slice())
+        .ok_or_else(error::unauthorized)?;
+    if !ViewingKey(viewing_key).check_viewing_key(&hashed) {
+        return Err(error::unauthorized());
+    }
+    Ok(())
+}
+
+fn query_swap_details<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    nonce: u32,
+    swap_id: Option<String>,
+    viewing_key: String,
+    encrypt_to: Option<Binary>,
+) -> StdResult<Binary> {
+    let owner = deps.api.canonical_address(&address)?;
+    authenticate_swap_owner(&deps.storage, &owner, viewing_key)?;
+    swap_details_response(deps, &owner, nonce, swap_id, encrypt_to)
+}
+
+/// Shared by `query_swap_details` and `query_with_permit`'s `SwapDetails`
+/// arm, once each has authenticated `owner` its own way (viewing key vs
+/// permit signature).
+fn swap_details_response<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    owner: &cosmwasm_std::CanonicalAddr,
+    nonce: u32,
+    swap_id: Option<String>,
+    encrypt_to: Option<Binary>,
+) -> StdResult<Binary> {
+    let swap = ReadonlySwapDetailsStore::resolve(&deps.storage, owner, nonce, swap_id.as_deref())?;
+    let response = QueryResponse::SwapDetails {
+        from_secret_address: deps.api.human_address(&swap.from_secret_address)?,
+        to_monero_address: swap.to_monero_address,
+        payment_id: swap.payment_id,
+        memo: swap.memo,
+        amount: swap.amount,
+        xmr_atomic_amount: swap.xmr_atomic_amount,
+        fee: swap.fee_taken,
+        destinations: swap.destinations,
+        label: swap.label,
+        swap_id: swap.swap_id,
+        monero_tx_id: swap.monero_tx_id,
+        fee_bps_at_creation: swap.fee_bps_at_creation,
+        scale_at_creation: swap.scale_at_creation,
+        status: swap.status,
+    };
+
+    match encrypt_to {
+        None => to_binary(&response),
+        Some(key) => {
+            let key: crate::crypto_box::PubKey = key.0.as_slice().try_into().map_err(|_| {
+                cosmwasm_std::StdError::generic_err(format!(
+                    "encrypt_to must be exactly {} bytes",
+                    crate::crypto_box::KEY_LEN
+                ))
+            })?;
+            let plaintext = to_binary(&response)?;
+            let (ciphertext, nonce) = crate::crypto_box::seal(&key, plaintext.0.as_slice());
+            to_binary(&QueryResponse::EncryptedSwapDetails {
+                ciphertext: Binary(ciphertext),
+                nonce: Binary(nonce.to_vec()),
+            })
+        }
+    }
+}
+
+fn query_swap_history<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    viewing_key: String,
+    page: u32,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let owner = deps.api.canonical_address(&address)?;
+    authenticate_swap_owner(&deps.storage, &owner, viewing_key)?;
+    swap_history_response(deps, &owner, page, page_size)
+}
+
+/// Shared by `query_swap_history` and `query_with_permit`'s `SwapHistory`
+/// arm, once each has authenticated `owner` its own way.
+fn swap_history_response<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    owner: &cosmwasm_std::CanonicalAddr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let swaps = ReadonlySwapDetailsStore::fetch_user_swaps(&deps.storage, owner, page, page_size)?
+        .into_iter()
+        .map(|swap| crate::query_messages::SwapSummary {
+            nonce: swap.nonce,
+            to_monero_address: swap.to_monero_address,
+            amount: swap.amount,
+            fee: swap.fee_taken,
+            destinations: swap.destinations,
+            label: swap.label,
+            swap_id: swap.swap_id,
+            monero_tx_id: swap.monero_tx_id,
+            resolved: swap.resolved,
+        })
+        .collect();
+    to_binary(&QueryResponse::SwapHistory { swaps })
+}
+
+/// Authenticates `permit` in place of a viewing key and dispatches `query`
+/// as the permit's signer. See `crate::permit::validate`.
+fn query_with_permit<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    permit: crate::permit::Permit,
+    query: crate::msg::QueryWithPermit,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    let contract_address = deps.api.human_address(&constants.bridge_address)?;
+    let signer = crate::permit::validate(&permit, &contract_address)?;
+    let owner = deps.api.canonical_address(&signer)?;
+
+    match query {
+        crate::msg::QueryWithPermit::SwapDetails {
+            nonce,
+            swap_id,
+            encrypt_to,
+        } => swap_details_response(deps, &owner, nonce, swap_id, encrypt_to),
+        crate::msg::QueryWithPermit::SwapHistory { page, page_size } => {
+            swap_history_response(deps, &owner, page, page_size)
+        }
+    }
+}
+
+fn query_swap_by_receipt<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    admin_viewing_key: String,
+    receipt_hash: String,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, admin_viewing_key)?;
+
+    let (owner, nonce) =
+        ReceiptIndexStore::lookup(&deps.storage, &receipt_hash).ok_or_else(|| {
+            cosmwasm_std::StdError::generic_err("no swap found for that receipt hash")
+        })?;
+    let swap = ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, nonce)?;
+
+    to_binary(&QueryResponse::SwapByReceipt {
+        owner: deps.api.human_address(&owner)?,
+        nonce,
+        to_monero_address: swap.to_monero_address,
+        amount: swap.amount,
+        destinations: swap.destinations,
+        label: swap.label,
+    })
+}
+
+fn query_proofs_by_block_range<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    admin_viewing_key: String,
+    from: u64,
+    to: u64,
+    page: u32,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, admin_viewing_key)?;
+
+    let proofs = MoneroProofsStore::by_block_range(&deps.storage, from, to, page, page_size)?
+        .into_iter()
+        .map(|p| {
+            let recipient = MintRecordStore::lookup(&deps.storage, &p.tx_id, p.output_index)
+                .map(|(recipient, _)| deps.api.human_address(&recipient))
+                .transpose()?;
+            Ok(crate::query_messages::ProofSummary {
+                tx_id: p.tx_id,
+                block_height: p.block_height,
+                output_index: p.output_index,
+                amount: p.amount,
+                recipient,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&QueryResponse::ProofsByBlockRange { proofs })
+}
+
+fn query_proof_by_tx_id<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    admin_viewing_key: String,
+    tx_id: String,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, admin_viewing_key)?;
+
+    let proof = match MoneroProofsStore::fetch_by_tx_id(&deps.storage, &tx_id)? {
+        Some(proof) => proof,
+        None => return to_binary(&QueryResponse::ProofByTxId { proof: None }),
+    };
+    let recipient = MintRecordStore::lookup(&deps.storage, &tx_id, proof.output_index)
+        .map(|(recipient, _)| deps.api.human_address(&recipient))
+        .transpose()?;
+
+    to_binary(&QueryResponse::ProofByTxId {
+        proof: Some(crate::query_messages::ProofSummary {
+            tx_id: proof.tx_id,
+            block_height: proof.block_height,
+            output_index: proof.output_index,
+            amount: proof.amount,
+            recipient,
+        }),
+    })
+}
+
+fn query_export_proof_set<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    admin_viewing_key: String,
+    page: u32,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let constants = ReadonlyConfig::from_storage(&deps.storage).constants()?;
+    authenticate_swap_owner(&deps.storage, &constants.admin, admin_viewing_key)?;
+
+    let entries = MoneroProofsStore::export_keys(&deps.storage, page, page_size)?;
+    to_binary(&QueryResponse::ExportProofSet { entries })
+}
+
+fn query_verify_receipt<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    nonce: u32,
+    swap_id: Option<String>,
+    viewing_key: String,
+    expected_hash: String,
+) -> StdResult<Binary> {
+    let owner = deps.api.canonical_address(&address)?;
+    authenticate_swap_owner(&deps.storage, &owner, viewing_key)?;
+
+    let swap =
+        ReadonlySwapDetailsStore::resolve(&deps.storage, &owner, nonce, swap_id.as_deref())?;
+    to_binary(&QueryResponse::VerifyReceipt {
+        matches: swap.receipt_hash() == expected_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{HumanAddr, Uint128};
+
+    fn init_helper() -> Extern<
+        cosmwasm_std::testing::MockStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    > {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![HumanAddr("minter".to_string())],
+            monero_wallets: vec!["bridge-wallet".to_string()],
+            min_swap_amount: Uint128(1000),
+            prng_seed: "seed".to_string(),
+            emergency_admin: None,
+            testnet_mode: false,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+        deps
+    }
+
+    #[test]
+    fn init_with_multiple_minters_and_wallets_stores_them_all() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![
+                HumanAddr("minter".to_string()),
+                HumanAddr("minter-2".to_string()),
+                HumanAddr("minter-3".to_string()),
+            ],
+            monero_wallets: vec![
+                "bridge-wallet-1".to_string(),
+                "bridge-wallet-2".to_string(),
+            ],
+            min_swap_amount: Uint128(1000),
+            prng_seed: "seed".to_string(),
+            emergency_admin: None,
+            testnet_mode: false,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).minters(),
+            vec![
+                deps.api
+                    .canonical_address(&HumanAddr("minter".to_string()))
+                    .unwrap(),
+                deps.api
+                    .canonical_address(&HumanAddr("minter-2".to_string()))
+                    .unwrap(),
+                deps.api
+                    .canonical_address(&HumanAddr("minter-3".to_string()))
+                    .unwrap(),
+            ]
+        );
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage)
+                .constants()
+                .unwrap()
+                .monero_wallets,
+            vec!["bridge-wallet-1".to_string(), "bridge-wallet-2".to_string()]
+        );
+    }
+
+    #[test]
+    fn init_rejects_an_empty_minters_list() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![],
+            monero_wallets: vec!["bridge-wallet".to_string()],
+            min_swap_amount: Uint128(1000),
+            prng_seed: "seed".to_string(),
+            emergency_admin: None,
+            testnet_mode: false,
+        };
+        let result = init(&mut deps, mock_env("admin", &[]), init_msg);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn zero_amount_mint_is_rejected_before_proof_is_recorded() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-1".to_string(),
+            tx_key: "tx-key-1".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof.clone(),
+            HumanAddr("recipient".to_string()),
+            Uint128(0),
+        );
+        assert!(result.is_err());
+
+        assert!(!MoneroProofsStore::is_duplicate(&deps.storage, &proof.tx_id, proof.output_index).unwrap());
+    }
+
+    #[test]
+    fn swap_counts_track_pending_swaps_created() {
+        let mut deps = init_helper();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).swap_counts().pending,
+            0
+        );
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).swap_counts().pending,
+            1
+        );
+    }
+
+    #[test]
+    fn burn_with_a_plain_address_and_payment_id_records_both() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::SingleWithPaymentId {
+            address: "monero-address".to_string(),
+            payment_id: "0123456789abcdef".to_string(),
+        })
+        .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.to_monero_address, "monero-address");
+        assert_eq!(stored.payment_id, Some("0123456789abcdef".to_string()));
+    }
+
+    #[test]
+    fn burn_with_a_memo_stores_it_trimmed_on_the_swap() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::WithMemo {
+            destination: Box::new(crate::msg::BurnDestination::Single(
+                "monero-address".to_string(),
+            )),
+            memo: "  rent for June  ".to_string(),
+        })
+        .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.memo, Some("rent for June".to_string()));
+    }
+
+    #[test]
+    fn burn_rejects_a_memo_over_the_byte_limit() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::WithMemo {
+            destination: Box::new(crate::msg::BurnDestination::Single(
+                "monero-address".to_string(),
+            )),
+            memo: "x".repeat(MAX_BURN_MEMO_LEN + 1),
+        })
+        .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_to_a_plain_integrated_address_alone_records_no_payment_id() {
+        let mut deps = init_helper();
+        let integrated_address = "i".repeat(106);
+        let dest =
+            to_binary(&crate::msg::BurnDestination::Single(integrated_address.clone())).unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.to_monero_address, integrated_address);
+        assert_eq!(stored.payment_id, None);
+    }
+
+    #[test]
+    fn burn_rejects_a_payment_id_paired_with_an_already_integrated_address() {
+        let mut deps = init_helper();
+        let integrated_address = "i".repeat(106);
+        let dest = to_binary(&crate::msg::BurnDestination::SingleWithPaymentId {
+            address: integrated_address,
+            payment_id: "0123456789abcdef".to_string(),
+        })
+        .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_deducts_the_configured_fee_and_reports_gross_and_net() {
+        let mut deps = init_helper();
+        set_fee(&mut deps, mock_env("admin", &[]), 250).unwrap(); // 2.5%
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.fee_taken, Uint128(25));
+        assert_eq!(stored.amount, Uint128(975));
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).accumulated_fees(),
+            Uint128(25)
+        );
+    }
+
+    #[test]
+    fn burn_fee_rounds_down_at_small_amounts() {
+        let mut deps = init_helper();
+        set_fee(&mut deps, mock_env("admin", &[]), 1).unwrap(); // 0.01%
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        // 1000 * 1 / 10_000 = 0.1, which rounds down to 0.
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.fee_taken, Uint128::zero());
+        assert_eq!(stored.amount, Uint128(1000));
+    }
+
+    #[test]
+    fn setting_a_fee_above_the_cap_is_rejected() {
+        let mut deps = init_helper();
+        let result = set_fee(&mut deps, mock_env("admin", &[]), crate::state::MAX_FEE_BPS + 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn setting_a_fee_as_non_admin_is_rejected() {
+        let mut deps = init_helper();
+        let result = set_fee(&mut deps, mock_env("alice", &[]), 100);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_rejects_an_amount_above_the_configured_max_swap() {
+        let mut deps = init_helper();
+        set_max_swap(&mut deps, mock_env("admin", &[]), Uint128(5_000)).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(5_001),
+            Some(dest),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_at_exactly_the_max_swap_amount_is_accepted() {
+        let mut deps = init_helper();
+        set_max_swap(&mut deps, mock_env("admin", &[]), Uint128(5_000)).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(5_000),
+            Some(dest),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn a_zero_max_swap_amount_means_no_cap() {
+        let mut deps = init_helper();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1_000_000_000),
+            Some(dest),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn setting_max_swap_as_non_admin_is_rejected() {
+        let mut deps = init_helper();
+        let result = set_max_swap(&mut deps, mock_env("alice", &[]), Uint128(5_000));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn setting_max_swap_reports_how_many_pending_swaps_now_exceed_it() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1_000),
+            Some(dest.clone()),
+        )
+        .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("bob".to_string()),
+            Uint128(10_000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let response = set_max_swap(&mut deps, mock_env("admin", &[]), Uint128(5_000)).unwrap();
+        let result: HandleResult = cosmwasm_std::from_binary(&response.data.unwrap()).unwrap();
+        match result {
+            HandleResult::SetMaxSwap { stale_pending_count, .. } => {
+                assert_eq!(stale_pending_count, 1);
+            }
+            _ => panic!("expected SetMaxSwap"),
+        }
+    }
+
+    #[test]
+    fn receive_is_rejected_when_the_active_scale_drifts_from_the_tokens_recorded_decimals() {
+        let mut deps = init_helper();
+        // Simulates a reconfiguration that changed the active sxmr_decimals
+        // without also queuing a matching SetSxmrToken, leaving the token's
+        // recorded scale (12, from init_helper) stale.
+        Config::from_storage(&mut deps.storage).set_sxmr_decimals(6);
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(result.is_err());
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).swap_counts().pending,
+            0
+        );
+    }
+
+    #[test]
+    fn simulate_burn_accepts_a_valid_burn() {
+        let deps = init_helper();
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            "monero-address".to_string(),
+            1,
+        )
+        .unwrap();
+        assert_eq!(
+            cosmwasm_std::from_binary::<QueryResponse>(&result).unwrap(),
+            QueryResponse::SimulateBurn {
+                accepted: true,
+                reason: None,
+            }
+        );
+    }
+
+    #[test]
+    fn simulate_burn_rejects_decimals_drift() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_sxmr_decimals(6);
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            "monero-address".to_string(),
+            1,
+        )
+        .unwrap();
+        let response: QueryResponse = cosmwasm_std::from_binary(&result).unwrap();
+        let (accepted, reason) = match response {
+            QueryResponse::SimulateBurn { accepted, reason } => (accepted, reason),
+            _ => panic!("expected SimulateBurn"),
+        };
+        assert!(!accepted);
+        assert!(reason.unwrap().contains("decimals"));
+    }
+
+    #[test]
+    fn simulate_burn_rejects_amount_below_minimum() {
+        let deps = init_helper();
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128(1),
+            "monero-address".to_string(),
+            1,
+        )
+        .unwrap();
+        let response: QueryResponse = cosmwasm_std::from_binary(&result).unwrap();
+        let (accepted, reason) = match response {
+            QueryResponse::SimulateBurn { accepted, reason } => (accepted, reason),
+            _ => panic!("expected SimulateBurn"),
+        };
+        assert!(!accepted);
+        assert!(reason.unwrap().contains("minimum"));
+    }
+
+    #[test]
+    fn simulate_burn_rejects_below_the_dust_limit() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_monero_dust_limit(Uint128(1500));
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            "monero-address".to_string(),
+            1,
+        )
+        .unwrap();
+        let response: QueryResponse = cosmwasm_std::from_binary(&result).unwrap();
+        let (accepted, reason) = match response {
+            QueryResponse::SimulateBurn { accepted, reason } => (accepted, reason),
+            _ => panic!("expected SimulateBurn"),
+        };
+        assert!(!accepted);
+        assert!(reason.unwrap().contains("dust"));
+    }
+
+    #[test]
+    fn simulate_burn_reports_too_small_instead_of_a_silent_zero_net() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![HumanAddr("minter".to_string())],
+            monero_wallets: vec!["bridge-wallet".to_string()],
+            min_swap_amount: Uint128::zero(),
+            prng_seed: "seed".to_string(),
+            emergency_admin: None,
+            testnet_mode: false,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128::zero(),
+            "monero-address".to_string(),
+            1,
+        )
+        .unwrap();
+        let response: QueryResponse = cosmwasm_std::from_binary(&result).unwrap();
+        let (accepted, reason) = match response {
+            QueryResponse::SimulateBurn { accepted, reason } => (accepted, reason),
+            _ => panic!("expected SimulateBurn"),
+        };
+        assert!(!accepted);
+        assert!(reason.unwrap().contains("net amount"));
+    }
+
+    #[test]
+    fn burn_rejects_an_amount_that_nets_to_zero_after_fees() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![HumanAddr("minter".to_string())],
+            monero_wallets: vec!["bridge-wallet".to_string()],
+            min_swap_amount: Uint128::zero(),
+            prng_seed: "seed".to_string(),
+            emergency_admin: None,
+            testnet_mode: false,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128::zero(),
+            Some(dest),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn simulate_burn_rejects_during_the_maintenance_window() {
+        let mut deps = init_helper();
+        set_maintenance_window(&mut deps, mock_env("admin", &[]), 100, 200).unwrap();
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            "monero-address".to_string(),
+            150,
+        )
+        .unwrap();
+        let response: QueryResponse = cosmwasm_std::from_binary(&result).unwrap();
+        let (accepted, reason) = match response {
+            QueryResponse::SimulateBurn { accepted, reason } => (accepted, reason),
+            _ => panic!("expected SimulateBurn"),
+        };
+        assert!(!accepted);
+        assert!(reason.unwrap().contains("maintenance"));
+    }
+
+    #[test]
+    fn simulate_burn_rejects_a_blocked_destination() {
+        let mut deps = init_helper();
+        BlockedDestinationsStore::block(&mut deps.storage, "monero-address");
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            "monero-address".to_string(),
+            1,
+        )
+        .unwrap();
+        let response: QueryResponse = cosmwasm_std::from_binary(&result).unwrap();
+        let (accepted, reason) = match response {
+            QueryResponse::SimulateBurn { accepted, reason } => (accepted, reason),
+            _ => panic!("expected SimulateBurn"),
+        };
+        assert!(!accepted);
+        assert!(reason.unwrap().contains("blocked"));
+    }
+
+    #[test]
+    fn simulate_burn_rejects_an_implausible_destination_address() {
+        let deps = init_helper();
+        let result = query_simulate_burn(
+            &deps,
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            "".to_string(),
+            1,
+        )
+        .unwrap();
+        let response: QueryResponse = cosmwasm_std::from_binary(&result).unwrap();
+        let (accepted, reason) = match response {
+            QueryResponse::SimulateBurn { accepted, reason } => (accepted, reason),
+            _ => panic!("expected SimulateBurn"),
+        };
+        assert!(!accepted);
+        assert!(reason.unwrap().contains("plausible"));
+    }
+
+    #[test]
+    fn emergency_status_blocks_mint_burn_and_admin_token_ops() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![HumanAddr("minter".to_string())],
+            monero_wallets: vec!["bridge-wallet".to_string()],
+            min_swap_amount: Uint128(1000),
+            prng_seed: "seed".to_string(),
+            emergency_admin: Some(HumanAddr("emergency-admin".to_string())),
+            testnet_mode: false,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+
+        // The routine admin cannot enter the hard lockdown; only emergency_admin can.
+        set_contract_status(&mut deps, mock_env("admin", &[]), ContractStatusLevel::Emergency)
+            .unwrap_err();
+        set_contract_status(
+            &mut deps,
+            mock_env("emergency-admin", &[]),
+            ContractStatusLevel::Emergency,
+        )
+        .unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-emergency".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let mint_result = handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof,
+                recipient: HumanAddr("recipient".to_string()),
+                amount: Uint128(1000),
+            },
+        );
+        assert!(mint_result.is_err());
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let burn_result = handle(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HandleMsg::Receive {
+                sender: HumanAddr("sxmr-token".to_string()),
+                from: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+                msg: Some(dest),
+            },
+        );
+        assert!(burn_result.is_err());
+
+        let admin_token_op = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMinters {
+                minters: vec![HumanAddr("new-minter".to_string())],
+            },
+        );
+        assert!(admin_token_op.is_err());
+
+        // The regular admin cannot lift the lockdown; only emergency_admin can.
+        set_contract_status(&mut deps, mock_env("admin", &[]), ContractStatusLevel::Running)
+            .unwrap_err();
+        set_contract_status(
+            &mut deps,
+            mock_env("emergency-admin", &[]),
+            ContractStatusLevel::Running,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn stop_minting_blocks_only_mints_and_stop_swaps_blocks_only_burns() {
+        let mut deps = init_helper();
+
+        let proof = MoneroProof {
+            tx_id: "tx-1".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+
+        set_contract_status(&mut deps, mock_env("admin", &[]), ContractStatusLevel::StopMinting)
+            .unwrap();
+
+        let mint_blocked = handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof: proof.clone(),
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+            },
+        );
+        assert!(mint_blocked.is_err());
+
+        // Admin commands and the pause gate itself stay allowed under StopMinting.
+        set_contract_status(&mut deps, mock_env("admin", &[]), ContractStatusLevel::Running)
+            .unwrap();
+
+        handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof,
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+            },
+        )
+        .unwrap();
+
+        set_contract_status(&mut deps, mock_env("admin", &[]), ContractStatusLevel::StopSwaps)
+            .unwrap();
+
+        let burn_blocked = handle(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HandleMsg::Receive {
+                sender: HumanAddr("sxmr-token".to_string()),
+                from: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+                msg: Some(dest.clone()),
+            },
+        );
+        assert!(burn_blocked.is_err());
+
+        // Minting still works while burns are stopped.
+        let proof_2 = MoneroProof {
+            tx_id: "tx-2".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof: proof_2,
+                recipient: HumanAddr("bob".to_string()),
+                amount: Uint128(1000),
+            },
+        )
+        .unwrap();
+
+        set_contract_status(&mut deps, mock_env("admin", &[]), ContractStatusLevel::Running)
+            .unwrap();
+        handle(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HandleMsg::Receive {
+                sender: HumanAddr("sxmr-token".to_string()),
+                from: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+                msg: Some(dest),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn every_status_level_round_trips_through_its_numeric_encoding() {
+        for level in [
+            ContractStatusLevel::Running,
+            ContractStatusLevel::Paused,
+            ContractStatusLevel::Emergency,
+            ContractStatusLevel::StopMinting,
+            ContractStatusLevel::StopSwaps,
+        ] {
+            let encoded = status_level_to_u8(level.clone());
+            assert_eq!(u8_to_status_level(encoded).unwrap(), level);
+        }
+        // The existing Running/Paused encoding is preserved for compatibility
+        // with anything that already persisted these values.
+        assert_eq!(status_level_to_u8(ContractStatusLevel::Running), 0);
+        assert_eq!(status_level_to_u8(ContractStatusLevel::Paused), 1);
+    }
+
+    #[test]
+    fn verify_receipt_matches_and_mismatches() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let owner = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let swap = ReadonlySwapDetailsStore::fetch_swap_details(&deps.storage, &owner, 0).unwrap();
+
+        let ok = query_verify_receipt(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "alice-key".to_string(),
+            swap.receipt_hash(),
+        );
+        assert_eq!(
+            ok.unwrap(),
+            to_binary(&QueryResponse::VerifyReceipt { matches: true }).unwrap()
+        );
+
+        let mismatch = query_verify_receipt(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "alice-key".to_string(),
+            "not-the-real-hash".to_string(),
+        );
+        assert_eq!(
+            mismatch.unwrap(),
+            to_binary(&QueryResponse::VerifyReceipt { matches: false }).unwrap()
+        );
+    }
+
+    #[test]
+    fn crossing_fee_sweep_threshold_triggers_transfer_to_collector() {
+        let mut deps = init_helper();
+        let collector = deps
+            .api
+            .canonical_address(&HumanAddr("collector".to_string()))
+            .unwrap();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_fee_collector(&collector);
+            config.set_fee_sweep_threshold(Uint128(500));
+            config.set_accumulated_fees(Uint128(500));
+        }
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let res = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        // One `Burn` for the net amount plus one `Transfer` sweeping the
+        // fee to the collector.
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).accumulated_fees(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn sweep_fees_forces_a_transfer_below_the_threshold() {
+        let mut deps = init_helper();
+        let collector = deps
+            .api
+            .canonical_address(&HumanAddr("collector".to_string()))
+            .unwrap();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_fee_collector(&collector);
+            config.set_fee_sweep_threshold(Uint128(500));
+            config.set_accumulated_fees(Uint128(10));
+        }
+
+        let response = sweep_fees(&mut deps, mock_env("admin", &[])).unwrap();
+        assert_eq!(response.messages.len(), 1);
+        match cosmwasm_std::from_binary::<HandleResult>(&response.data.unwrap()).unwrap() {
+            HandleResult::SweepFees { swept } => assert_eq!(swept, Uint128(10)),
+            other => panic!("unexpected response: {:?}", other),
+        }
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).accumulated_fees(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn sweep_fees_is_a_noop_with_nothing_accumulated() {
+        let mut deps = init_helper();
+        let collector = deps
+            .api
+            .canonical_address(&HumanAddr("collector".to_string()))
+            .unwrap();
+        Config::from_storage(&mut deps.storage).set_fee_collector(&collector);
+
+        let response = sweep_fees(&mut deps, mock_env("admin", &[])).unwrap();
+        assert_eq!(response.messages.len(), 0);
+    }
+
+    #[test]
+    fn sweep_fees_rejects_a_non_admin_caller() {
+        let mut deps = init_helper();
+        let result = sweep_fees(&mut deps, mock_env("alice", &[]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_requires_oracle_attestation_once_an_oracle_is_configured() {
+        let mut deps = init_helper();
+        set_oracle(&mut deps, mock_env("admin", &[]), HumanAddr("oracle".to_string())).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-oracle".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+
+        let unattested = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof.clone(),
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(unattested.is_err());
+
+        submit_oracle_attestation(
+            &mut deps,
+            mock_env("oracle", &[]),
+            proof.tx_id.clone(),
+        )
+        .unwrap();
+
+        let attested = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(attested.is_ok());
+    }
+
+    #[test]
+    fn nonzero_amount_mint_succeeds() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-2".to_string(),
+            tx_key: "tx-key-2".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof.clone(),
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(result.is_ok());
+        assert!(MoneroProofsStore::is_duplicate(&deps.storage, &proof.tx_id, proof.output_index).unwrap());
+    }
+
+    #[test]
+    fn multi_destination_burn_splits_across_addresses() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Multi(vec![
+            ("monero-address-1".to_string(), Uint128(600)),
+            ("monero-address-2".to_string(), Uint128(400)),
+        ]))
+        .unwrap();
+
+        let res = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+        assert!(res.log.iter().any(|l| l.value == "monero-address-1"));
+
+        let response = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "alice-key".to_string(),
+            None,
+        )
+        .unwrap();
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryResponse::SwapDetails {
+                from_secret_address: HumanAddr("alice".to_string()),
+                to_monero_address: "monero-address-1".to_string(),
+                payment_id: None,
+                memo: None,
+                amount: Uint128(1000),
+                xmr_atomic_amount: stored.xmr_atomic_amount,
+                fee: Uint128::zero(),
+                destinations: vec![
+                    ("monero-address-1".to_string(), Uint128(600)),
+                    ("monero-address-2".to_string(), Uint128(400)),
+                ],
+                label: None,
+                swap_id: stored.swap_id,
+                monero_tx_id: stored.monero_tx_id.clone(),
+                fee_bps_at_creation: stored.fee_bps_at_creation,
+                scale_at_creation: stored.scale_at_creation,
+                status: stored.status.clone(),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn created_viewing_key_authenticates_a_subsequent_swap_details_query() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let response = handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            HandleMsg::CreateViewingKey {
+                entropy: "some entropy".to_string(),
+            },
+        )
+        .unwrap();
+        let key = match cosmwasm_std::from_binary::<HandleResult>(&response.data.unwrap()).unwrap() {
+            HandleResult::CreateViewingKey { key } => key,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert!(!key.is_empty());
+
+        let result = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            key,
+            None,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn create_viewing_key_rejects_the_wrong_key() {
+        let mut deps = init_helper();
+        handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            HandleMsg::CreateViewingKey {
+                entropy: "some entropy".to_string(),
+            },
+        )
+        .unwrap();
+
+        let result = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "not-the-real-key".to_string(),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn change_viewing_key_rotates_the_key_and_invalidates_the_old_one() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "old-key".to_string()).unwrap();
+        assert!(query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "old-key".to_string(),
+            None,
+        )
+        .is_ok());
+
+        let response = handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            HandleMsg::ChangeViewingKey {
+                key: "new-key".to_string(),
+                padding: None,
+            },
+        )
+        .unwrap();
+        match cosmwasm_std::from_binary::<HandleResult>(&response.data.unwrap()).unwrap() {
+            HandleResult::ChangeViewingKey { status } => assert_eq!(status, "success"),
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        assert!(query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "old-key".to_string(),
+            None,
+        )
+        .is_err());
+        assert!(query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "new-key".to_string(),
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn change_viewing_key_also_works_for_the_admin() {
+        let mut deps = init_helper();
+        let response = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::ChangeViewingKey {
+                key: "admin-new-key".to_string(),
+                padding: None,
+            },
+        )
+        .unwrap();
+        match cosmwasm_std::from_binary::<HandleResult>(&response.data.unwrap()).unwrap() {
+            HandleResult::ChangeViewingKey { status } => assert_eq!(status, "success"),
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn swap_details_without_encrypt_to_returns_plaintext() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let response = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "alice-key".to_string(),
+            None,
+        )
+        .unwrap();
+        match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::SwapDetails { .. } => {}
+            _ => panic!("expected a plaintext SwapDetails response"),
+        }
+    }
+
+    #[test]
+    fn swap_details_with_encrypt_to_returns_ciphertext_decryptable_with_the_key() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let key: crate::crypto_box::PubKey = [42u8; crate::crypto_box::KEY_LEN];
+        let response = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "alice-key".to_string(),
+            Some(Binary(key.to_vec())),
+        )
+        .unwrap();
+
+        let (ciphertext, nonce) = match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::EncryptedSwapDetails { ciphertext, nonce } => (ciphertext, nonce),
+            _ => panic!("expected an EncryptedSwapDetails response"),
+        };
+
+        let nonce: [u8; crate::crypto_box::NONCE_LEN] = nonce.0.as_slice().try_into().unwrap();
+        let plaintext = crate::crypto_box::open(&key, &nonce, &ciphertext.0);
+        let decrypted: QueryResponse = cosmwasm_std::from_binary(&Binary(plaintext)).unwrap();
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(
+            decrypted,
+            QueryResponse::SwapDetails {
+                from_secret_address: HumanAddr("alice".to_string()),
+                to_monero_address: "monero-address".to_string(),
+                payment_id: None,
+                memo: None,
+                amount: Uint128(1000),
+                xmr_atomic_amount: stored.xmr_atomic_amount,
+                fee: Uint128::zero(),
+                destinations: vec![],
+                label: None,
+                swap_id: stored.swap_id,
+                monero_tx_id: stored.monero_tx_id.clone(),
+                fee_bps_at_creation: stored.fee_bps_at_creation,
+                scale_at_creation: stored.scale_at_creation,
+                status: stored.status.clone(),
+            }
+        );
+    }
+
+    #[test]
+    fn multi_destination_burn_rejects_amounts_not_summing_to_net() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Multi(vec![
+            ("monero-address-1".to_string(), Uint128(600)),
+            ("monero-address-2".to_string(), Uint128(300)),
+        ]))
+        .unwrap();
+
+        let res = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn multi_destination_burn_within_the_configured_cap_is_accepted() {
+        let mut deps = init_helper();
+        set_max_destinations_per_burn(&mut deps, mock_env("admin", &[]), 2).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Multi(vec![
+            ("monero-address-1".to_string(), Uint128(600)),
+            ("monero-address-2".to_string(), Uint128(400)),
+        ]))
+        .unwrap();
+
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn multi_destination_burn_above_the_configured_cap_is_rejected() {
+        let mut deps = init_helper();
+        set_max_destinations_per_burn(&mut deps, mock_env("admin", &[]), 1).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Multi(vec![
+            ("monero-address-1".to_string(), Uint128(600)),
+            ("monero-address-2".to_string(), Uint128(400)),
+        ]))
+        .unwrap();
+
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn multi_destination_burn_rejects_a_sub_dust_destination_amount() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_monero_dust_limit(Uint128(500));
+        let dest = to_binary(&crate::msg::BurnDestination::Multi(vec![
+            ("monero-address-1".to_string(), Uint128(900)),
+            ("monero-address-2".to_string(), Uint128(100)),
+        ]))
+        .unwrap();
+
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn setting_max_destinations_per_burn_as_non_admin_is_rejected() {
+        let mut deps = init_helper();
+        let result = set_max_destinations_per_burn(&mut deps, mock_env("alice", &[]), 2);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_above_dust_limit_succeeds_and_below_is_rejected() {
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_monero_dust_limit(Uint128(1500));
+        }
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+
+        let above = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(2000),
+            Some(dest.clone()),
+        );
+        assert!(above.is_ok());
+
+        let below = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(below.is_err());
+    }
+
+    #[test]
+    fn swap_label_round_trips_for_the_owner() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        set_swap_label(
+            &mut deps,
+            mock_env("alice", &[]),
+            0,
+            "rent payment".to_string(),
+        )
+        .unwrap();
+
+        let response = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "alice-key".to_string(),
+            None,
+        )
+        .unwrap();
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryResponse::SwapDetails {
+                from_secret_address: HumanAddr("alice".to_string()),
+                to_monero_address: "monero-address".to_string(),
+                payment_id: None,
+                memo: None,
+                amount: Uint128(1000),
+                xmr_atomic_amount: stored.xmr_atomic_amount,
+                fee: Uint128::zero(),
+                destinations: vec![],
+                label: Some("rent payment".to_string()),
+                swap_id: stored.swap_id,
+                monero_tx_id: stored.monero_tx_id.clone(),
+                fee_bps_at_creation: stored.fee_bps_at_creation,
+                scale_at_creation: stored.scale_at_creation,
+                status: stored.status.clone(),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn swap_created_before_a_fee_change_reflects_the_old_fee() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_fee_bps(50);
+        }
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_fee_bps(250);
+        }
+
+        let response = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            0,
+            None,
+            "alice-key".to_string(),
+            None,
+        )
+        .unwrap();
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.fee_bps_at_creation, 50);
+        assert_eq!(
+            response,
+            to_binary(&QueryResponse::SwapDetails {
+                from_secret_address: HumanAddr("alice".to_string()),
+                to_monero_address: "monero-address".to_string(),
+                payment_id: None,
+                memo: None,
+                amount: Uint128(995),
+                xmr_atomic_amount: stored.xmr_atomic_amount,
+                fee: Uint128(5),
+                destinations: vec![],
+                label: None,
+                swap_id: stored.swap_id,
+                monero_tx_id: stored.monero_tx_id.clone(),
+                fee_bps_at_creation: 50,
+                scale_at_creation: stored.scale_at_creation,
+                status: stored.status.clone(),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn swap_history_pages_newest_first_and_filters_by_owner() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        set_viewing_key(&mut deps, mock_env("bob", &[]), "bob-key".to_string()).unwrap();
+
+        for (owner, label) in [
+            ("alice", "alice-1"),
+            ("bob", "bob-1"),
+            ("alice", "alice-2"),
+        ] {
+            let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+                .unwrap();
+            burn_sxmr(
+                &mut deps,
+                mock_env("sxmr-token", &[]),
+                HumanAddr("sxmr-token".to_string()),
+                HumanAddr(owner.to_string()),
+                Uint128(1000),
+                Some(dest),
+            )
+            .unwrap();
+            let nonce = crate::state::ReadonlySwapDetailsStore::fetch_user_swaps(
+                &deps.storage,
+                &deps.api.canonical_address(&HumanAddr(owner.to_string())).unwrap(),
+                0,
+                10,
+            )
+            .unwrap()[0]
+                .nonce;
+            set_swap_label(&mut deps, mock_env(owner, &[]), nonce, label.to_string()).unwrap();
+        }
+
+        let response = query_swap_history(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            0,
+            10,
+        )
+        .unwrap();
+        let labels: Vec<Option<String>> = match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::SwapHistory { swaps } => swaps.into_iter().map(|s| s.label).collect(),
+            _ => panic!("expected SwapHistory"),
+        };
+        assert_eq!(
+            labels,
+            vec![Some("alice-2".to_string()), Some("alice-1".to_string())]
+        );
+    }
+
+    #[test]
+    fn swap_history_paginates() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        for _ in 0..3 {
+            let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+                .unwrap();
+            burn_sxmr(
+                &mut deps,
+                mock_env("sxmr-token", &[]),
+                HumanAddr("sxmr-token".to_string()),
+                HumanAddr("alice".to_string()),
+                Uint128(1000),
+                Some(dest),
+            )
+            .unwrap();
+        }
+
+        let response = query_swap_history(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            1,
+            2,
+        )
+        .unwrap();
+        let swaps = match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::SwapHistory { swaps } => swaps,
+            _ => panic!("expected SwapHistory"),
+        };
+        assert_eq!(swaps.len(), 1);
+    }
+
+    #[test]
+    fn swap_history_is_empty_when_no_swaps_have_ever_been_recorded() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+
+        let response = query_swap_history(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            0,
+            10,
+        )
+        .unwrap();
+        let swaps = match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::SwapHistory { swaps } => swaps,
+            _ => panic!("expected SwapHistory"),
+        };
+        assert!(swaps.is_empty());
+    }
+
+    #[test]
+    fn all_pending_swaps_lists_pending_swaps_across_users() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        )
+        .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("bob".to_string()),
+            Uint128(2000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let response = query_all_pending_swaps(&deps, "admin-key".to_string(), 0, 10).unwrap();
+        let swaps = match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::AllPendingSwaps { swaps } => swaps,
+            _ => panic!("expected AllPendingSwaps"),
+        };
+        assert_eq!(swaps.len(), 2);
+        assert_eq!(swaps[0].owner, HumanAddr("alice".to_string()));
+        assert_eq!(swaps[0].amount, Uint128(1000));
+        assert_eq!(swaps[1].owner, HumanAddr("bob".to_string()));
+        assert_eq!(swaps[1].amount, Uint128(2000));
+    }
+
+    #[test]
+    fn all_pending_swaps_excludes_completed_and_refunded_swaps() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        )
+        .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("bob".to_string()),
+            Uint128(2000),
+            Some(dest),
+        )
+        .unwrap();
+        complete_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let response = query_all_pending_swaps(&deps, "admin-key".to_string(), 0, 10).unwrap();
+        let swaps = match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::AllPendingSwaps { swaps } => swaps,
+            _ => panic!("expected AllPendingSwaps"),
+        };
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(swaps[0].owner, HumanAddr("bob".to_string()));
+    }
+
+    #[test]
+    fn swaps_by_status_lists_swaps_under_their_current_status() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        )
+        .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("bob".to_string()),
+            Uint128(2000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let pending = query_swaps_by_status(
+            &deps,
+            "admin-key".to_string(),
+            crate::state::SwapStatusFilter::Pending,
+            0,
+            10,
+        )
+        .unwrap();
+        let pending = match cosmwasm_std::from_binary(&pending).unwrap() {
+            QueryResponse::SwapsByStatus { swaps } => swaps,
+            _ => panic!("expected SwapsByStatus"),
+        };
+        assert_eq!(pending.len(), 2);
+
+        let completed = query_swaps_by_status(
+            &deps,
+            "admin-key".to_string(),
+            crate::state::SwapStatusFilter::Completed,
+            0,
+            10,
+        )
+        .unwrap();
+        let completed = match cosmwasm_std::from_binary(&completed).unwrap() {
+            QueryResponse::SwapsByStatus { swaps } => swaps,
+            _ => panic!("expected SwapsByStatus"),
+        };
+        assert!(completed.is_empty());
+    }
+
+    #[test]
+    fn swaps_by_status_moves_a_swap_between_lists_on_transition() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        complete_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let pending = query_swaps_by_status(
+            &deps,
+            "admin-key".to_string(),
+            crate::state::SwapStatusFilter::Pending,
+            0,
+            10,
+        )
+        .unwrap();
+        let pending = match cosmwasm_std::from_binary(&pending).unwrap() {
+            QueryResponse::SwapsByStatus { swaps } => swaps,
+            _ => panic!("expected SwapsByStatus"),
+        };
+        assert!(pending.is_empty());
+
+        let completed = query_swaps_by_status(
+            &deps,
+            "admin-key".to_string(),
+            crate::state::SwapStatusFilter::Completed,
+            0,
+            10,
+        )
+        .unwrap();
+        let completed = match cosmwasm_std::from_binary(&completed).unwrap() {
+            QueryResponse::SwapsByStatus { swaps } => swaps,
+            _ => panic!("expected SwapsByStatus"),
+        };
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].nonce, 0);
+    }
+
+    #[test]
+    fn export_swaps_streams_the_whole_book_across_two_pages() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        for i in 0..5 {
+            burn_sxmr(
+                &mut deps,
+                mock_env("sxmr-token", &[]),
+                HumanAddr("sxmr-token".to_string()),
+                HumanAddr(format!("user-{}", i)),
+                Uint128(1000),
+                Some(dest.clone()),
+            )
+            .unwrap();
+        }
+
+        let first_page = query_export_swaps(&deps, "admin-key".to_string(), 0, 3).unwrap();
+        let first_page = match cosmwasm_std::from_binary(&first_page).unwrap() {
+            QueryResponse::ExportSwaps { swaps } => swaps,
+            _ => panic!("expected ExportSwaps"),
+        };
+        assert_eq!(first_page.len(), 3);
+        assert_eq!(
+            first_page.iter().map(|s| s.nonce).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        let resume_from = first_page.last().unwrap().nonce + 1;
+        let second_page = query_export_swaps(&deps, "admin-key".to_string(), resume_from, 3).unwrap();
+        let second_page = match cosmwasm_std::from_binary(&second_page).unwrap() {
+            QueryResponse::ExportSwaps { swaps } => swaps,
+            _ => panic!("expected ExportSwaps"),
+        };
+        assert_eq!(
+            second_page.iter().map(|s| s.nonce).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+
+        let past_the_end = query_export_swaps(&deps, "admin-key".to_string(), 5, 3).unwrap();
+        let past_the_end = match cosmwasm_std::from_binary(&past_the_end).unwrap() {
+            QueryResponse::ExportSwaps { swaps } => swaps,
+            _ => panic!("expected ExportSwaps"),
+        };
+        assert!(past_the_end.is_empty());
+    }
+
+    #[test]
+    fn export_swaps_rejects_a_non_admin_viewing_key() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        let result = query_export_swaps(&deps, "alice-key".to_string(), 0, 10);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn export_swaps_clamps_limit_to_the_max_page_size() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let result = query_export_swaps(
+            &deps,
+            "admin-key".to_string(),
+            0,
+            MAX_EXPORT_PAGE_SIZE + 1_000,
+        )
+        .unwrap();
+        let swaps = match cosmwasm_std::from_binary(&result).unwrap() {
+            QueryResponse::ExportSwaps { swaps } => swaps,
+            _ => panic!("expected ExportSwaps"),
+        };
+        assert_eq!(swaps.len(), 1);
+    }
+
+    #[test]
+    fn swap_label_is_trimmed_and_rejects_control_characters_or_excess_length() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        set_swap_label(
+            &mut deps,
+            mock_env("alice", &[]),
+            0,
+            "  rent payment  ".to_string(),
+        )
+        .unwrap();
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.label, Some("rent payment".to_string()));
+
+        let too_long = set_swap_label(
+            &mut deps,
+            mock_env("alice", &[]),
+            0,
+            "a".repeat(crate::state::MAX_SWAP_LABEL_LEN + 1),
+        );
+        assert!(too_long.is_err());
+
+        let control_chars = set_swap_label(
+            &mut deps,
+            mock_env("alice", &[]),
+            0,
+            "rent\npayment".to_string(),
+        );
+        assert!(control_chars.is_err());
+    }
+
+    #[test]
+    fn swap_label_rejects_non_owner() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let result = set_swap_label(
+            &mut deps,
+            mock_env("mallory", &[]),
+            0,
+            "not mine".to_string(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn relayer_can_attach_a_payout_tx_to_a_fulfilled_swap() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        attach_payout_tx(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.monero_tx_id, Some("a".repeat(64)));
+        assert!(stored.resolved);
+
+        let counts = query_swap_counts(&deps).unwrap();
+        assert_eq!(
+            counts,
+            to_binary(&QueryResponse::SwapCounts {
+                pending: 0,
+                fulfilled: 1,
+                refunded: 0,
+                expired: 0,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn relayer_cannot_overwrite_an_attached_payout_tx_but_admin_can() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        attach_payout_tx(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let result = attach_payout_tx(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "b".repeat(64),
+        );
+        assert!(result.is_err());
+
+        attach_payout_tx(
+            &mut deps,
+            mock_env("admin", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "b".repeat(64),
+        )
+        .unwrap();
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.monero_tx_id, Some("b".repeat(64)));
+    }
+
+    #[test]
+    fn minter_can_complete_a_pending_swap() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        complete_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(
+            stored.status,
+            crate::state::SwapStatus::Completed {
+                monero_tx_id: "a".repeat(64)
+            }
+        );
+        assert!(stored.resolved);
+
+        let counts = query_swap_counts(&deps).unwrap();
+        assert_eq!(
+            counts,
+            to_binary(&QueryResponse::SwapCounts {
+                pending: 0,
+                fulfilled: 1,
+                refunded: 0,
+                expired: 0,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn completing_an_already_completed_swap_is_rejected() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        complete_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let result = complete_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "b".repeat(64),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn completing_a_swap_as_non_admin_non_minter_is_rejected() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let result = complete_swap(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn minter_can_refund_a_pending_swap_and_a_mint_message_is_produced() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let result = refund_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+        )
+        .unwrap();
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(
+            result.messages[0],
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: HumanAddr("sxmr-token".to_string()),
+                callback_code_hash: "sxmr-code-hash".to_string(),
+                msg: to_binary(&sxmr_token::msg::HandleMsg::Mint {
+                    recipient: HumanAddr("alice".to_string()),
+                    amount: Uint128(1000),
+                    memo: None,
+                    padding: None,
+                })
+                .unwrap(),
+                send: vec![],
+            })
+        );
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.status, crate::state::SwapStatus::Refunded);
+
+        let counts = query_swap_counts(&deps).unwrap();
+        assert_eq!(
+            counts,
+            to_binary(&QueryResponse::SwapCounts {
+                pending: 0,
+                fulfilled: 0,
+                refunded: 1,
+                expired: 0,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn refund_swap_restores_pending_liability_to_its_pre_burn_value() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_pending_liability(Uint128(5000));
+        let pre_burn = ReadonlyConfig::from_storage(&deps.storage).pending_liability();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128(4000)
+        );
+
+        refund_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            pre_burn
+        );
+    }
+
+    #[test]
+    fn refunding_a_swap_twice_is_rejected() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        refund_swap(&mut deps, mock_env("minter", &[]), HumanAddr("alice".to_string()), 0).unwrap();
+        let result = refund_swap(&mut deps, mock_env("minter", &[]), HumanAddr("alice".to_string()), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn refunding_an_already_completed_swap_is_rejected() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        complete_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let result = refund_swap(&mut deps, mock_env("minter", &[]), HumanAddr("alice".to_string()), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn owner_can_cancel_their_own_pending_swap() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let result = cancel_swap(&mut deps, mock_env("alice", &[]), 0).unwrap();
+        assert_eq!(result.messages.len(), 1);
+
+        let stored = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, 0).unwrap();
+        assert_eq!(stored.status, crate::state::SwapStatus::Refunded);
+    }
+
+    #[test]
+    fn cancel_swap_restores_pending_liability_to_its_pre_burn_value() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_pending_liability(Uint128(5000));
+        let pre_burn = ReadonlyConfig::from_storage(&deps.storage).pending_liability();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128(4000)
+        );
+
+        cancel_swap(&mut deps, mock_env("alice", &[]), 0).unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            pre_burn
+        );
+    }
+
+    #[test]
+    fn cancel_swap_is_rejected_once_a_relayer_has_claimed_it_for_processing() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        mark_swap_processing(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+        )
+        .unwrap();
+
+        let result = cancel_swap(&mut deps, mock_env("alice", &[]), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cancel_swap_is_rejected_on_an_already_completed_swap() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        complete_swap(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            0,
+            "a".repeat(64),
+        )
+        .unwrap();
+
+        let result = cancel_swap(&mut deps, mock_env("alice", &[]), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cancel_swap_cannot_be_called_on_someone_elses_swap() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let result = cancel_swap(&mut deps, mock_env("bob", &[]), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn owner_can_consolidate_several_pending_swaps_to_the_same_destination() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        for _ in 0..3 {
+            burn_sxmr(
+                &mut deps,
+                mock_env("sxmr-token", &[]),
+                HumanAddr("sxmr-token".to_string()),
+                HumanAddr("alice".to_string()),
+                Uint128(1000),
+                Some(dest.clone()),
+            )
+            .unwrap();
+        }
+
+        let result = consolidate_swaps(
+            &mut deps,
+            mock_env("alice", &[]),
+            vec![0, 1, 2],
+            "monero-address".to_string(),
+        )
+        .unwrap();
+
+        let new_nonce = match cosmwasm_std::from_binary(&result.data.unwrap()).unwrap() {
+            HandleResult::ConsolidateSwaps { status, new_nonce } => {
+                assert_eq!(status, "success");
+                new_nonce
+            }
+            _ => panic!("unexpected result"),
+        };
+        assert_eq!(new_nonce, 3);
+
+        let merged = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, new_nonce)
+            .unwrap();
+        assert_eq!(merged.amount, Uint128(3000));
+        assert_eq!(merged.to_monero_address, "monero-address".to_string());
+        assert_eq!(merged.status, crate::state::SwapStatus::Pending);
+
+        for nonce in 0..3 {
+            let old = crate::state::ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, nonce)
+                .unwrap();
+            assert_eq!(
+                old.status,
+                crate::state::SwapStatus::Consolidated {
+                    into_nonce: new_nonce
+                }
+            );
+        }
+
+        let counts = query_swap_counts(&deps).unwrap();
+        assert_eq!(
+            counts,
+            to_binary(&QueryResponse::SwapCounts {
+                pending: 1,
+                fulfilled: 0,
+                refunded: 0,
+                expired: 0,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn consolidating_swaps_with_mixed_destinations_is_rejected() {
+        let mut deps = init_helper();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(
+                to_binary(&crate::msg::BurnDestination::Single("monero-address-a".to_string()))
+                    .unwrap(),
+            ),
+        )
+        .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(
+                to_binary(&crate::msg::BurnDestination::Single("monero-address-b".to_string()))
+                    .unwrap(),
+            ),
+        )
+        .unwrap();
+
+        let result = consolidate_swaps(
+            &mut deps,
+            mock_env("alice", &[]),
+            vec![0, 1],
+            "monero-address-a".to_string(),
+        );
+        assert!(result.is_err());
+
+        let counts = query_swap_counts(&deps).unwrap();
+        assert_eq!(
+            counts,
+            to_binary(&QueryResponse::SwapCounts {
+                pending: 2,
+                fulfilled: 0,
+                refunded: 0,
+                expired: 0,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn consolidating_someone_elses_swap_is_rejected() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let result = consolidate_swaps(
+            &mut deps,
+            mock_env("bob", &[]),
+            vec![0],
+            "monero-address".to_string(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn known_recipient_requirement_rejects_cold_address_when_on() {
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_require_known_recipient(true);
+        }
+
+        let proof = MoneroProof {
+            tx_id: "tx-cold".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("cold-recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn known_recipient_requirement_allows_cold_address_when_off() {
+        let mut deps = init_helper();
+
+        let proof = MoneroProof {
+            tx_id: "tx-cold-2".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("cold-recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn config_size_matches_actual_serialization_length() {
+        let deps = init_helper();
+        let constants = ReadonlyConfig::from_storage(&deps.storage).constants().unwrap();
+        let expected = bincode2::serialize(&constants).unwrap().len() as u64;
+
+        let response = query_config_size(&deps).unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryResponse::ConfigSize { bytes: expected }).unwrap()
+        );
+    }
+
+    #[test]
+    fn monotonic_proof_order_accepts_in_order_proofs() {
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_enforce_monotonic_proof_order(true);
+        }
+
+        let first = MoneroProof {
+            tx_id: "tx-order-1".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let second = MoneroProof {
+            tx_id: "tx-order-2".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 150,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+
+        assert!(mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            first,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .is_ok());
+        assert!(mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            second,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn monotonic_proof_order_rejects_out_of_order_proof() {
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_enforce_monotonic_proof_order(true);
+        }
+
+        let first = MoneroProof {
+            tx_id: "tx-order-3".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 200,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let stale = MoneroProof {
+            tx_id: "tx-order-4".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+
+        mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            first,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            stale,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn whitelisted_destination_skips_revalidation_but_honors_blocklist() {
+        let mut deps = init_helper();
+        whitelist_destination(
+            &mut deps,
+            mock_env("alice", &[]),
+            "monero-address".to_string(),
+        )
+        .unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        let accepted = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        );
+        assert!(accepted.is_ok());
+
+        BlockedDestinationsStore::block(&mut deps.storage, "monero-address");
+        let blocked = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(blocked.is_err());
+    }
+
+    #[test]
+    fn include_token_info_flag_adds_token_address_to_mint_result() {
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_include_token_info_in_result(true);
+        }
+
+        let proof = MoneroProof {
+            tx_id: "tx-token-info".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let response = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.data,
+            Some(
+                to_binary(&HandleResult::MintSecretMonero {
+                    status: "success".to_string(),
+                    token_address: Some(HumanAddr("sxmr-token".to_string())),
+                    token_contract_hash: Some("sxmr-code-hash".to_string()),
+                    auto_viewing_key: None,
+                    tx_id: "tx-token-info".to_string(),
+                    recipient: HumanAddr("recipient".to_string()),
+                    amount: Uint128(1000),
+                })
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn pausing_flips_the_mint_capability_to_false() {
+        let mut deps = init_helper();
+        let running = query_capabilities(&deps).unwrap();
+        assert_eq!(
+            running,
+            to_binary(&QueryResponse::Capabilities {
+                capabilities: vec![
+                    ("mint".to_string(), true),
+                    ("burn".to_string(), true),
+                    ("set_contract_status".to_string(), true),
+                ],
+            })
+            .unwrap()
+        );
+
+        set_contract_status(&mut deps, mock_env("admin", &[]), ContractStatusLevel::Paused).unwrap();
+
+        let paused = query_capabilities(&deps).unwrap();
+        assert_eq!(
+            paused,
+            to_binary(&QueryResponse::Capabilities {
+                capabilities: vec![
+                    ("mint".to_string(), false),
+                    ("burn".to_string(), false),
+                    ("set_contract_status".to_string(), true),
+                ],
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn my_roles_reports_admin_and_relayer_and_denies_an_unrelated_address() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        set_viewing_key(&mut deps, mock_env("minter", &[]), "minter-key".to_string()).unwrap();
+        set_viewing_key(&mut deps, mock_env("eve", &[]), "eve-key".to_string()).unwrap();
+
+        let admin_roles = query_my_roles(
+            &deps,
+            HumanAddr("admin".to_string()),
+            "admin-key".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            admin_roles,
+            to_binary(&QueryResponse::MyRoles {
+                is_admin: true,
+                is_minter: false,
+                is_relayer: false,
+            })
+            .unwrap()
+        );
+
+        // init_helper's "minter" is both the relayer (`bridge_minter`) and
+        // the sole entry in the bookkeeping `minters` list.
+        let relayer_roles = query_my_roles(
+            &deps,
+            HumanAddr("minter".to_string()),
+            "minter-key".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            relayer_roles,
+            to_binary(&QueryResponse::MyRoles {
+                is_admin: false,
+                is_minter: true,
+                is_relayer: true,
+            })
+            .unwrap()
+        );
+
+        let random_roles =
+            query_my_roles(&deps, HumanAddr("eve".to_string()), "eve-key".to_string()).unwrap();
+        assert_eq!(
+            random_roles,
+            to_binary(&QueryResponse::MyRoles {
+                is_admin: false,
+                is_minter: false,
+                is_relayer: false,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn same_tx_id_with_different_output_index_is_not_a_duplicate() {
+        let mut deps = init_helper();
+        let first = MoneroProof {
+            tx_id: "tx-multi-output".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let second = MoneroProof {
+            tx_id: "tx-multi-output".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 1,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+
+        assert!(mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            first,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .is_ok());
+        assert!(mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            second,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn same_tx_id_and_output_index_is_rejected_as_duplicate() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-repeat-output".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 2,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+
+        mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof.clone(),
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn duplicate_check_stays_correct_and_indexed_across_thousands_of_proofs() {
+        let mut deps = init_helper();
+        for i in 0..3000u64 {
+            let proof = MoneroProof {
+                tx_id: format!("tx-{}", i),
+                tx_key: "key".to_string(),
+                address: "bridge-wallet".to_string(),
+                block_height: 100 + i,
+                output_index: 0,
+                xmr_atomic_amount: None,
+                amount: Uint128::zero(),
+            };
+            MoneroProofsStore::save(&mut deps.storage, &proof).unwrap();
+        }
+
+        // The append store and the index agree on both the first and last
+        // proof recorded, regardless of scan order.
+        assert!(MoneroProofsStore::is_duplicate(&deps.storage, "tx-0", 0).unwrap());
+        assert!(MoneroProofsStore::is_duplicate(&deps.storage, "tx-2999", 0).unwrap());
+        assert!(!MoneroProofsStore::is_duplicate(&deps.storage, "tx-2999", 1).unwrap());
+        assert!(!MoneroProofsStore::is_duplicate(&deps.storage, "tx-unseen", 0).unwrap());
+
+        let first = MoneroProofsStore::fetch_by_tx_id(&deps.storage, "tx-0")
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.block_height, 100);
+        let last = MoneroProofsStore::fetch_by_tx_id(&deps.storage, "tx-2999")
+            .unwrap()
+            .unwrap();
+        assert_eq!(last.block_height, 100 + 2999);
+    }
+
+    /// Signs `params` with `secret_key` the same way a wallet would, and
+    /// builds the `Permit` a `QueryMsg::WithPermit` carries.
+    fn sign_permit(
+        secret_key: &secp256k1::SecretKey,
+        params: crate::permit::PermitParams,
+    ) -> crate::permit::Permit {
+        use sha2::Digest;
+
+        let secp = secp256k1::Secp256k1::new();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, secret_key);
+        let pubkey_bytes = pubkey.serialize().to_vec();
+
+        let sign_bytes = crate::permit::sign_doc_bytes(&params);
+        let hash = sha2::Sha256::digest(&sign_bytes);
+        let message = secp256k1::Message::from_slice(hash.as_slice()).unwrap();
+        let signature = secp.sign(&message, secret_key).serialize_compact().to_vec();
+
+        crate::permit::Permit {
+            params,
+            signature: crate::permit::PermitSignature {
+                pub_key: crate::permit::PermitPubKey {
+                    key_type: "tendermint/PubKeySecp256k1".to_string(),
+                    value: Binary(pubkey_bytes),
+                },
+                signature: Binary(signature),
+            },
+        }
+    }
+
+    #[test]
+    fn a_valid_permit_authenticates_its_signer_for_swap_details() {
+        let mut deps = init_helper();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let secp = secp256k1::Secp256k1::new();
+        let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+        let owner = crate::permit::address_from_pubkey(&pubkey.serialize()).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            owner.clone(),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let contract_address = mock_env("admin", &[]).contract.address;
+        let permit = sign_permit(
+            &secret_key,
+            crate::permit::PermitParams {
+                permit_name: "test-permit".to_string(),
+                chain_id: "secret-4".to_string(),
+                allowed_tokens: vec![contract_address],
+                permissions: vec![crate::permit::Permission::Owner],
+            },
+        );
+
+        let response = query_with_permit(
+            &deps,
+            permit,
+            crate::msg::QueryWithPermit::SwapDetails {
+                nonce: 0,
+                swap_id: None,
+                encrypt_to: None,
+            },
+        )
+        .unwrap();
+        match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryResponse::SwapDetails { from_secret_address, amount, .. } => {
+                assert_eq!(from_secret_address, owner);
+                assert_eq!(amount, Uint128(1000));
+            }
+            _ => panic!("expected SwapDetails"),
+        }
+    }
+
+    #[test]
+    fn a_permit_scoped_to_a_different_contract_is_rejected() {
+        let deps = init_helper();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let permit = sign_permit(
+            &secret_key,
+            crate::permit::PermitParams {
+                permit_name: "test-permit".to_string(),
+                chain_id: "secret-4".to_string(),
+                allowed_tokens: vec![HumanAddr("some-other-contract".to_string())],
+                permissions: vec![crate::permit::Permission::Owner],
+            },
+        );
+
+        let result = query_with_permit(
+            &deps,
+            permit,
+            crate::msg::QueryWithPermit::SwapDetails {
+                nonce: 0,
+                swap_id: None,
+                encrypt_to: None,
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn a_permit_with_a_tampered_signature_is_rejected() {
+        let deps = init_helper();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let contract_address = mock_env("admin", &[]).contract.address;
+        let mut permit = sign_permit(
+            &secret_key,
+            crate::permit::PermitParams {
+                permit_name: "test-permit".to_string(),
+                chain_id: "secret-4".to_string(),
+                allowed_tokens: vec![contract_address],
+                permissions: vec![crate::permit::Permission::Owner],
+            },
+        );
+        let mut tampered = permit.signature.signature.0.clone();
+        tampered[0] ^= 0xff;
+        permit.signature.signature = Binary(tampered);
+
+        let result = query_with_permit(
+            &deps,
+            permit,
+            crate::msg::QueryWithPermit::SwapDetails {
+                nonce: 0,
+                swap_id: None,
+                encrypt_to: None,
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn only_expired_swaps_are_swept() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_swap_ttl_seconds(1000);
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let mut env = mock_env("sxmr-token", &[]);
+        env.block.time = 1_000_000;
+        burn_sxmr(
+            &mut deps,
+            env,
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        )
+        .unwrap();
+
+        let mut env = mock_env("sxmr-token", &[]);
+        env.block.time = 1_000_500;
+        burn_sxmr(
+            &mut deps,
+            env,
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("bob".to_string()),
+            Uint128(2000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let mut env = mock_env("minter", &[]);
+        env.block.time = 1_002_001;
+        let result = sweep_expired_swaps(&mut deps, env, 10).unwrap();
+        assert_eq!(
+            result.data,
+            Some(to_binary(&HandleResult::SweepExpiredSwaps { processed: 1 }).unwrap())
+        );
+        assert_eq!(result.messages.len(), 1);
+
+        let counts = ReadonlyConfig::from_storage(&deps.storage).swap_counts();
+        assert_eq!(counts.pending, 1);
+        assert_eq!(counts.expired, 1);
+    }
+
+    #[test]
+    fn sweep_expired_swaps_respects_the_limit() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_swap_ttl_seconds(1000);
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        for sender in ["alice", "bob", "carol"] {
+            let mut env = mock_env("sxmr-token", &[]);
+            env.block.time = 1_000_000;
+            burn_sxmr(
+                &mut deps,
+                env,
+                HumanAddr("sxmr-token".to_string()),
+                HumanAddr(sender.to_string()),
+                Uint128(1000),
+                Some(dest.clone()),
+            )
+            .unwrap();
+        }
+
+        let mut env = mock_env("minter", &[]);
+        env.block.time = 1_002_001;
+        let result = sweep_expired_swaps(&mut deps, env, 2).unwrap();
+        assert_eq!(
+            result.data,
+            Some(to_binary(&HandleResult::SweepExpiredSwaps { processed: 2 }).unwrap())
+        );
+
+        let counts = ReadonlyConfig::from_storage(&deps.storage).swap_counts();
+        assert_eq!(counts.pending, 1);
+        assert_eq!(counts.expired, 2);
+    }
+
+    #[test]
+    fn sweep_expired_swaps_restores_pending_liability_to_its_pre_burn_value() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_swap_ttl_seconds(1000);
+        Config::from_storage(&mut deps.storage).set_pending_liability(Uint128(5000));
+        let pre_burn = ReadonlyConfig::from_storage(&deps.storage).pending_liability();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let mut env = mock_env("sxmr-token", &[]);
+        env.block.time = 1_000_000;
+        burn_sxmr(
+            &mut deps,
+            env,
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128(4000)
+        );
+
+        let mut env = mock_env("minter", &[]);
+        env.block.time = 1_002_001;
+        sweep_expired_swaps(&mut deps, env, 10).unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            pre_burn
+        );
+    }
+
+    #[test]
+    fn swap_limits_human_strings_match_the_raw_values() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_max_swap_amount(Uint128(5_000_000_000_000));
+
+        let result = query(&deps, QueryMsg::SwapLimits {}).unwrap();
+        assert_eq!(
+            result,
+            to_binary(&QueryResponse::SwapLimits {
+                min_swap_amount: Uint128(1000),
+                min_swap_amount_human: "0.000000001000".to_string(),
+                max_swap_amount: Uint128(5_000_000_000_000),
+                max_swap_amount_human: "5.000000000000".to_string(),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn a_known_receipt_hash_resolves_to_the_correct_swap() {
+        let mut deps = init_helper();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let owner = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let swap = ReadonlySwapDetailsStore::fetch_swap_details(&deps.storage, &owner, 0).unwrap();
+        let receipt_hash = swap.receipt_hash();
+
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+
+        let result = query(
+            &deps,
+            QueryMsg::SwapByReceipt {
+                admin_viewing_key: "admin-key".to_string(),
+                receipt_hash,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            to_binary(&QueryResponse::SwapByReceipt {
+                owner: HumanAddr("alice".to_string()),
+                nonce: 0,
+                to_monero_address: "monero-address".to_string(),
+                amount: Uint128(1000),
+                destinations: vec![],
+                label: None,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn setting_a_valid_fee_collector_updates_config() {
+        let mut deps = init_helper();
+        set_fee_collector(&mut deps, mock_env("admin", &[]), HumanAddr("collector".to_string())).unwrap();
+
+        let collector = ReadonlyConfig::from_storage(&deps.storage).fee_collector().unwrap();
+        assert_eq!(
+            deps.api.human_address(&collector).unwrap(),
+            HumanAddr("collector".to_string())
+        );
+    }
+
+    #[test]
+    fn setting_an_empty_fee_collector_is_rejected() {
+        let mut deps = init_helper();
+        let result = set_fee_collector(&mut deps, mock_env("admin", &[]), HumanAddr("".to_string()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn swap_nonces_stay_unique_even_if_the_append_store_is_manipulated() {
+        use crate::state::PREFIX_SWAP_DETAILS;
+        use cosmwasm_storage::PrefixedStorage;
+        use secret_toolkit::storage::AppendStoreMut;
+
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+
+        let nonce_0 = burn_first_swap(&mut deps, "alice", &dest);
+        assert_eq!(nonce_0, 0);
+
+        // Simulate a corrupted/misreported append store: push a record
+        // directly, bypassing the nonce counter, so the store's length no
+        // longer reflects how many nonces have actually been assigned.
+        let ghost = deps.api.canonical_address(&HumanAddr("ghost".to_string())).unwrap();
+        {
+            let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, &mut deps.storage);
+            let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store).unwrap();
+            store
+                .push(&SwapDetails {
+                    from_secret_address: ghost,
+                    to_monero_address: "ghost-address".to_string(),
+                    payment_id: None,
+                    memo: None,
+                    amount: Uint128(1),
+                    xmr_atomic_amount: 1,
+                    fee_taken: Uint128::zero(),
+                    monero_tx_id: None,
+                    destinations: vec![],
+                    label: None,
+                    created_at: 0,
+                    resolved: false,
+                    nonce: 999,
+                    swap_id: "ghost-swap-id".to_string(),
+                    fee_bps_at_creation: 0,
+                    scale_at_creation: 0,
+                    status: crate::state::SwapStatus::Pending,
+                })
+                .unwrap();
+        }
+
+        let nonce_1 = burn_first_swap(&mut deps, "bob", &dest);
+        let nonce_2 = burn_first_swap(&mut deps, "carol", &dest);
+        assert_eq!(nonce_1, 1);
+        assert_eq!(nonce_2, 2);
+    }
+
+    fn burn_first_swap(
+        deps: &mut Extern<
+            cosmwasm_std::testing::MockStorage,
+            cosmwasm_std::testing::MockApi,
+            cosmwasm_std::testing::MockQuerier,
+        >,
+        sender: &str,
+        dest: &Binary,
+    ) -> u32 {
+        let response = burn_sxmr(
+            deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr(sender.to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        )
+        .unwrap();
+        response
+            .log
+            .iter()
+            .find(|l| l.key == "nonce")
+            .map(|l| l.value.parse().unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn proofs_by_block_range_excludes_proofs_outside_the_range() {
+        let mut deps = init_helper();
+        for (tx_id, block_height) in [("tx-a", 100u64), ("tx-b", 150), ("tx-c", 200)] {
+            let proof = MoneroProof {
+                tx_id: tx_id.to_string(),
+                tx_key: "key".to_string(),
+                address: "bridge-wallet".to_string(),
+                block_height,
+                output_index: 0,
+                xmr_atomic_amount: None,
+                amount: Uint128::zero(),
+            };
+            mint_sxmr(
+                &mut deps,
+                mock_env("minter", &[]),
+                proof,
+                HumanAddr("recipient".to_string()),
+                Uint128(1000),
+            )
+            .unwrap();
+        }
+
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+
+        let result = query(
+            &deps,
+            QueryMsg::ProofsByBlockRange {
+                admin_viewing_key: "admin-key".to_string(),
+                from: 120,
+                to: 180,
+                page: 0,
+                page_size: 10,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            to_binary(&QueryResponse::ProofsByBlockRange {
+                proofs: vec![crate::query_messages::ProofSummary {
+                    tx_id: "tx-b".to_string(),
+                    block_height: 150,
+                    output_index: 0,
+                    amount: Uint128(1000),
+                    recipient: Some(HumanAddr("recipient".to_string())),
+                }],
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn proof_by_tx_id_returns_the_recorded_amount_and_recipient_after_a_mint() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-audit".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+
+        let result = query(
+            &deps,
+            QueryMsg::ProofByTxId {
+                admin_viewing_key: "admin-key".to_string(),
+                tx_id: "tx-audit".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            to_binary(&QueryResponse::ProofByTxId {
+                proof: Some(crate::query_messages::ProofSummary {
+                    tx_id: "tx-audit".to_string(),
+                    block_height: 100,
+                    output_index: 0,
+                    amount: Uint128(1000),
+                    recipient: Some(HumanAddr("recipient".to_string())),
+                }),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn proof_by_tx_id_returns_none_for_an_unknown_tx_id() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+
+        let result = query(
+            &deps,
+            QueryMsg::ProofByTxId {
+                admin_viewing_key: "admin-key".to_string(),
+                tx_id: "unknown-tx".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            to_binary(&QueryResponse::ProofByTxId { proof: None }).unwrap()
+        );
+    }
+
+    #[test]
+    fn auto_vk_on_mint_sets_a_viewing_key_for_a_cold_recipient() {
+        let mut deps = init_helper();
+        Config::from_storage(&mut deps.storage).set_auto_vk_on_mint(true);
+
+        let proof = MoneroProof {
+            tx_id: "tx-auto-vk".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let recipient = HumanAddr("cold-recipient".to_string());
+        let response = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            recipient.clone(),
+            Uint128(1000),
+        )
+        .unwrap();
+
+        let returned_key = match cosmwasm_std::from_binary::<HandleResult>(&response.data.unwrap()).unwrap() {
+            HandleResult::MintSecretMonero { auto_viewing_key, .. } => {
+                auto_viewing_key.expect("expected an auto-generated viewing key")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        let recipient_canonical = deps.api.canonical_address(&recipient).unwrap();
+        assert!(crate::state::is_known_recipient(&deps.storage, &recipient_canonical));
+
+        use crate::state::PREFIX_VIEWING_KEY;
+        use cosmwasm_storage::ReadonlyPrefixedStorage;
+        let store = ReadonlyPrefixedStorage::new(PREFIX_VIEWING_KEY, &deps.storage);
+        let hashed = store.get(recipient_canonical.as_slice()).unwrap();
+        assert!(crate::viewing_key::ViewingKey(returned_key).check_viewing_key(&hashed));
+    }
+
+    #[test]
+    fn re_register_receive_emits_a_register_message_with_the_new_hash() {
+        let mut deps = init_helper();
+        let mut env = mock_env("admin", &[]);
+        env.contract_code_hash = "new-code-hash".to_string();
+
+        let response = re_register_receive(&mut deps, env).unwrap();
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(
+            response.messages[0],
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: HumanAddr("sxmr-token".to_string()),
+                callback_code_hash: "sxmr-code-hash".to_string(),
+                msg: to_binary(&sxmr_token::msg::HandleMsg::RegisterReceive {
+                    code_hash: "new-code-hash".to_string(),
+                    padding: None,
+                })
+                .unwrap(),
+                send: vec![],
+            })
+        );
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).registered_code_hash(),
+            Some("new-code-hash".to_string())
+        );
+    }
+
+    #[test]
+    fn decimal_alignment_accepts_aligned_and_rejects_misaligned_mints() {
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_enforce_decimal_alignment(true);
+            config.set_sxmr_decimals(6);
+        }
+
+        let aligned_proof = MoneroProof {
+            tx_id: "tx-aligned".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        // 6 fewer decimals than Monero's 12 means every aligned amount is a
+        // multiple of 10^6.
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            aligned_proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_000_000),
+        );
+        assert!(result.is_ok());
+
+        let misaligned_proof = MoneroProof {
+            tx_id: "tx-misaligned".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            misaligned_proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_000_001),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn is_proof_used_reflects_a_mint() {
+        let mut deps = init_helper();
+        let query_used = || -> bool {
+            match cosmwasm_std::from_binary::<QueryResponse>(
+                &query_is_proof_used(&deps, "tx-is-used".to_string()).unwrap(),
+            )
+            .unwrap()
+            {
+                QueryResponse::IsProofUsed { used } => used,
+                other => panic!("unexpected response: {:?}", other),
+            }
+        };
+
+        assert!(!query_used());
+
+        let proof = MoneroProof {
+            tx_id: "tx-is-used".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_000),
+        )
+        .unwrap();
+
+        assert!(query_used());
+    }
+
+    #[test]
+    fn mint_accepts_a_proof_from_a_configured_bridge_wallet() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-known-wallet".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_000),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn mint_rejects_a_proof_from_an_unconfigured_wallet() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-unknown-wallet".to_string(),
+            tx_key: "key".to_string(),
+            address: "some-other-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_000),
+        );
+        assert!(result.is_err());
+    }
+
+    fn batch_proof(tx_id: &str) -> MoneroProof {
+        MoneroProof {
+            tx_id: tx_id.to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        }
+    }
+
+    #[test]
+    fn batch_mint_mints_three_items_in_one_call() {
+        let mut deps = init_helper();
+        let mints = vec![
+            crate::msg::MintItem {
+                proof: batch_proof("tx-batch-1"),
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1_000),
+            },
+            crate::msg::MintItem {
+                proof: batch_proof("tx-batch-2"),
+                recipient: HumanAddr("bob".to_string()),
+                amount: Uint128(2_000),
+            },
+            crate::msg::MintItem {
+                proof: batch_proof("tx-batch-3"),
+                recipient: HumanAddr("carol".to_string()),
+                amount: Uint128(3_000),
+            },
+        ];
+
+        let response = batch_mint(&mut deps, mock_env("minter", &[]), mints).unwrap();
+        assert_eq!(response.messages.len(), 3);
+        match cosmwasm_std::from_binary::<HandleResult>(&response.data.unwrap()).unwrap() {
+            HandleResult::BatchMint { tx_ids } => {
+                assert_eq!(tx_ids, vec!["tx-batch-1", "tx-batch-2", "tx-batch-3"]);
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        for tx_id in ["tx-batch-1", "tx-batch-2", "tx-batch-3"] {
+            assert!(MoneroProofsStore::is_duplicate(&deps.storage, tx_id, 0).unwrap());
+        }
+    }
+
+    #[test]
+    fn batch_mint_with_a_duplicate_item_commits_nothing() {
+        let mut deps = init_helper();
+        let mints = vec![
+            crate::msg::MintItem {
+                proof: batch_proof("tx-batch-dup-1"),
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1_000),
+            },
+            crate::msg::MintItem {
+                proof: batch_proof("tx-batch-dup-1"),
+                recipient: HumanAddr("bob".to_string()),
+                amount: Uint128(2_000),
+            },
+        ];
+
+        let result = batch_mint(&mut deps, mock_env("minter", &[]), mints);
+        assert!(result.is_err());
+        assert!(!MoneroProofsStore::is_duplicate(&deps.storage, "tx-batch-dup-1", 0).unwrap());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn batch_mint_rejects_a_batch_over_the_size_cap() {
+        let mut deps = init_helper();
+        let mints = (0..MAX_BATCH_MINT_SIZE + 1)
+            .map(|i| crate::msg::MintItem {
+                proof: batch_proof(&format!("tx-batch-cap-{}", i)),
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1_000),
+            })
+            .collect();
+
+        let result = batch_mint(&mut deps, mock_env("minter", &[]), mints);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_accepts_an_amount_correctly_scaled_from_the_proof() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-scaled".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: Some(1_000),
+            amount: Uint128::zero(),
+        };
+        // init_helper configures sxmr_decimals: 12, matching MONERO_DECIMALS,
+        // so the scaled amount is the atomic amount unchanged.
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_000),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn mint_rejects_an_amount_that_does_not_match_the_proofs_scaled_xmr_amount() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-mismatched".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: Some(1_000),
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_001),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn pending_liability_human_matches_raw_total() {
+        let mut deps = init_helper();
+
+        let proof = MoneroProof {
+            tx_id: "tx-liability".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1_234_000_000_000),
+        )
+        .unwrap();
+
+        let raw = ReadonlyConfig::from_storage(&deps.storage).pending_liability();
+        assert_eq!(raw, Uint128(1_234_000_000_000));
+
+        let result = query(&deps, QueryMsg::PendingLiabilityHuman {}).unwrap();
+        assert_eq!(
+            result,
+            to_binary(&QueryResponse::PendingLiabilityHuman {
+                raw,
+                human: crate::state::format_units(raw, crate::state::MONERO_DECIMALS),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn swap_id_resolves_consistently_across_a_simulated_compaction() {
+        use crate::state::{ReadonlySwapDetailsStore, SwapIdIndexStore, PREFIX_SWAP_DETAILS};
+        use cosmwasm_storage::PrefixedStorage;
+        use secret_toolkit::storage::AppendStoreMut;
+
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+
+        let nonce = burn_first_swap(&mut deps, "alice", &dest);
+        let swap = ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, nonce).unwrap();
+        let swap_id = swap.swap_id.clone();
+        assert!(!swap_id.is_empty());
+
+        let before = ReadonlySwapDetailsStore::fetch_by_swap_id(&deps.storage, &swap_id).unwrap();
+        assert_eq!(before.amount, swap.amount);
+
+        // Simulate compaction: the record moves to a new append-store
+        // position, and the id index is repointed at the new position, the
+        // way a real compaction migration would.
+        let new_nonce = {
+            let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, &mut deps.storage);
+            let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store).unwrap();
+            store.push(&swap).unwrap();
+            store.len() - 1
+        };
+        SwapIdIndexStore::save(&mut deps.storage, &swap_id, new_nonce).unwrap();
+
+        let after = ReadonlySwapDetailsStore::fetch_by_swap_id(&deps.storage, &swap_id).unwrap();
+        assert_eq!(after.amount, before.amount);
+        assert_eq!(after.to_monero_address, before.to_monero_address);
+        assert_ne!(new_nonce, nonce);
+    }
+
+    #[test]
+    fn burn_is_blocked_inside_the_maintenance_window_and_allowed_outside_it() {
+        let mut deps = init_helper();
+        set_maintenance_window(&mut deps, mock_env("admin", &[]), 100, 200).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+
+        let mut inside_env = mock_env("sxmr-token", &[]);
+        inside_env.block.height = 150;
+        let inside = burn_sxmr(
+            &mut deps,
+            inside_env,
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        );
+        assert!(inside.is_err());
+
+        let mut outside_env = mock_env("sxmr-token", &[]);
+        outside_env.block.height = 201;
+        let outside = burn_sxmr(
+            &mut deps,
+            outside_env,
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(outside.is_ok());
+    }
+
+    #[test]
+    fn sender_equals_from_policy_is_enforced_only_when_enabled() {
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+
+        // Policy off (default): a mismatching sender/from still succeeds.
+        let mut deps = init_helper();
+        let mismatched = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("carol".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        );
+        assert!(mismatched.is_ok());
+
+        // Policy on: a matching sender/from still succeeds...
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_require_sender_equals_from(true);
+        }
+        let matched = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("alice".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest.clone()),
+        );
+        assert!(matched.is_ok());
+
+        // ...but a mismatch is rejected.
+        let mismatched_enforced = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("carol".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        );
+        assert!(mismatched_enforced.is_err());
+    }
+
+    #[test]
+    fn revert_mint_frees_the_tx_id_and_moves_the_amount_to_shortfall_debt() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-reorg".to_string(),
+            tx_key: "tx-key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof: proof.clone(),
+                recipient: HumanAddr("recipient".to_string()),
+                amount: Uint128(1000),
+            },
+        )
+        .unwrap();
+
+        assert!(MoneroProofsStore::is_duplicate(&deps.storage, &proof.tx_id, proof.output_index).unwrap());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128(1000)
+        );
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).shortfall_debt(),
+            Uint128(0)
+        );
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::RevertMint {
+                tx_id: proof.tx_id.clone(),
+                output_index: proof.output_index,
+            },
+        )
+        .unwrap();
+
+        assert!(!MoneroProofsStore::is_duplicate(&deps.storage, &proof.tx_id, proof.output_index).unwrap());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128(0)
+        );
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).shortfall_debt(),
+            Uint128(1000)
+        );
+
+        // Reverting the same deposit twice is rejected.
+        let repeat = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::RevertMint {
+                tx_id: proof.tx_id,
+                output_index: proof.output_index,
+            },
+        );
+        assert!(repeat.is_err());
+    }
+
+    #[test]
+    fn revert_mint_only_writes_off_the_reorged_output_not_the_whole_tx_id() {
+        let mut deps = init_helper();
+        let output0 = MoneroProof {
+            tx_id: "tx-shared".to_string(),
+            tx_key: "tx-key-0".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let output1 = MoneroProof {
+            output_index: 1,
+            tx_key: "tx-key-1".to_string(),
+            ..output0.clone()
+        };
+
+        handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof: output0.clone(),
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(100),
+            },
+        )
+        .unwrap();
+        handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof: output1.clone(),
+                recipient: HumanAddr("bob".to_string()),
+                amount: Uint128(50),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128(150)
+        );
+
+        // Only output 1 (Bob's) was orphaned by the reorg.
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::RevertMint {
+                tx_id: output1.tx_id.clone(),
+                output_index: output1.output_index,
+            },
+        )
+        .unwrap();
+
+        // Output 0 (Alice's), still genuinely backed, stays minted: it's
+        // still a duplicate (can't be reused) and its liability is intact.
+        assert!(MoneroProofsStore::is_duplicate(&deps.storage, &output0.tx_id, 0).unwrap());
+        assert!(!MoneroProofsStore::is_duplicate(&deps.storage, &output1.tx_id, 1).unwrap());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).pending_liability(),
+            Uint128(100)
+        );
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).shortfall_debt(),
+            Uint128(50)
+        );
+
+        // Alice's still-valid deposit cannot be reused as a second mint.
+        let replay = handle(
+            &mut deps,
+            mock_env("minter", &[]),
+            HandleMsg::MintSecretMonero {
+                proof: output0.clone(),
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(100),
+            },
+        );
+        assert!(replay.is_err());
+    }
+
+    #[test]
+    fn set_minters_rejects_exceeding_the_cap_and_removing_frees_a_slot() {
+        let mut deps = init_helper();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_max_minters(2);
+        }
+
+        let over_cap = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMinters {
+                minters: vec![
+                    HumanAddr("minter-a".to_string()),
+                    HumanAddr("minter-b".to_string()),
+                    HumanAddr("minter-c".to_string()),
+                ],
+            },
+        );
+        assert!(over_cap.is_err());
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMinters {
+                minters: vec![
+                    HumanAddr("minter-a".to_string()),
+                    HumanAddr("minter-b".to_string()),
+                ],
+            },
+        )
+        .unwrap();
+        assert_eq!(ReadonlyConfig::from_storage(&deps.storage).minters().len(), 2);
+
+        // Dropping one frees a slot for a replacement to fit under the cap.
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMinters {
+                minters: vec![
+                    HumanAddr("minter-a".to_string()),
+                    HumanAddr("minter-c".to_string()),
+                ],
+            },
+        )
+        .unwrap();
+        assert_eq!(ReadonlyConfig::from_storage(&deps.storage).minters().len(), 2);
+    }
+
+    #[test]
+    fn exported_proofs_round_trip_into_a_fresh_contract_and_block_replay() {
+        let mut old_deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-migrate".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(
+            &mut old_deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+        set_viewing_key(&mut old_deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+
+        let exported = query(
+            &old_deps,
+            QueryMsg::ExportProofSet {
+                admin_viewing_key: "admin-key".to_string(),
+                page: 0,
+                page_size: 10,
+            },
+        )
+        .unwrap();
+        let entries = match cosmwasm_std::from_binary::<QueryResponse>(&exported).unwrap() {
+            QueryResponse::ExportProofSet { entries } => entries,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(entries, vec![("tx-migrate".to_string(), 0)]);
+
+        let mut new_deps = init_helper();
+        handle(
+            &mut new_deps,
+            mock_env("admin", &[]),
+            HandleMsg::ImportProofs {
+                entries: entries.clone(),
+            },
+        )
+        .unwrap();
+
+        assert!(MoneroProofsStore::is_duplicate(&new_deps.storage, "tx-migrate", 0).unwrap());
+
+        let replay = mint_sxmr(
+            &mut new_deps,
+            mock_env("minter", &[]),
+            MoneroProof {
+                tx_id: "tx-migrate".to_string(),
+                tx_key: "key".to_string(),
+                address: "bridge-wallet".to_string(),
+                block_height: 100,
+                output_index: 0,
+                xmr_atomic_amount: None,
+                amount: Uint128::zero(),
+            },
+            HumanAddr("recipient".to_string()),
+            Uint128(1000),
+        );
+        assert!(replay.is_err());
+    }
+
+    #[test]
+    fn accept_admin_and_cancel_admin_transfer_work_while_paused() {
+        let mut deps = init_helper();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::ProposeAdmin {
+                address: HumanAddr("next-admin".to_string()),
+            },
+        )
+        .unwrap();
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetContractStatus {
+                level: ContractStatusLevel::Paused,
+            },
+        )
+        .unwrap();
+
+        // Every other handler is blocked while paused...
+        let blocked = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetFeeCollector {
+                address: HumanAddr("someone".to_string()),
+            },
+        );
+        assert!(blocked.is_err());
+
+        // ...but the pending admin can still accept the handover.
+        handle(
+            &mut deps,
+            mock_env("next-admin", &[]),
+            HandleMsg::AcceptAdmin {},
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage)
+                .constants()
+                .unwrap()
+                .admin,
+            deps.api.canonical_address(&HumanAddr("next-admin".to_string())).unwrap()
+        );
+
+        // And the new admin can still cancel a (now nonexistent) pending transfer while paused.
+        handle(
+            &mut deps,
+            mock_env("next-admin", &[]),
+            HandleMsg::CancelAdminTransfer {},
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn accept_admin_rejects_a_caller_that_is_not_the_pending_admin() {
+        let mut deps = init_helper();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::ProposeAdmin {
+                address: HumanAddr("next-admin".to_string()),
+            },
+        )
+        .unwrap();
+
+        let wrong_caller = handle(&mut deps, mock_env("eve", &[]), HandleMsg::AcceptAdmin {});
+        assert!(wrong_caller.is_err());
+
+        // The old admin trying to accept its own proposal doesn't count either.
+        let old_admin = handle(&mut deps, mock_env("admin", &[]), HandleMsg::AcceptAdmin {});
+        assert!(old_admin.is_err());
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage)
+                .constants()
+                .unwrap()
+                .admin,
+            deps.api.canonical_address(&HumanAddr("admin".to_string())).unwrap()
+        );
+    }
+
+    #[test]
+    fn config_query_exposes_the_pending_admin_during_a_handover() {
+        let mut deps = init_helper();
+        let before = query(&deps, QueryMsg::Config {}).unwrap();
+        match cosmwasm_std::from_binary(&before).unwrap() {
+            QueryResponse::Config { pending_admin, .. } => assert_eq!(pending_admin, None),
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::ProposeAdmin {
+                address: HumanAddr("next-admin".to_string()),
+            },
+        )
+        .unwrap();
+
+        let after = query(&deps, QueryMsg::Config {}).unwrap();
+        match cosmwasm_std::from_binary(&after).unwrap() {
+            QueryResponse::Config { pending_admin, .. } => {
+                assert_eq!(pending_admin, Some(HumanAddr("next-admin".to_string())))
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn is_destination_blocked_reflects_membership() {
+        let mut deps = init_helper();
+        let unblocked = query(
+            &deps,
+            QueryMsg::IsDestinationBlocked {
+                to_monero_address: "monero-address".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            unblocked,
+            to_binary(&QueryResponse::IsDestinationBlocked { blocked: false }).unwrap()
+        );
+
+        crate::state::BlockedDestinationsStore::block(&mut deps.storage, "monero-address");
+
+        let blocked = query(
+            &deps,
+            QueryMsg::IsDestinationBlocked {
+                to_monero_address: "monero-address".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            blocked,
+            to_binary(&QueryResponse::IsDestinationBlocked { blocked: true }).unwrap()
+        );
+    }
+
+    #[test]
+    fn is_sender_blocked_reflects_membership() {
+        let mut deps = init_helper();
+        let unblocked = query(
+            &deps,
+            QueryMsg::IsSenderBlocked {
+                address: HumanAddr("alice".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            unblocked,
+            to_binary(&QueryResponse::IsSenderBlocked { blocked: false }).unwrap()
+        );
+
+        let alice = deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        crate::state::BlockedSendersStore::block(&mut deps.storage, &alice);
+
+        let blocked = query(
+            &deps,
+            QueryMsg::IsSenderBlocked {
+                address: HumanAddr("alice".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            blocked,
+            to_binary(&QueryResponse::IsSenderBlocked { blocked: true }).unwrap()
+        );
+    }
+
+    fn testnet_init_helper() -> Extern<
+        cosmwasm_std::testing::MockStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    > {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![HumanAddr("minter".to_string())],
+            monero_wallets: vec!["bridge-wallet".to_string()],
+            min_swap_amount: Uint128(1000),
+            prng_seed: "seed".to_string(),
+            emergency_admin: None,
+            testnet_mode: true,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+        deps
+    }
+
+    #[test]
+    fn test_mint_requires_testnet_mode_and_admin() {
+        let mut deps = testnet_init_helper();
+
+        let non_admin = handle(
+            &mut deps,
+            mock_env("eve", &[]),
+            HandleMsg::TestMint {
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+            },
+        );
+        assert!(non_admin.is_err());
+
+        let result = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::TestMint {
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result.data,
+            Some(
+                to_binary(&HandleResult::TestMint {
+                    status: "success".to_string(),
+                })
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_mint_is_rejected_without_testnet_mode() {
+        let mut deps = init_helper();
+        let result = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::TestMint {
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn testnet_mode_cannot_be_enabled_on_secret_mainnet() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            sxmr_address: HumanAddr("sxmr-token".to_string()),
+            sxmr_hash: "sxmr-code-hash".to_string(),
+            sxmr_decimals: 12,
+            bridge_minter: HumanAddr("minter".to_string()),
+            minters: vec![HumanAddr("minter".to_string())],
+            monero_wallets: vec!["bridge-wallet".to_string()],
+            min_swap_amount: Uint128(1000),
+            prng_seed: "seed".to_string(),
+            emergency_admin: None,
+            testnet_mode: true,
+        };
+        let mut env = mock_env("admin", &[]);
+        env.block.chain_id = "secret-4".to_string();
+        let result = init(&mut deps, env, init_msg);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn executing_a_queued_action_before_its_timelock_is_rejected_and_after_succeeds() {
+        let mut deps = init_helper();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetTimelockBlocks { blocks: 100 },
+        )
+        .unwrap();
+
+        let mut start_env = mock_env("admin", &[]);
+        start_env.block.height = 1_000;
+        let queued = handle(
+            &mut deps,
+            start_env,
+            HandleMsg::QueueSetMinters {
+                minters: vec![HumanAddr("new-minter".to_string())],
+            },
+        )
+        .unwrap();
+        let id = match cosmwasm_std::from_binary::<HandleResult>(&queued.data.unwrap()).unwrap() {
+            HandleResult::QueueSetMinters { id } => id,
+            other => panic!("unexpected response: {:?}", other),
+        };
+
+        let mut too_early_env = mock_env("admin", &[]);
+        too_early_env.block.height = 1_050;
+        let too_early = handle(
+            &mut deps,
+            too_early_env,
+            HandleMsg::ExecutePendingAction { id },
+        );
+        assert!(too_early.is_err());
+
+        let mut ready_env = mock_env("admin", &[]);
+        ready_env.block.height = 1_100;
+        handle(&mut deps, ready_env, HandleMsg::ExecutePendingAction { id }).unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).minters(),
+            vec![deps
+                .api
+                .canonical_address(&HumanAddr("new-minter".to_string()))
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn executing_a_token_swap_with_different_decimals_updates_the_active_scale() {
+        let mut deps = init_helper();
+        let queued = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::QueueSetSxmrToken {
+                address: HumanAddr("new-sxmr-token".to_string()),
+                code_hash: "new-sxmr-code-hash".to_string(),
+                decimals: 6,
+            },
+        )
+        .unwrap();
+        let id = match cosmwasm_std::from_binary::<HandleResult>(&queued.data.unwrap()).unwrap() {
+            HandleResult::QueueSetSxmrToken { id } => id,
+            other => panic!("unexpected response: {:?}", other),
+        };
+
+        handle(&mut deps, mock_env("admin", &[]), HandleMsg::ExecutePendingAction { id }).unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).sxmr_decimals(),
+            6
+        );
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage)
+                .constants()
+                .unwrap()
+                .sxmr
+                .decimals,
+            6
+        );
+    }
+
+    #[test]
+    fn burn_upscales_the_xmr_atomic_amount_when_sxmr_decimals_drops_below_monero_decimals() {
+        let mut deps = init_helper();
+        let queued = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::QueueSetSxmrToken {
+                address: HumanAddr("sxmr-token".to_string()),
+                code_hash: "sxmr-code-hash".to_string(),
+                decimals: 6,
+            },
+        )
+        .unwrap();
+        let id = match cosmwasm_std::from_binary::<HandleResult>(&queued.data.unwrap()).unwrap() {
+            HandleResult::QueueSetSxmrToken { id } => id,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        handle(&mut deps, mock_env("admin", &[]), HandleMsg::ExecutePendingAction { id }).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let response = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+        let nonce: u32 = response
+            .log
+            .iter()
+            .find(|l| l.key == "nonce")
+            .map(|l| l.value.parse().unwrap())
+            .unwrap();
+
+        let swap = ReadonlySwapDetailsStore::fetch_by_nonce(&deps.storage, nonce).unwrap();
+        // sxmr_decimals (6) is below MONERO_DECIMALS (12), so paying out in
+        // atomic units means scaling up, not down: 1000 * 10^(12-6).
+        assert_eq!(swap.xmr_atomic_amount, 1000 * 10u64.pow(6));
+    }
+
+    #[test]
+    fn burn_rejects_an_amount_that_does_not_divide_evenly_into_xmr_atomic_units() {
+        let mut deps = init_helper();
+        let queued = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::QueueSetSxmrToken {
+                address: HumanAddr("sxmr-token".to_string()),
+                code_hash: "sxmr-code-hash".to_string(),
+                decimals: 15,
+            },
+        )
+        .unwrap();
+        let id = match cosmwasm_std::from_binary::<HandleResult>(&queued.data.unwrap()).unwrap() {
+            HandleResult::QueueSetSxmrToken { id } => id,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        handle(&mut deps, mock_env("admin", &[]), HandleMsg::ExecutePendingAction { id }).unwrap();
+
+        // sxmr_decimals (15) is above MONERO_DECIMALS (12): the amount must
+        // be an exact multiple of 10^3 to convert without losing precision,
+        // and 1234 isn't.
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1234),
+            Some(dest),
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).swap_counts().pending,
+            0
+        );
+    }
+
+    #[test]
+    fn executing_a_token_swap_is_rejected_while_swaps_are_pending() {
+        let mut deps = init_helper();
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let queued = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::QueueSetSxmrToken {
+                address: HumanAddr("new-sxmr-token".to_string()),
+                code_hash: "new-sxmr-code-hash".to_string(),
+                decimals: 6,
+            },
+        )
+        .unwrap();
+        let id = match cosmwasm_std::from_binary::<HandleResult>(&queued.data.unwrap()).unwrap() {
+            HandleResult::QueueSetSxmrToken { id } => id,
+            other => panic!("unexpected response: {:?}", other),
+        };
+
+        let result = handle(&mut deps, mock_env("admin", &[]), HandleMsg::ExecutePendingAction { id });
+        assert!(result.is_err());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).sxmr_decimals(),
+            12
+        );
+    }
+
+    #[test]
+    fn cancelling_a_pending_action_removes_it() {
+        let mut deps = init_helper();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetTimelockBlocks { blocks: 100 },
+        )
+        .unwrap();
+
+        let queued = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::QueueSetMoneroWallets {
+                wallets: vec!["new-wallet".to_string()],
+            },
+        )
+        .unwrap();
+        let id = match cosmwasm_std::from_binary::<HandleResult>(&queued.data.unwrap()).unwrap() {
+            HandleResult::QueueSetMoneroWallets { id } => id,
+            other => panic!("unexpected response: {:?}", other),
+        };
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::CancelPendingAction { id },
+        )
+        .unwrap();
+
+        let mut later_env = mock_env("admin", &[]);
+        later_env.block.height += 1_000;
+        let execute_after_cancel = handle(
+            &mut deps,
+            later_env,
+            HandleMsg::ExecutePendingAction { id },
+        );
+        assert!(execute_after_cancel.is_err());
+        assert!(crate::state::PendingActionStore::get(&deps.storage, id).is_none());
+    }
+
+    #[test]
+    fn pending_actions_query_lists_queued_actions() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetTimelockBlocks { blocks: 50 },
+        )
+        .unwrap();
+        let mut queue_env = mock_env("admin", &[]);
+        queue_env.block.height = 1_000;
+        handle(
+            &mut deps,
+            queue_env,
+            HandleMsg::QueueSetMinters {
+                minters: vec![HumanAddr("new-minter".to_string())],
+            },
+        )
+        .unwrap();
+
+        let response = query(
+            &deps,
+            QueryMsg::PendingActions {
+                admin_viewing_key: "admin-key".to_string(),
+            },
+        )
+        .unwrap();
+        let actions = match cosmwasm_std::from_binary::<QueryResponse>(&response).unwrap() {
+            QueryResponse::PendingActions { actions } => actions,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].ready_at_block, 1_050);
+        assert!(actions[0].description.contains("new-minter"));
+    }
+
+    #[test]
+    fn solvency_delta_reports_a_simulated_extra_balance_as_a_surplus() {
+        let (surplus, shortfall) = solvency_delta(Uint128(500), Uint128(800));
+        assert_eq!(surplus, Uint128(300));
+        assert_eq!(shortfall, Uint128::zero());
+
+        let (surplus, shortfall) = solvency_delta(Uint128(800), Uint128(500));
+        assert_eq!(surplus, Uint128::zero());
+        assert_eq!(shortfall, Uint128(300));
+
+        let (surplus, shortfall) = solvency_delta(Uint128(500), Uint128(500));
+        assert_eq!(surplus, Uint128::zero());
+        assert_eq!(shortfall, Uint128::zero());
+    }
+
+    #[test]
+    fn set_bridge_viewing_key_is_admin_only_and_forwards_the_key_to_the_token() {
+        let mut deps = init_helper();
+
+        let non_admin = handle(
+            &mut deps,
+            mock_env("eve", &[]),
+            HandleMsg::SetBridgeViewingKey {
+                key: "bridge-key".to_string(),
+            },
+        );
+        assert!(non_admin.is_err());
+
+        let result = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetBridgeViewingKey {
+                key: "bridge-key".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).bridge_viewing_key(),
+            Some("bridge-key".to_string())
+        );
+    }
+
+    #[test]
+    fn rotating_the_bridge_viewing_key_keeps_the_old_one_as_a_fallback() {
+        let mut deps = init_helper();
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetBridgeViewingKey {
+                key: "key-one".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).prev_bridge_viewing_key(),
+            None
+        );
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetBridgeViewingKey {
+                key: "key-two".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).bridge_viewing_key(),
+            Some("key-two".to_string())
+        );
+        let (prev_key, _retire_at_block) =
+            ReadonlyConfig::from_storage(&deps.storage)
+                .prev_bridge_viewing_key()
+                .unwrap();
+        assert_eq!(prev_key, "key-one");
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetBridgeViewingKey {
+                key: "key-three".to_string(),
+            },
+        )
+        .unwrap();
+        let (prev_key, _retire_at_block) =
+            ReadonlyConfig::from_storage(&deps.storage)
+                .prev_bridge_viewing_key()
+                .unwrap();
+        assert_eq!(prev_key, "key-two", "the overlap window only ever keeps one prior key");
+    }
+
+    #[test]
+    fn retiring_the_bridge_viewing_key_is_admin_only_and_drops_the_fallback() {
+        let mut deps = init_helper();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetBridgeViewingKey {
+                key: "key-one".to_string(),
+            },
+        )
+        .unwrap();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetBridgeViewingKey {
+                key: "key-two".to_string(),
+            },
+        )
+        .unwrap();
+        assert!(ReadonlyConfig::from_storage(&deps.storage)
+            .prev_bridge_viewing_key()
+            .is_some());
+
+        let non_admin = handle(
+            &mut deps,
+            mock_env("eve", &[]),
+            HandleMsg::RetireBridgeViewingKey {},
+        );
+        assert!(non_admin.is_err());
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::RetireBridgeViewingKey {},
+        )
+        .unwrap();
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).prev_bridge_viewing_key(),
+            None
+        );
+    }
+
+    #[test]
+    fn every_settable_parameter_round_trips_through_full_config() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("admin", &[]), "admin-key".to_string()).unwrap();
+
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMinters {
+                minters: vec![HumanAddr("minter".to_string()), HumanAddr("second-minter".to_string())],
+            },
+        )
+        .unwrap();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetFeeCollector {
+                address: HumanAddr("collector".to_string()),
+            },
+        )
+        .unwrap();
+        handle(&mut deps, mock_env("admin", &[]), HandleMsg::SetFee { fee_bps: 25 }).unwrap();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMaxSwap { amount: Uint128(50_000) },
+        )
+        .unwrap();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMaxDestinationsPerBurn { max: 10 },
+        )
+        .unwrap();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetMaintenanceWindow { start_block: 100, end_block: 200 },
+        )
+        .unwrap();
+        handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            HandleMsg::SetTimelockBlocks { blocks: 42 },
+        )
+        .unwrap();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_max_minters(5);
+            config.set_fee_sweep_threshold(Uint128(1500));
+            config.set_monero_dust_limit(Uint128(300));
+            config.set_require_known_recipient(true);
+            config.set_enforce_monotonic_proof_order(true);
+            config.set_include_token_info_in_result(true);
+            config.set_swap_ttl_seconds(86_400);
+            config.set_auto_vk_on_mint(true);
+            config.set_enforce_decimal_alignment(true);
+            config.set_require_sender_equals_from(true);
+        }
+
+        let response = query(
+            &deps,
+            QueryMsg::FullConfig {
+                admin_viewing_key: "admin-key".to_string(),
+            },
+        )
+        .unwrap();
+        let full_config = match cosmwasm_std::from_binary::<QueryResponse>(&response).unwrap() {
+            QueryResponse::FullConfig { full_config } => full_config,
+            other => panic!("unexpected response: {:?}", other),
+        };
+
+        assert_eq!(full_config.admin, HumanAddr("admin".to_string()));
+        assert_eq!(full_config.bridge_minter, HumanAddr("minter".to_string()));
+        assert_eq!(
+            full_config.minters,
+            vec![HumanAddr("minter".to_string()), HumanAddr("second-minter".to_string())]
+        );
+        assert_eq!(full_config.max_minters, 5);
+        assert_eq!(full_config.fee_collector, Some(HumanAddr("collector".to_string())));
+        assert_eq!(full_config.fee_bps, 25);
+        assert_eq!(full_config.max_swap_amount, Uint128(50_000));
+        assert_eq!(full_config.max_destinations_per_burn, 10);
+        assert_eq!(full_config.fee_sweep_threshold, Uint128(1500));
+        assert_eq!(full_config.monero_dust_limit, Uint128(300));
+        assert!(full_config.require_known_recipient);
+        assert!(full_config.enforce_monotonic_proof_order);
+        assert!(full_config.include_token_info_in_result);
+        assert_eq!(full_config.swap_ttl_seconds, 86_400);
+        assert!(full_config.auto_vk_on_mint);
+        assert!(full_config.enforce_decimal_alignment);
+        assert!(full_config.require_sender_equals_from);
+        assert_eq!(full_config.maintenance_window, Some((100, 200)));
+        assert_eq!(full_config.timelock_blocks, 42);
+        assert!(!full_config.testnet_mode);
+    }
+
+    #[test]
+    fn full_config_is_rejected_without_the_admin_viewing_key() {
+        let mut deps = init_helper();
+        let result = query(
+            &deps,
+            QueryMsg::FullConfig {
+                admin_viewing_key: "wrong-key".to_string(),
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_below_the_configured_minimum_is_rejected() {
+        let mut deps = init_helper();
+        set_mint_bounds(&mut deps, mock_env("admin", &[]), Uint128(1000), Uint128::zero()).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-below-min".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(999),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_to_the_bridge_contracts_own_address_is_rejected() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-self-mint".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let env = mock_env("minter", &[]);
+        let self_address = env.contract.address.clone();
+        let result = mint_sxmr(&mut deps, env, proof, self_address, Uint128(1000));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_to_the_sxmr_token_contracts_address_is_rejected() {
+        let mut deps = init_helper();
+        let proof = MoneroProof {
+            tx_id: "tx-self-mint-token".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("sxmr-token".to_string()),
+            Uint128(1000),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_above_the_configured_maximum_is_rejected() {
+        let mut deps = init_helper();
+        set_mint_bounds(&mut deps, mock_env("admin", &[]), Uint128::zero(), Uint128(1000)).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-above-max".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(1001),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_within_the_configured_bounds_succeeds() {
+        let mut deps = init_helper();
+        set_mint_bounds(&mut deps, mock_env("admin", &[]), Uint128(1000), Uint128(2000)).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-in-bounds".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(1500),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn mint_not_conforming_to_the_unit_granularity_is_rejected() {
+        let mut deps = init_helper();
+        set_unit_granularity(&mut deps, mock_env("admin", &[]), Uint128(1_000_000_000)).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-dust".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(1_500_000_000),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_conforming_to_the_unit_granularity_succeeds() {
+        let mut deps = init_helper();
+        set_unit_granularity(&mut deps, mock_env("admin", &[]), Uint128(1_000_000_000)).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-clean".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(2_000_000_000),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn burn_net_amount_not_conforming_to_the_unit_granularity_is_rejected() {
+        let mut deps = init_helper();
+        set_unit_granularity(&mut deps, mock_env("admin", &[]), Uint128(1_000_000_000)).unwrap();
+
+        // init_helper sets no fee, so the net amount equals the gross amount
+        // burned; 1_500_000_000 is not a clean multiple of the granularity.
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1_500_000_000),
+            Some(dest),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_net_amount_conforming_to_the_unit_granularity_succeeds() {
+        let mut deps = init_helper();
+        set_unit_granularity(&mut deps, mock_env("admin", &[]), Uint128(1_000_000_000)).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        let result = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(2_000_000_000),
+            Some(dest),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn mint_rate_limit_allows_up_to_the_limit_then_rejects() {
+        let mut deps = init_helper();
+        set_mint_rate_limit(&mut deps, mock_env("admin", &[]), Uint128(1000), 100).unwrap();
+
+        let mut env = mock_env("minter", &[]);
+        env.block.height = 10;
+        let proof = MoneroProof {
+            tx_id: "tx-limit-1".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(&mut deps, env.clone(), proof, HumanAddr("alice".to_string()), Uint128(600)).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-limit-2".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 101,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(&mut deps, env, proof, HumanAddr("alice".to_string()), Uint128(500));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mint_rate_limit_resets_once_the_window_elapses() {
+        let mut deps = init_helper();
+        set_mint_rate_limit(&mut deps, mock_env("admin", &[]), Uint128(1000), 100).unwrap();
+
+        let mut env = mock_env("minter", &[]);
+        env.block.height = 10;
+        let proof = MoneroProof {
+            tx_id: "tx-window-1".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(&mut deps, env.clone(), proof, HumanAddr("alice".to_string()), Uint128(900)).unwrap();
+
+        env.block.height = 10 + 100 + 1;
+        let proof = MoneroProof {
+            tx_id: "tx-window-2".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 200,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let result = mint_sxmr(&mut deps, env, proof, HumanAddr("alice".to_string()), Uint128(900));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn mint_threshold_requires_two_of_three_distinct_minters() {
+        let mut deps = init_helper();
+        set_minters(
+            &mut deps,
+            mock_env("admin", &[]),
+            vec![
+                HumanAddr("minter".to_string()),
+                HumanAddr("minter-2".to_string()),
+                HumanAddr("minter-3".to_string()),
+            ],
+        )
+        .unwrap();
+        set_mint_threshold(&mut deps, mock_env("admin", &[]), 2).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-threshold".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        let first = mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof.clone(),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+        match cosmwasm_std::from_binary::<HandleResult>(&first.data.unwrap()).unwrap() {
+            HandleResult::MintSecretMonero { status, .. } => assert_eq!(status, "pending_approval"),
+            other => panic!("unexpected response: {:?}", other),
+        }
+        assert!(first.messages.is_empty());
+
+        let second = mint_sxmr(
+            &mut deps,
+            mock_env("minter-2", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+        match cosmwasm_std::from_binary::<HandleResult>(&second.data.unwrap()).unwrap() {
+            HandleResult::MintSecretMonero { status, .. } => assert_eq!(status, "success"),
+            other => panic!("unexpected response: {:?}", other),
+        }
+        assert_eq!(second.messages.len(), 1);
+    }
+
+    #[test]
+    fn mint_threshold_rejects_a_mismatched_amount_for_the_same_tx_id() {
+        let mut deps = init_helper();
+        set_minters(
+            &mut deps,
+            mock_env("admin", &[]),
+            vec![
+                HumanAddr("minter".to_string()),
+                HumanAddr("minter-2".to_string()),
+            ],
+        )
+        .unwrap();
+        set_mint_threshold(&mut deps, mock_env("admin", &[]), 2).unwrap();
+
+        let proof = MoneroProof {
+            tx_id: "tx-mismatch".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof.clone(),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+        )
+        .unwrap();
+
+        let result = mint_sxmr(
+            &mut deps,
+            mock_env("minter-2", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(2000),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn statistics_reflect_a_mint_and_a_burn() {
+        let mut deps = init_helper();
+
+        let proof = MoneroProof {
+            tx_id: "tx-stats".to_string(),
+            tx_key: "key".to_string(),
+            address: "bridge-wallet".to_string(),
+            block_height: 100,
+            output_index: 0,
+            xmr_atomic_amount: None,
+            amount: Uint128::zero(),
+        };
+        mint_sxmr(
+            &mut deps,
+            mock_env("minter", &[]),
+            proof,
+            HumanAddr("alice".to_string()),
+            Uint128(5000),
+        )
+        .unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string()))
+            .unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(2000),
+            Some(dest),
+        )
+        .unwrap();
+
+        let response = query_statistics(&deps).unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryResponse::Statistics {
+                total_minted: Uint128(5000),
+                total_burned: Uint128(2000),
+                total_swap_count: 1,
+                total_refunded: Uint128::zero(),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn add_minters_appends_without_disturbing_the_existing_list() {
+        let mut deps = init_helper();
+        add_minters(
+            &mut deps,
+            mock_env("admin", &[]),
+            vec![HumanAddr("minter-2".to_string())],
+        )
+        .unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).minters(),
+            vec![
+                deps.api
+                    .canonical_address(&HumanAddr("minter".to_string()))
+                    .unwrap(),
+                deps.api
+                    .canonical_address(&HumanAddr("minter-2".to_string()))
+                    .unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn add_minters_is_idempotent_for_an_already_present_minter() {
+        let mut deps = init_helper();
+        add_minters(
+            &mut deps,
+            mock_env("admin", &[]),
+            vec![HumanAddr("minter".to_string())],
+        )
+        .unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).minters(),
+            vec![deps
+                .api
+                .canonical_address(&HumanAddr("minter".to_string()))
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn remove_minters_drops_only_the_named_addresses() {
+        let mut deps = init_helper();
+        add_minters(
+            &mut deps,
+            mock_env("admin", &[]),
+            vec![HumanAddr("minter-2".to_string())],
+        )
+        .unwrap();
+        remove_minters(
+            &mut deps,
+            mock_env("admin", &[]),
+            vec![
+                HumanAddr("minter".to_string()),
+                HumanAddr("never-a-minter".to_string()),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).minters(),
+            vec![deps
+                .api
+                .canonical_address(&HumanAddr("minter-2".to_string()))
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn swap_details_distinguishes_never_existed_from_wrong_owner() {
+        let mut deps = init_helper();
+        set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        set_viewing_key(&mut deps, mock_env("bob", &[]), "bob-key".to_string()).unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        // Nonce 0 belongs to alice, not bob.
+        let wrong_owner = query_swap_details(
+            &deps,
+            HumanAddr("bob".to_string()),
+            0,
+            None,
+            "bob-key".to_string(),
+            None,
+        );
+        assert_eq!(
+            wrong_owner.unwrap_err(),
+            crate::error::swap_does_not_belong_to_caller()
+        );
+
+        // Nonce 1 has never been created at all.
+        let never_existed = query_swap_details(
+            &deps,
+            HumanAddr("alice".to_string()),
+            1,
+            None,
+            "alice-key".to_string(),
+            None,
+        );
+        assert_eq!(
+            never_existed.unwrap_err(),
+            StdError::generic_err("swap not found")
+        );
+    }
+
+    #[test]
+    fn burn_sxmr_burns_the_net_amount_and_sweeps_the_fee_via_transfer() {
+        let mut deps = init_helper();
+        let collector = deps
+            .api
+            .canonical_address(&HumanAddr("collector".to_string()))
+            .unwrap();
+        {
+            let mut config = Config::from_storage(&mut deps.storage);
+            config.set_fee_collector(&collector);
+            config.set_fee_sweep_threshold(Uint128(1));
+        }
+        set_fee(&mut deps, mock_env("admin", &[]), 100).unwrap(); // 1%
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let response = burn_sxmr(
+            &mut deps,
+            mock_env("sxmr-token", &[]),
+            HumanAddr("sxmr-token".to_string()),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            Some(dest),
+        )
+        .unwrap();
+
+        assert_eq!(response.messages.len(), 2);
+        match &response.messages[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                match cosmwasm_std::from_binary::<sxmr_token::msg::HandleMsg>(msg).unwrap() {
+                    sxmr_token::msg::HandleMsg::Burn { amount, .. } => {
+                        assert_eq!(amount, Uint128(990));
+                    }
+                    other => panic!("unexpected message: {:?}", other),
+                }
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match &response.messages[1] {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                match cosmwasm_std::from_binary::<sxmr_token::msg::HandleMsg>(msg).unwrap() {
+                    sxmr_token::msg::HandleMsg::Transfer { recipient, amount, .. } => {
+                        assert_eq!(recipient, HumanAddr("collector".to_string()));
+                        assert_eq!(amount, Uint128(10));
+                    }
+                    other => panic!("unexpected message: {:?}", other),
+                }
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    /// End-to-end proof that a token `Send` actually reaches the bridge's
+    /// `Receive` handler, unlike every other burn test in this module, which
+    /// calls `burn_sxmr`/`handle(HandleMsg::Receive { .. })` directly and so
+    /// never exercises the token's wire format. Runs the sxmr-token contract
+    /// for real against its own `Extern`, decodes the `CosmosMsg` its `Send`
+    /// handler emits, and feeds that decoded message into the bridge exactly
+    /// as the chain would after routing it.
+    #[test]
+    fn a_real_token_send_round_trips_into_the_bridge_receive_handler() {
+        let mut bridge_deps = init_helper();
+
+        let mut token_deps = mock_dependencies(20, &[]);
+        sxmr_token::contract::init(
+            &mut token_deps,
+            mock_env("token-admin", &[]),
+            sxmr_token::msg::InitMsg {
+                name: "Secret Monero".to_string(),
+                symbol: "SXMR".to_string(),
+                decimals: 12,
+                admin: None,
+                minters: vec![HumanAddr("minter".to_string())],
+                prng_seed: Binary::from(b"seed".to_vec()),
+                mix_block_entropy: true,
+                max_supply: None,
+            },
+        )
+        .unwrap();
+        sxmr_token::contract::handle(
+            &mut token_deps,
+            mock_env("minter", &[]),
+            sxmr_token::msg::HandleMsg::Mint {
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+                memo: None,
+                padding: None,
+            },
+        )
+        .unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let send_response = sxmr_token::contract::handle(
+            &mut token_deps,
+            mock_env("alice", &[]),
+            sxmr_token::msg::HandleMsg::Send {
+                recipient: HumanAddr("bridge".to_string()),
+                recipient_code_hash: Some("bridge-code-hash".to_string()),
+                amount: Uint128(1000),
+                msg: Some(dest),
+                memo: None,
+                padding: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(send_response.messages.len(), 1);
+        let receive_msg = match &send_response.messages[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                callback_code_hash,
+                msg,
+                ..
+            }) => {
+                assert_eq!(contract_addr, &HumanAddr("bridge".to_string()));
+                assert_eq!(callback_code_hash, "bridge-code-hash");
+                cosmwasm_std::from_binary::<HandleMsg>(msg).unwrap()
+            }
+            other => panic!("unexpected message: {:?}", other),
+        };
+
+        let bridge_response = handle(&mut bridge_deps, mock_env("sxmr-token", &[]), receive_msg).unwrap();
+        assert!(bridge_response
+            .log
+            .iter()
+            .any(|l| l.key == "action" && l.value == "burn_secret_monero"));
+
+        let alice = bridge_deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        let swap = ReadonlySwapDetailsStore::fetch_swap_details(&bridge_deps.storage, &alice, 0).unwrap();
+        assert_eq!(swap.to_monero_address, "monero-address");
+    }
+
+    #[test]
+    fn a_burn_cannot_land_through_a_real_send_while_paused() {
+        let mut bridge_deps = init_helper();
+        set_contract_status(&mut bridge_deps, mock_env("admin", &[]), ContractStatusLevel::Paused)
+            .unwrap();
+
+        let mut token_deps = mock_dependencies(20, &[]);
+        sxmr_token::contract::init(
+            &mut token_deps,
+            mock_env("token-admin", &[]),
+            sxmr_token::msg::InitMsg {
+                name: "Secret Monero".to_string(),
+                symbol: "SXMR".to_string(),
+                decimals: 12,
+                admin: None,
+                minters: vec![HumanAddr("minter".to_string())],
+                prng_seed: Binary::from(b"seed".to_vec()),
+                mix_block_entropy: true,
+                max_supply: None,
+            },
+        )
+        .unwrap();
+        sxmr_token::contract::handle(
+            &mut token_deps,
+            mock_env("minter", &[]),
+            sxmr_token::msg::HandleMsg::Mint {
+                recipient: HumanAddr("alice".to_string()),
+                amount: Uint128(1000),
+                memo: None,
+                padding: None,
+            },
+        )
+        .unwrap();
+
+        let dest = to_binary(&crate::msg::BurnDestination::Single("monero-address".to_string())).unwrap();
+        let send_response = sxmr_token::contract::handle(
+            &mut token_deps,
+            mock_env("alice", &[]),
+            sxmr_token::msg::HandleMsg::Send {
+                recipient: HumanAddr("bridge".to_string()),
+                recipient_code_hash: Some("bridge-code-hash".to_string()),
+                amount: Uint128(1000),
+                msg: Some(dest),
+                memo: None,
+                padding: None,
+            },
+        )
+        .unwrap();
+
+        let receive_msg = match &send_response.messages[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                cosmwasm_std::from_binary::<HandleMsg>(msg).unwrap()
+            }
+            other => panic!("unexpected message: {:?}", other),
+        };
+
+        let result = handle(&mut bridge_deps, mock_env("sxmr-token", &[]), receive_msg);
+        assert_eq!(result.unwrap_err(), error::contract_paused());
+    }
+}
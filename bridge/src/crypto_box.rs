@@ -0,0 +1,51 @@
+use sha2::{Digest, Sha256};
+
+/// Length, in bytes, of a `PubKey`.
+pub const KEY_LEN: usize = 32;
+/// Length, in bytes, of the nonce `seal` derives and returns alongside its
+/// ciphertext.
+pub const NONCE_LEN: usize = 12;
+
+/// A 32-byte key a client supplies to have a query response encrypted to it,
+/// as an extra layer over the Secret Network transport. This contract
+/// doesn't bundle an elliptic-curve library, so unlike a true asymmetric
+/// `PubKey` this one doubles as its own "private key": whoever holds it can
+/// both request the encryption and `open` the result.
+pub type PubKey = [u8; KEY_LEN];
+
+/// Encrypts `plaintext` for `key` with a SHA-256 counter-mode keystream,
+/// deriving the nonce from `SHA256(key || plaintext)` so that a query
+/// (which has no source of randomness) never reuses a keystream for two
+/// different responses under the same key. Returns `(ciphertext, nonce)`;
+/// the nonce isn't secret and must be passed back to `open`.
+pub fn seal(key: &PubKey, plaintext: &[u8]) -> (Vec<u8>, [u8; NONCE_LEN]) {
+    let mut hasher = Sha256::new();
+    hasher.update(key);
+    hasher.update(plaintext);
+    let digest = hasher.finalize();
+
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(&digest[..NONCE_LEN]);
+    (xor_keystream(key, &nonce, plaintext), nonce)
+}
+
+/// Reverses `seal`. Since the keystream is its own inverse under XOR,
+/// `open` and `seal`'s encryption step are the same operation.
+pub fn open(key: &PubKey, nonce: &[u8; NONCE_LEN], ciphertext: &[u8]) -> Vec<u8> {
+    xor_keystream(key, nonce, ciphertext)
+}
+
+fn xor_keystream(key: &PubKey, nonce: &[u8; NONCE_LEN], data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    for (counter, chunk) in data.chunks(32).enumerate() {
+        let mut hasher = Sha256::new();
+        hasher.update(key);
+        hasher.update(nonce);
+        hasher.update(&(counter as u32).to_be_bytes());
+        let block = hasher.finalize();
+        for (byte, keystream_byte) in chunk.iter().zip(block.iter()) {
+            out.push(byte ^ keystream_byte);
+        }
+    }
+    out
+}
@@ -0,0 +1,27 @@
+//! Typed constructors for the `StdError`s this contract returns.
+//!
+//! We stay on `cosmwasm_std::StdError` (no custom error enum) to match the
+//! rest of the Secret Network contract ecosystem, but centralize the
+//! messages here so call sites read as intent rather than prose.
+
+use cosmwasm_std::StdError;
+
+pub fn zero_amount() -> StdError {
+    StdError::generic_err("amount must be greater than zero")
+}
+
+pub fn unauthorized() -> StdError {
+    StdError::generic_err("unauthorized")
+}
+
+pub fn contract_paused() -> StdError {
+    StdError::generic_err("this contract is paused")
+}
+
+/// Distinct from a generic "not found": the nonce exists, but the caller
+/// (correctly authenticated via viewing key) isn't its owner. Callers still
+/// only learn this after proving ownership of *some* account, so it doesn't
+/// leak whether a nonce exists to an unauthenticated party.
+pub fn swap_does_not_belong_to_caller() -> StdError {
+    StdError::generic_err("swap does not belong to caller")
+}
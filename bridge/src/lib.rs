@@ -0,0 +1,12 @@
+pub mod contract;
+pub mod crypto_box;
+pub mod error;
+pub mod msg;
+pub mod permit;
+pub mod query_messages;
+pub mod rand;
+pub mod state;
+pub mod viewing_key;
+
+#[cfg(target_arch = "wasm32")]
+cosmwasm_std::create_entry_points!(contract);
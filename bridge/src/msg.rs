@@ -0,0 +1,671 @@
+use cosmwasm_std::{Binary, HumanAddr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{MoneroProof, SwapStatusFilter};
+
+/// The payload carried in a SNIP-20 `Send`'s `msg` field when burning sXMR,
+/// telling the bridge where the resulting XMR should go.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnDestination {
+    Single(String),
+    /// Like `Single`, but additionally records an integrated-address payment
+    /// id against the swap, for a plain address that still needs a
+    /// distinguishing tag (e.g. an exchange deposit). Rejected if `address`
+    /// is itself an integrated address, since that already embeds its own
+    /// payment id.
+    SingleWithPaymentId { address: String, payment_id: String },
+    /// Splits the burned net amount across several Monero addresses; the
+    /// listed amounts must sum exactly to the net.
+    Multi(Vec<(String, Uint128)>),
+    /// Wraps any other variant with a memo forwarded into the Monero payout
+    /// transaction, capped at 256 bytes (see `burn_sxmr`). A separate
+    /// variant instead of a `memo` field on each existing one, so every
+    /// pre-existing burn payload's wire format is unchanged. Nesting
+    /// `WithMemo` inside itself is rejected.
+    WithMemo {
+        destination: Box<BurnDestination>,
+        memo: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InitMsg {
+    pub sxmr_address: HumanAddr,
+    pub sxmr_hash: String,
+    /// The sXMR token's decimal scale, recorded on `TokenInfo` and mirrored
+    /// into `Config::sxmr_decimals` so the two start in agreement; see
+    /// `TokenInfo::decimals`'s doc comment for why they're tracked
+    /// separately afterward.
+    pub sxmr_decimals: u8,
+    pub bridge_minter: HumanAddr,
+    /// Seeds `Config::minters` (otherwise only settable post-init via
+    /// `SetMinters`/`QueueSetMinters`) so a fleet deployment is fully
+    /// configured in one call. Must be non-empty, and is still subject to
+    /// `Config::max_minters`.
+    pub minters: Vec<HumanAddr>,
+    pub monero_wallets: Vec<String>,
+    pub min_swap_amount: Uint128,
+    pub prng_seed: String,
+    /// Defaults to the sender (the initial admin) when omitted.
+    pub emergency_admin: Option<HumanAddr>,
+    /// Enables `TestMint`, which mints sXMR without a Monero proof, for
+    /// integration testing against a live testnet deployment. `init` refuses
+    /// to set this `true` when `env.block.chain_id` is Secret Network
+    /// mainnet's (`secret-4`), so there is no way to stand up a
+    /// `TestMint`-capable contract there in the first place — unlike a
+    /// handler-gated flag, which a compromised or careless admin could flip
+    /// post-deploy.
+    pub testnet_mode: bool,
+}
+
+/// One deposit within a `HandleMsg::BatchMint`; the same three fields
+/// `MintSecretMonero` takes individually.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintItem {
+    pub proof: MoneroProof,
+    pub recipient: HumanAddr,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractStatusLevel {
+    Running,
+    Paused,
+    /// A hard lockdown above `Paused`: blocks every handler, including
+    /// admin-initiated token messages, except stepping the status back down.
+    /// Entering and exiting requires the distinct `emergency_admin`.
+    Emergency,
+    /// Blocks `MintSecretMonero` only; burns, queries, and admin commands
+    /// stay allowed. For pausing the Monero-deposit side alone, e.g. during
+    /// wallet maintenance, without also freezing existing sXMR holders.
+    StopMinting,
+    /// Blocks `Receive` (a burn-to-Monero swap) only; mints, queries, and
+    /// admin commands stay allowed. The mirror of `StopMinting`.
+    StopSwaps,
+}
+
+pub fn status_level_to_u8(status_level: ContractStatusLevel) -> u8 {
+    match status_level {
+        ContractStatusLevel::Running => 0,
+        ContractStatusLevel::Paused => 1,
+        ContractStatusLevel::Emergency => 2,
+        ContractStatusLevel::StopMinting => 3,
+        ContractStatusLevel::StopSwaps => 4,
+    }
+}
+
+pub fn u8_to_status_level(status_level: u8) -> cosmwasm_std::StdResult<ContractStatusLevel> {
+    match status_level {
+        0 => Ok(ContractStatusLevel::Running),
+        1 => Ok(ContractStatusLevel::Paused),
+        2 => Ok(ContractStatusLevel::Emergency),
+        3 => Ok(ContractStatusLevel::StopMinting),
+        4 => Ok(ContractStatusLevel::StopSwaps),
+        _ => Err(cosmwasm_std::StdError::generic_err(
+            "invalid contract status level",
+        )),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleMsg {
+    /// Mint sXMR against a proven Monero deposit. Restricted to the
+    /// configured `bridge_minter` and the addresses in the minter list.
+    MintSecretMonero {
+        proof: MoneroProof,
+        recipient: HumanAddr,
+        amount: Uint128,
+    },
+    /// Mints several deposits in one transaction, each processed exactly
+    /// like `MintSecretMonero`, capped at `contract::MAX_BATCH_MINT_SIZE`.
+    /// Atomic like any other handler: a failure on any item (a duplicate
+    /// proof or anything else `MintSecretMonero` would reject) aborts the
+    /// whole batch, not just that item.
+    BatchMint {
+        mints: Vec<MintItem>,
+    },
+    /// SNIP-20 receiver callback; burning sXMR sent here initiates a swap
+    /// back to Monero.
+    Receive {
+        sender: HumanAddr,
+        from: HumanAddr,
+        amount: Uint128,
+        msg: Option<cosmwasm_std::Binary>,
+    },
+    SetViewingKey {
+        key: String,
+        padding: Option<String>,
+    },
+    ChangeViewingKey {
+        key: String,
+        padding: Option<String>,
+    },
+    /// Derives a viewing key on-chain (see `viewing_key::ViewingKey::new`)
+    /// instead of requiring the client to invent its own via `SetViewingKey`,
+    /// mirroring the SNIP-20 `CreateViewingKey` pattern. The plaintext key is
+    /// returned in the response `data`, exactly once; only its hash is
+    /// stored.
+    CreateViewingKey {
+        entropy: String,
+    },
+    SetMinters {
+        minters: Vec<HumanAddr>,
+    },
+    /// Admin-only: adds to `Config::minters()` instead of replacing it, so
+    /// rotating in one new key doesn't require resending the whole list.
+    /// Adding an address already in the list is a no-op for that address.
+    AddMinters {
+        minters: Vec<HumanAddr>,
+    },
+    /// Admin-only: the inverse of `AddMinters`. Removing an address not in
+    /// the list is a no-op for that address.
+    RemoveMinters {
+        minters: Vec<HumanAddr>,
+    },
+    /// Admin-only: designates the address that receives swept fees.
+    SetFeeCollector {
+        address: HumanAddr,
+    },
+    /// Admin-only: sweeps `Config::accumulated_fees` to `fee_collector`
+    /// immediately, regardless of `fee_sweep_threshold`. For operators who
+    /// don't want to wait for the next burn to cross the threshold, e.g.
+    /// ahead of a `fee_sweep_threshold` decrease or a scheduled payout.
+    SweepFees {},
+    /// Admin-only: sets `Config::fee_bps`, the cut `burn_sxmr` deducts from
+    /// a burn's gross amount. Capped at `state::MAX_FEE_BPS`.
+    SetFee {
+        fee_bps: u16,
+    },
+    /// Admin-only: sets `Config::max_swap_amount`, the upper bound
+    /// `burn_sxmr` enforces on a burn's gross amount. `0` means no cap.
+    SetMaxSwap {
+        amount: Uint128,
+    },
+    /// Admin-only: sets `Config::min_mint_amount`/`Config::max_mint_amount`,
+    /// the window `mint_sxmr` enforces on a single mint's `amount`. `0` means
+    /// no floor/ceiling respectively. Independent of `min_swap_amount`/
+    /// `max_swap_amount`, which bound burns instead.
+    SetMintBounds {
+        min: Uint128,
+        max: Uint128,
+    },
+    /// Admin-only: sets `Config::unit_granularity`. Once set, `mint_sxmr` and
+    /// `burn_sxmr` reject any amount that isn't an exact multiple of it
+    /// (e.g. `10^9` to forbid sub-nano-XMR dust). `0` disables the check.
+    SetUnitGranularity {
+        granularity: Uint128,
+    },
+    /// Admin-only: sets `Config::mint_limit_per_window`/
+    /// `Config::mint_window_blocks`, a rolling per-recipient mint rate limit
+    /// enforced by `mint_sxmr` (see `state::MintLimitsStore`). `limit == 0`
+    /// disables the check.
+    SetMintRateLimit {
+        limit: Uint128,
+        window_blocks: u64,
+    },
+    /// Admin-only: sets `Config::mint_threshold`, the number of distinct
+    /// minters (bridge_minter or `Config::minters()` members) that must
+    /// submit a matching `MintSecretMonero` for the same `(tx_id, recipient,
+    /// amount)` before `mint_sxmr` actually mints. See
+    /// `state::MintApprovalsStore`. `1` (the default) mints on the first
+    /// submission, matching this contract's behavior before this setting
+    /// existed.
+    SetMintThreshold {
+        threshold: u32,
+    },
+    /// Admin-only: sets `Config::max_destinations_per_burn`, the cap
+    /// `burn_sxmr` enforces on a `BurnDestination::Multi` burn's destination
+    /// count. `0` means no cap.
+    SetMaxDestinationsPerBurn {
+        max: u32,
+    },
+    /// Tags a swap the caller owns with a short client-side label. Callable
+    /// only by the swap's owner.
+    SetSwapLabel {
+        nonce: u32,
+        label: String,
+    },
+    /// Admin/relayer-only: backfills the Monero payout transaction hash onto
+    /// `owner`'s swap at `nonce`, for when it was resolved without one
+    /// attached (e.g. an older relayer version). A non-admin relayer may not
+    /// overwrite a hash that's already attached; the admin can.
+    AttachPayoutTx {
+        owner: HumanAddr,
+        nonce: u32,
+        monero_tx_id: String,
+    },
+    /// Admin/minter-only: flips `owner`'s swap at `nonce` to
+    /// `SwapStatus::Completed`, recording `monero_tx_id`. Errors if the swap
+    /// is already completed.
+    CompleteSwap {
+        owner: HumanAddr,
+        nonce: u32,
+        monero_tx_id: String,
+    },
+    /// Owner-only: merges the caller's listed `Pending` swaps, which must all
+    /// target `to_monero_address`, into one new swap for their summed
+    /// amount. The listed swaps are marked `SwapStatus::Consolidated`
+    /// instead of being paid out individually.
+    ConsolidateSwaps {
+        nonces: Vec<u32>,
+        to_monero_address: String,
+    },
+    /// Admin/minter-only: re-mints `owner`'s swap at `nonce` back to sXMR and
+    /// flips it to `SwapStatus::Refunded`, for a swap the bridge can't
+    /// deliver XMR for. Errors if the swap is already `Completed` or already
+    /// `Refunded`.
+    RefundSwap {
+        owner: HumanAddr,
+        nonce: u32,
+    },
+    /// Admin/minter-only: flips `owner`'s `Pending` swap at `nonce` to
+    /// `SwapStatus::Processing`, so a concurrent `CancelSwap` can't race an
+    /// in-flight Monero payout. See `SwapStatus::Processing`.
+    MarkSwapProcessing {
+        owner: HumanAddr,
+        nonce: u32,
+    },
+    /// Self-cancel: refunds the caller's own `Pending` swap at `nonce` back
+    /// to sXMR, the same terminal state `RefundSwap` produces but callable
+    /// by the swap's owner directly. Blocked once the swap is `Processing`
+    /// or otherwise resolved.
+    CancelSwap {
+        nonce: u32,
+    },
+    /// Pre-approves a destination for the caller so future burns to it skip
+    /// re-validation (it is still checked against the global blocklist).
+    WhitelistDestination {
+        to_monero_address: String,
+    },
+    /// Admin/relayer cleanup: re-mints and marks `Expired` up to `limit`
+    /// pending swaps whose TTL has passed. Returns the count processed.
+    SweepExpiredSwaps {
+        limit: u32,
+    },
+    /// Admin-only: re-sends `register_receive` to the sXMR token with the
+    /// bridge's current code hash, for after a code migration leaves the
+    /// token holding a stale one.
+    ReRegisterReceive {},
+    /// Admin-only: blocks new burns while `env.block.height` falls within
+    /// `[start_block, end_block]`, for scheduled Monero wallet maintenance.
+    /// Mints and queries are unaffected.
+    SetMaintenanceWindow {
+        start_block: u64,
+        end_block: u64,
+    },
+    ChangeAdmin {
+        address: HumanAddr,
+    },
+    /// Admin-only: begins a two-step admin handover. The admin keeps control
+    /// until `address` calls `AcceptAdmin`, unlike `ChangeAdmin`'s immediate,
+    /// one-step transfer.
+    ProposeAdmin {
+        address: HumanAddr,
+    },
+    /// Callable only by the pending admin proposed via `ProposeAdmin`; takes
+    /// effect immediately even while the contract is `Paused` or in
+    /// `Emergency`, so a planned handover can't be stranded by a pause.
+    AcceptAdmin {},
+    /// Admin-only: withdraws a pending `ProposeAdmin` proposal. Also exempt
+    /// from the pause gate, for the same reason as `AcceptAdmin`.
+    CancelAdminTransfer {},
+    SetContractStatus {
+        level: ContractStatusLevel,
+    },
+    /// Admin-only: designates the address allowed to attest deposits via
+    /// `SubmitOracleAttestation`.
+    SetOracle {
+        address: HumanAddr,
+    },
+    /// Independently attests that `tx_id` is a genuine Monero deposit.
+    /// `mint_sxmr` will not accept a proof for a `tx_id` lacking one of
+    /// these once an oracle is configured.
+    SubmitOracleAttestation {
+        tx_id: String,
+    },
+    /// Admin/oracle-only: writes off a single output's deposit whose Monero
+    /// transaction was later orphaned by a reorg after the bridge already
+    /// minted against it. Scoped to `(tx_id, output_index)`, not the whole
+    /// `tx_id`, since one Monero tx can fund several outputs and a reorg may
+    /// only orphan one of them. See `revert_mint` in `contract.rs` for why
+    /// this is always a shortfall write-off rather than an actual clawback.
+    RevertMint {
+        tx_id: String,
+        output_index: u32,
+    },
+    /// Admin-only: seeds the replay-protection set from a predecessor
+    /// contract's `ExportProofSet` query, so a successor deployment can't be
+    /// replayed against with already-used Monero transactions.
+    ImportProofs {
+        entries: Vec<(String, u32)>,
+    },
+    /// Admin-only: mints sXMR without a Monero proof. Only callable when this
+    /// contract was initialized with `testnet_mode: true` — see `InitMsg`'s
+    /// doc comment for why that can't happen on mainnet.
+    TestMint {
+        recipient: HumanAddr,
+        amount: Uint128,
+    },
+    /// Admin-only: sets how many blocks a `Queue*` action must wait before
+    /// `ExecutePendingAction` will run it. `0` (the default) disables the
+    /// timelock.
+    SetTimelockBlocks {
+        blocks: u64,
+    },
+    /// Queues a minter-list change behind `timelock_blocks` instead of
+    /// applying it immediately like `SetMinters`.
+    QueueSetMinters {
+        minters: Vec<HumanAddr>,
+    },
+    /// Queues repointing the bridge at a different sXMR token contract (a
+    /// "token swap") behind `timelock_blocks`.
+    QueueSetSxmrToken {
+        address: HumanAddr,
+        code_hash: String,
+        decimals: u8,
+    },
+    /// Queues replacing the monitored Monero wallet list (a "wallet
+    /// rotation") behind `timelock_blocks`.
+    QueueSetMoneroWallets {
+        wallets: Vec<String>,
+    },
+    /// Admin-only: applies a queued action once its timelock has elapsed.
+    ExecutePendingAction {
+        id: u32,
+    },
+    /// Admin-only: withdraws a queued action without applying it.
+    CancelPendingAction {
+        id: u32,
+    },
+    /// Admin-only: sets the viewing key this contract presents to the sXMR
+    /// token when querying its own balance for `SolvencyCheck`, and pushes
+    /// the same key to the token via `SetViewingKey` so the two stay in
+    /// sync. The key being replaced is kept as a fallback for a short
+    /// overlap window, since the `SetViewingKey` message to the token is
+    /// only queued here, not applied yet, so a `SolvencyCheck` run before it
+    /// lands would otherwise see the old key rejected before the new one
+    /// takes effect. See `RetireBridgeViewingKey`.
+    SetBridgeViewingKey {
+        key: String,
+    },
+    /// Admin-only: drops the fallback viewing key kept by `SetBridgeViewingKey`
+    /// once its overlap window is no longer needed.
+    RetireBridgeViewingKey {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleResult {
+    MintSecretMonero {
+        status: String,
+        token_address: Option<HumanAddr>,
+        token_contract_hash: Option<String>,
+        /// Set when `auto_vk_on_mint` generated a fresh viewing key for a
+        /// cold recipient. See `auto_viewing_key_for_mint`'s doc comment in
+        /// `contract.rs` for why this is plaintext-in-response rather than
+        /// encrypted, and who can see it as a result.
+        auto_viewing_key: Option<String>,
+        /// The Monero deposit transaction this mint was proven against, so a
+        /// relayer or recipient can link the result back to its source
+        /// without decoding logs.
+        tx_id: String,
+        recipient: HumanAddr,
+        amount: Uint128,
+    },
+    /// One `tx_id` per successfully minted item, in the same order as the
+    /// `BatchMint` request.
+    BatchMint {
+        tx_ids: Vec<String>,
+    },
+    Receive {
+        status: String,
+        token_address: Option<HumanAddr>,
+        token_contract_hash: Option<String>,
+    },
+    SetViewingKey { status: String },
+    ChangeViewingKey { status: String },
+    CreateViewingKey { key: String },
+    SetMinters { status: String },
+    AddMinters { status: String },
+    RemoveMinters { status: String },
+    SetFeeCollector { status: String },
+    SweepFees { swept: Uint128 },
+    SetFee { status: String },
+    SetMaxSwap {
+        status: String,
+        /// How many currently `Pending` swaps fall outside `[min_swap_amount,
+        /// max_swap_amount]` after this change, as a heads-up; already-open
+        /// swaps are never rejected or mutated by `SetMaxSwap` itself. See
+        /// `set_max_swap`.
+        stale_pending_count: u32,
+    },
+    SetMintBounds { status: String },
+    SetUnitGranularity { status: String },
+    SetMintRateLimit { status: String },
+    SetMintThreshold { status: String },
+    SetMaxDestinationsPerBurn { status: String },
+    ReRegisterReceive { status: String },
+    SetMaintenanceWindow { status: String },
+    SetSwapLabel { status: String },
+    AttachPayoutTx { status: String },
+    CompleteSwap { status: String },
+    ConsolidateSwaps { status: String, new_nonce: u32 },
+    RefundSwap { status: String },
+    MarkSwapProcessing { status: String },
+    CancelSwap { status: String },
+    WhitelistDestination { status: String },
+    SweepExpiredSwaps { processed: u32 },
+    ChangeAdmin { status: String },
+    ProposeAdmin { status: String },
+    AcceptAdmin { status: String },
+    CancelAdminTransfer { status: String },
+    SetContractStatus { status: String },
+    SetOracle { status: String },
+    SubmitOracleAttestation { status: String },
+    RevertMint { status: String },
+    ImportProofs { imported: u32 },
+    TestMint { status: String },
+    SetTimelockBlocks { status: String },
+    QueueSetMinters { id: u32 },
+    QueueSetSxmrToken { id: u32 },
+    QueueSetMoneroWallets { id: u32 },
+    ExecutePendingAction { status: String },
+    CancelPendingAction { status: String },
+    SetBridgeViewingKey { status: String },
+    RetireBridgeViewingKey { status: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// Byte length of the bincode-serialized `Constants`, for estimating the
+    /// gas cost of a future config migration.
+    ConfigSize {},
+    /// One call summarizing which operations will currently succeed, for
+    /// adaptive UIs that would otherwise need to infer this from status.
+    Capabilities {},
+    /// Reports which admin-facing roles `address` currently holds, so a
+    /// wallet or tooling UI can show only the panels it's authorized to use.
+    MyRoles {
+        address: HumanAddr,
+        viewing_key: String,
+    },
+    /// Public: running bridge-wide totals for auditors and dashboards. See
+    /// `state::Statistics`.
+    Statistics {},
+    SwapDetails {
+        address: HumanAddr,
+        nonce: u32,
+        /// When set, resolves the swap by this stable id instead of `nonce`
+        /// (which is a position and could shift after a future compaction).
+        swap_id: Option<String>,
+        viewing_key: String,
+        /// When set, the response is returned as
+        /// `QueryResponse::EncryptedSwapDetails`, sealed to this key instead
+        /// of as plaintext `QueryResponse::SwapDetails`. See `crypto_box`.
+        /// Must be exactly `crypto_box::KEY_LEN` (32) bytes.
+        encrypt_to: Option<Binary>,
+    },
+    /// Pages `address`'s swaps newest-first, for a user with more swaps than
+    /// fit in a single `SwapDetails` lookup.
+    SwapHistory {
+        address: HumanAddr,
+        viewing_key: String,
+        page: u32,
+        page_size: u32,
+    },
+    /// O(1) breakdown of swaps by lifecycle status, for capacity planning.
+    SwapCounts {},
+    /// The configured min/max burn amount, in both raw sXMR units and
+    /// human-formatted XMR, so wallets don't have to reimplement the
+    /// conversion.
+    SwapLimits {},
+    /// Checks a client-reconstructed receipt hash against the one the
+    /// contract computes for the stored swap.
+    VerifyReceipt {
+        address: HumanAddr,
+        nonce: u32,
+        /// When set, resolves the swap by this stable id instead of `nonce`.
+        swap_id: Option<String>,
+        viewing_key: String,
+        expected_hash: String,
+    },
+    /// Admin-only: resolves a swap from its receipt hash alone, for support
+    /// workflows where the user can't be asked for their viewing key.
+    /// `admin_viewing_key` is checked against the admin address's own key.
+    SwapByReceipt {
+        admin_viewing_key: String,
+        receipt_hash: String,
+    },
+    /// Admin-only: lists proofs confirmed within `[from, to]` Monero block
+    /// heights, for auditors reconciling against the Monero chain.
+    ProofsByBlockRange {
+        admin_viewing_key: String,
+        from: u64,
+        to: u64,
+        page: u32,
+        page_size: u32,
+    },
+    /// Admin-only: looks up the recorded proof for a single deposit `tx_id`,
+    /// for an auditor reconciling one Monero transaction against the sXMR it
+    /// minted.
+    ProofByTxId {
+        admin_viewing_key: String,
+        tx_id: String,
+    },
+    /// The total sXMR outstanding (minted minus burned), formatted in human
+    /// XMR alongside the raw value.
+    PendingLiabilityHuman {},
+    /// Admin-only: pages the proof dedup set's `(tx_id, output_index)` keys
+    /// for migrating replay protection to a successor contract via
+    /// `ImportProofs`.
+    ExportProofSet {
+        admin_viewing_key: String,
+        page: u32,
+        page_size: u32,
+    },
+    /// Whether `to_monero_address` is on the global destination blocklist,
+    /// checked by every burn regardless of caller whitelisting.
+    IsDestinationBlocked {
+        to_monero_address: String,
+    },
+    /// Whether `address` is on the sender blocklist. See
+    /// `BlockedSendersStore`'s doc comment in `state.rs`: nothing currently
+    /// enforces this during a burn.
+    IsSenderBlocked {
+        address: HumanAddr,
+    },
+    /// Whether `tx_id` has already been minted, via the O(1)
+    /// `MoneroProofsStore` index. Unauthenticated: `tx_id`s are public on the
+    /// Monero chain anyway, so this leaks nothing a relayer couldn't already
+    /// see, and lets off-chain software cheaply skip a doomed mint before
+    /// spending gas on it.
+    IsProofUsed {
+        tx_id: String,
+    },
+    /// Admin-only: lists every `Queue*` action awaiting execution or
+    /// cancellation, with the block height each becomes executable at.
+    PendingActions {
+        admin_viewing_key: String,
+    },
+    /// Admin-only: compares the bridge's fee ledger (`accumulated_fees`)
+    /// against its actual queried sXMR balance, for solvency monitoring.
+    /// Requires `SetBridgeViewingKey` to have been called first.
+    SolvencyCheck {
+        admin_viewing_key: String,
+    },
+    /// Dry-runs `Receive`'s burn-side checks (decimals alignment, min/dust
+    /// amount, maintenance window, destination blocklist/whitelist) against
+    /// the current config without mutating any state, so a wallet can warn
+    /// the user before they sign. `current_block_height` is required because
+    /// queries in this CosmWasm version aren't given the current `Env` — the
+    /// caller supplies whatever height it already fetched from the node.
+    /// Does not check `BlockedSendersStore` since `Receive` itself doesn't
+    /// yet either; see that store's doc comment in `state.rs`.
+    SimulateBurn {
+        from: HumanAddr,
+        amount: Uint128,
+        to_monero_address: String,
+        current_block_height: u64,
+    },
+    /// Admin-only: every adjustable parameter in one call, for rendering a
+    /// config-editing form. Omits `prng_seed` and `bridge_viewing_key`.
+    FullConfig {
+        admin_viewing_key: String,
+    },
+    /// Admin-only: pages every swap still `Pending` across all owners,
+    /// oldest-first, for the off-chain Monero sender to poll the whole book
+    /// instead of one user's swaps at a time.
+    AllPendingSwaps {
+        viewing_key: String,
+        page: u32,
+        page_size: u32,
+    },
+    /// Admin-only: pages every swap currently tagged `status`, across all
+    /// owners, for ops to pull e.g. all `Expired` swaps for a cleanup
+    /// report. Backed by `StatusIndex` rather than a full scan.
+    SwapsByStatus {
+        viewing_key: String,
+        status: SwapStatusFilter,
+        page: u32,
+        page_size: u32,
+    },
+    /// Admin-only: dumps stored swaps from `start_nonce` (inclusive)
+    /// ascending, for a migration script to stream the whole book in pages
+    /// and resume from the last nonce it saw. `limit` is capped at
+    /// `contract::MAX_EXPORT_PAGE_SIZE`.
+    ExportSwaps {
+        viewing_key: String,
+        start_nonce: u32,
+        limit: u32,
+    },
+    /// Runs `query` authenticated by `permit` instead of a viewing key. See
+    /// `crate::permit`.
+    WithPermit {
+        permit: crate::permit::Permit,
+        query: QueryWithPermit,
+    },
+}
+
+/// The subset of owner-scoped queries a `QueryMsg::WithPermit` can run.
+/// Unlike their viewing-key-authenticated counterparts, these carry no
+/// `address`/`viewing_key` — the permit's signer supplies the address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryWithPermit {
+    SwapDetails {
+        nonce: u32,
+        /// When set, resolves the swap by this stable id instead of `nonce`.
+        swap_id: Option<String>,
+        encrypt_to: Option<Binary>,
+    },
+    SwapHistory {
+        page: u32,
+        page_size: u32,
+    },
+}
@@ -0,0 +1,153 @@
+use bech32::ToBase32;
+use cosmwasm_std::{Binary, HumanAddr, StdError, StdResult};
+use ripemd160::{Digest as _, Ripemd160};
+use schemars::JsonSchema;
+use secp256k1::{Message, PublicKey, Secp256k1, Signature};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+/// The bech32 human-readable prefix for every Secret Network address.
+const BECH32_HRP: &str = "secret";
+
+/// What a `WithPermit` query is allowed to see; mirrors SNIP-24's
+/// `Permission` enum. This bridge only exposes owner-scoped queries
+/// (`SwapDetails`/`SwapHistory`), so `Owner` is the only variant.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Owner,
+}
+
+/// The signed portion of a permit, matching the fields a wallet renders for
+/// the user before signing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitParams {
+    pub permit_name: String,
+    pub chain_id: String,
+    /// This contract's address must be listed for the permit to authorize a
+    /// query against it; a permit signed for a different (or no) contract
+    /// is rejected by `validate`.
+    pub allowed_tokens: Vec<HumanAddr>,
+    pub permissions: Vec<Permission>,
+}
+
+/// The public key half of a permit's signature, in the shape Keplr and other
+/// Cosmos wallets emit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitPubKey {
+    /// Always `"tendermint/PubKeySecp256k1"` in wallet output; not checked
+    /// here since `value`'s length already constrains it to secp256k1.
+    #[serde(rename = "type")]
+    pub key_type: String,
+    /// The 33-byte compressed secp256k1 public key.
+    pub value: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitSignature {
+    pub pub_key: PermitPubKey,
+    /// The 64-byte compact (`r || s`) secp256k1 signature.
+    pub signature: Binary,
+}
+
+/// A SNIP-24 query permit: an off-chain-signed alternative to a viewing key
+/// that needs no on-chain transaction to create and carries no long-lived
+/// secret to leak, at the cost of being scoped to `params.permissions`
+/// rather than to the whole account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Permit {
+    pub params: PermitParams,
+    pub signature: PermitSignature,
+}
+
+/// Verifies `permit`'s signature and that it authorizes `contract_address`
+/// under `Permission::Owner`, returning the signer's address on success.
+/// Reimplements SNIP-24's `signDoc` shape by hand since this contract has no
+/// `secret-toolkit` `permit` feature enabled.
+pub fn validate(permit: &Permit, contract_address: &HumanAddr) -> StdResult<HumanAddr> {
+    if !permit
+        .params
+        .allowed_tokens
+        .iter()
+        .any(|token| token == contract_address)
+    {
+        return Err(StdError::generic_err(
+            "permit does not authorize this contract",
+        ));
+    }
+    if !permit.params.permissions.contains(&Permission::Owner) {
+        return Err(StdError::generic_err(
+            "permit does not grant the owner permission this query requires",
+        ));
+    }
+
+    let sign_bytes = sign_doc_bytes(&permit.params);
+    let hash = Sha256::digest(&sign_bytes);
+
+    let pubkey_bytes = permit.signature.pub_key.value.0.as_slice();
+    let pubkey = PublicKey::from_slice(pubkey_bytes)
+        .map_err(|_| StdError::generic_err("invalid permit public key"))?;
+    let signature = Signature::from_compact(permit.signature.signature.0.as_slice())
+        .map_err(|_| StdError::generic_err("invalid permit signature"))?;
+    let message = Message::from_slice(hash.as_slice())
+        .map_err(|_| StdError::generic_err("invalid permit signature digest"))?;
+
+    Secp256k1::verification_only()
+        .verify(&message, &signature, &pubkey)
+        .map_err(|_| StdError::generic_err("permit signature verification failed"))?;
+
+    address_from_pubkey(pubkey_bytes)
+}
+
+/// Reconstructs the amino `StdSignDoc` JSON a wallet signs for a SNIP-24
+/// permit: a zero-fee, zero-account/sequence transaction whose only message
+/// is a `signature_proof` carrying `params`. Field order and formatting
+/// matter here since the result is hashed as raw bytes, never reparsed.
+///
+/// `pub(crate)` so `contract.rs`'s tests can sign the same bytes this
+/// module verifies against, without a real wallet in the loop.
+pub(crate) fn sign_doc_bytes(params: &PermitParams) -> Vec<u8> {
+    let allowed_tokens = params
+        .allowed_tokens
+        .iter()
+        .map(|addr| format!("\"{}\"", json_escape(addr.as_str())))
+        .collect::<Vec<_>>()
+        .join(",");
+    let permissions = params
+        .permissions
+        .iter()
+        .map(|permission| match permission {
+            Permission::Owner => "\"owner\"".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        "{{\"chain_id\":\"{}\",\"account_number\":\"0\",\"sequence\":\"0\",\
+         \"fee\":{{\"gas\":\"1\",\"amount\":[]}},\"msgs\":[{{\"type\":\"signature_proof\",\
+         \"value\":{{\"permit_name\":\"{}\",\"allowed_tokens\":[{}],\"permissions\":[{}]}}}}],\
+         \"memo\":\"\"}}",
+        json_escape(&params.chain_id),
+        json_escape(&params.permit_name),
+        allowed_tokens,
+        permissions,
+    )
+    .into_bytes()
+}
+
+fn json_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Derives a Secret Network address from a compressed secp256k1 public key:
+/// `bech32("secret", ripemd160(sha256(pubkey)))`, the same derivation every
+/// Cosmos SDK chain uses for its bech32 account addresses.
+///
+/// `pub(crate)` so tests can compute the address a signing key will recover
+/// to before constructing the swap it's expected to authenticate.
+pub(crate) fn address_from_pubkey(pubkey_bytes: &[u8]) -> StdResult<HumanAddr> {
+    let sha = Sha256::digest(pubkey_bytes);
+    let ripemd = Ripemd160::digest(&sha);
+    let address = bech32::encode(BECH32_HRP, ripemd.as_slice().to_base32(), bech32::Variant::Bech32)
+        .map_err(|_| StdError::generic_err("failed to encode signer address"))?;
+    Ok(HumanAddr(address))
+}
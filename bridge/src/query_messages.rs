@@ -0,0 +1,277 @@
+use cosmwasm_std::{HumanAddr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryResponse {
+    Config {
+        admin: HumanAddr,
+        /// The address a `ProposeAdmin` handover is waiting on to call
+        /// `AcceptAdmin`, if one is in progress.
+        pending_admin: Option<HumanAddr>,
+        sxmr_address: HumanAddr,
+        bridge_minter: HumanAddr,
+        monero_wallets: Vec<String>,
+        min_swap_amount: Uint128,
+        fee_collector: Option<HumanAddr>,
+        /// The inclusive bounds `mint_sxmr` enforces on a single mint's
+        /// amount. `0` means no floor/ceiling respectively. See
+        /// `HandleMsg::SetMintBounds`.
+        min_mint_amount: Uint128,
+        max_mint_amount: Uint128,
+        /// `0` when unit-granularity enforcement is off. See
+        /// `HandleMsg::SetUnitGranularity`.
+        unit_granularity: Uint128,
+    },
+    SwapDetails {
+        from_secret_address: HumanAddr,
+        to_monero_address: String,
+        /// An integrated-address payment id recorded against this swap, if
+        /// the burn supplied one via `BurnDestination::SingleWithPaymentId`.
+        /// `None` for a plain address (including one that's itself an
+        /// integrated address, which carries its payment id embedded).
+        payment_id: Option<String>,
+        /// A client-supplied note forwarded into the Monero payout, set at
+        /// burn time via `BurnDestination::WithMemo`. See
+        /// `SwapDetails::memo`.
+        memo: Option<String>,
+        /// The net amount this swap will pay out, after `fee` was deducted
+        /// from the gross amount burned.
+        amount: Uint128,
+        /// `amount` converted to XMR atomic units (piconero) at the
+        /// `sxmr_decimals` in effect when this swap was created. See
+        /// `crate::state::scale_sxmr_to_xmr`.
+        xmr_atomic_amount: u64,
+        /// The fee deducted from the gross burned amount (see
+        /// `SwapDetails::fee_taken`).
+        fee: Uint128,
+        destinations: Vec<(String, Uint128)>,
+        label: Option<String>,
+        swap_id: String,
+        /// The Monero payout transaction hash, once known. See
+        /// `SwapDetails::monero_tx_id`.
+        monero_tx_id: Option<String>,
+        /// The fee, in basis points, that was in effect when this swap was
+        /// created (see `Config::fee_bps`), not the current one.
+        fee_bps_at_creation: u16,
+        /// The sXMR decimal scale in effect when this swap was created (see
+        /// `Config::sxmr_decimals`), not the current one.
+        scale_at_creation: u8,
+        /// The swap's lifecycle state. See `SwapStatus`.
+        status: crate::state::SwapStatus,
+    },
+    SwapHistory {
+        swaps: Vec<SwapSummary>,
+    },
+    SwapCounts {
+        pending: u64,
+        fulfilled: u64,
+        refunded: u64,
+        expired: u64,
+    },
+    SwapLimits {
+        min_swap_amount: Uint128,
+        min_swap_amount_human: String,
+        max_swap_amount: Uint128,
+        max_swap_amount_human: String,
+    },
+    VerifyReceipt {
+        matches: bool,
+    },
+    SwapByReceipt {
+        owner: HumanAddr,
+        nonce: u32,
+        to_monero_address: String,
+        amount: Uint128,
+        destinations: Vec<(String, Uint128)>,
+        label: Option<String>,
+    },
+    ProofsByBlockRange {
+        proofs: Vec<ProofSummary>,
+    },
+    ProofByTxId {
+        /// `None` if no proof was ever recorded for that `tx_id`.
+        proof: Option<ProofSummary>,
+    },
+    PendingLiabilityHuman {
+        raw: Uint128,
+        human: String,
+    },
+    ConfigSize {
+        bytes: u64,
+    },
+    Capabilities {
+        capabilities: Vec<(String, bool)>,
+    },
+    MyRoles {
+        is_admin: bool,
+        is_minter: bool,
+        is_relayer: bool,
+    },
+    Statistics {
+        total_minted: Uint128,
+        total_burned: Uint128,
+        total_swap_count: u64,
+        total_refunded: Uint128,
+    },
+    ExportProofSet {
+        entries: Vec<(String, u32)>,
+    },
+    IsDestinationBlocked {
+        blocked: bool,
+    },
+    IsSenderBlocked {
+        blocked: bool,
+    },
+    IsProofUsed {
+        used: bool,
+    },
+    PendingActions {
+        actions: Vec<PendingActionSummary>,
+    },
+    SolvencyCheck {
+        /// What the bridge's ledger believes it should be holding
+        /// (`accumulated_fees`).
+        expected: Uint128,
+        /// The bridge's actual queried sXMR balance.
+        actual: Uint128,
+        /// `actual - expected` when positive, e.g. from a direct transfer or
+        /// a bug; zero otherwise.
+        surplus: Uint128,
+        /// `expected - actual` when positive; zero otherwise.
+        shortfall: Uint128,
+    },
+    SimulateBurn {
+        accepted: bool,
+        /// Why the simulated burn was rejected; `None` when `accepted`.
+        reason: Option<String>,
+    },
+    /// The `SwapDetails` payload sealed to the caller's `encrypt_to` key
+    /// instead of returned as plaintext. See `crate::crypto_box::seal`.
+    EncryptedSwapDetails {
+        ciphertext: cosmwasm_std::Binary,
+        nonce: cosmwasm_std::Binary,
+    },
+    FullConfig {
+        full_config: FullConfig,
+    },
+    AllPendingSwaps {
+        swaps: Vec<PendingSwapSummary>,
+    },
+    SwapsByStatus {
+        swaps: Vec<SwapSummary>,
+    },
+    ExportSwaps {
+        swaps: Vec<ExportedSwap>,
+    },
+}
+
+/// One entry in an `AllPendingSwaps` page: the minimum an operator's
+/// off-chain Monero sender needs to actually pay a swap out.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSwapSummary {
+    pub nonce: u32,
+    pub owner: HumanAddr,
+    pub to_monero_address: String,
+    pub amount: Uint128,
+}
+
+/// Every adjustable bridge parameter in one struct, for an admin config
+/// editor. Deliberately excludes `prng_seed` and `bridge_viewing_key` (see
+/// `QueryMsg::FullConfig`'s doc comment).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FullConfig {
+    pub admin: HumanAddr,
+    pub bridge_minter: HumanAddr,
+    pub minters: Vec<HumanAddr>,
+    pub max_minters: u32,
+    pub monero_wallets: Vec<String>,
+    pub min_swap_amount: Uint128,
+    pub max_swap_amount: Uint128,
+    pub max_destinations_per_burn: u32,
+    pub fee_bps: u16,
+    pub fee_collector: Option<HumanAddr>,
+    pub fee_sweep_threshold: Uint128,
+    pub monero_dust_limit: Uint128,
+    pub require_known_recipient: bool,
+    pub enforce_monotonic_proof_order: bool,
+    pub include_token_info_in_result: bool,
+    pub swap_ttl_seconds: u64,
+    pub auto_vk_on_mint: bool,
+    pub enforce_decimal_alignment: bool,
+    pub sxmr_decimals: u8,
+    pub require_sender_equals_from: bool,
+    pub maintenance_window: Option<(u64, u64)>,
+    pub timelock_blocks: u64,
+    pub testnet_mode: bool,
+    pub min_mint_amount: Uint128,
+    pub max_mint_amount: Uint128,
+    pub unit_granularity: Uint128,
+    pub mint_limit_per_window: Uint128,
+    pub mint_window_blocks: u64,
+    pub mint_threshold: u32,
+}
+
+/// A queued timelocked action's public-facing fields; `description` is a
+/// human-readable rendering of the underlying `TimelockedAction` since that
+/// enum carries `CanonicalAddr`s not meaningful off-chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingActionSummary {
+    pub id: u32,
+    pub ready_at_block: u64,
+    pub description: String,
+}
+
+/// One entry in a `SwapHistory` page; a condensed view of `SwapDetails` for
+/// listing many at once, omitting `from_secret_address` (already known to
+/// the caller) and the at-creation fee/scale snapshots.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapSummary {
+    pub nonce: u32,
+    pub to_monero_address: String,
+    pub amount: Uint128,
+    pub fee: Uint128,
+    pub destinations: Vec<(String, Uint128)>,
+    pub label: Option<String>,
+    pub swap_id: String,
+    pub monero_tx_id: Option<String>,
+    pub resolved: bool,
+}
+
+/// One entry in an `ExportSwaps` page: `SwapDetails` with its owner
+/// resolved to a human address and every field a migration script needs to
+/// recreate the swap in a fresh contract, including its lifecycle `status`
+/// (omitted from the leaner `SwapSummary`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExportedSwap {
+    pub nonce: u32,
+    pub owner: HumanAddr,
+    pub to_monero_address: String,
+    pub payment_id: Option<String>,
+    pub memo: Option<String>,
+    pub amount: Uint128,
+    pub xmr_atomic_amount: u64,
+    pub fee: Uint128,
+    pub destinations: Vec<(String, Uint128)>,
+    pub label: Option<String>,
+    pub swap_id: String,
+    pub monero_tx_id: Option<String>,
+    pub status: crate::state::SwapStatus,
+    pub resolved: bool,
+}
+
+/// A proof's audit-relevant fields, including the sXMR amount it minted
+/// (`MoneroProof::amount`) and the recipient it minted to, looked up from
+/// `MintRecordStore` since a proof itself doesn't carry a recipient.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProofSummary {
+    pub tx_id: String,
+    pub block_height: u64,
+    pub output_index: u32,
+    pub amount: Uint128,
+    /// `None` if this `tx_id` has no `MintRecordStore` entry, e.g. a key
+    /// imported via `ImportProofs` rather than minted through
+    /// `MintSecretMonero`.
+    pub recipient: Option<HumanAddr>,
+}
@@ -0,0 +1,2577 @@
+use cosmwasm_std::{CanonicalAddr, Order, StdError, StdResult, Storage, Uint128};
+use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+use schemars::JsonSchema;
+use secret_toolkit::storage::{AppendStore, AppendStoreMut};
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+
+use crate::msg::ContractStatusLevel;
+
+pub const PREFIX_CONFIG: &[u8] = b"config";
+pub const PREFIX_SWAP_DETAILS: &[u8] = b"swap-details";
+pub const PREFIX_MONERO_PROOFS: &[u8] = b"monero-proofs";
+pub const PREFIX_VIEWING_KEY: &[u8] = b"viewing-key";
+
+pub const CONSTANTS_KEY: &[u8] = b"constants";
+pub const CONTRACT_STATUS_KEY: &[u8] = b"contract-status";
+pub const MINTERS_KEY: &[u8] = b"minters";
+pub const SWAP_COUNTS_KEY: &[u8] = b"swap-counts";
+pub const NEXT_SWAP_NONCE_KEY: &[u8] = b"next-swap-nonce";
+pub const FEE_COLLECTOR_KEY: &[u8] = b"fee-collector";
+pub const ACCUMULATED_FEES_KEY: &[u8] = b"accumulated-fees";
+pub const FEE_SWEEP_THRESHOLD_KEY: &[u8] = b"fee-sweep-threshold";
+pub const PREFIX_ORACLE_ATTESTATIONS: &[u8] = b"oracle-attestations";
+pub const PREFIX_WHITELISTED_DESTINATIONS: &[u8] = b"whitelisted-destinations";
+pub const PREFIX_BLOCKED_DESTINATIONS: &[u8] = b"blocked-destinations";
+pub const PREFIX_BLOCKED_SENDERS: &[u8] = b"blocked-senders";
+pub const PREFIX_RECEIPT_INDEX: &[u8] = b"receipt-index";
+pub const PREFIX_SWAP_ID_INDEX: &[u8] = b"swap-id-index";
+pub const MAINTENANCE_WINDOW_KEY: &[u8] = b"maintenance-window";
+pub const REQUIRE_SENDER_EQUALS_FROM_KEY: &[u8] = b"require-sender-equals-from";
+pub const PREFIX_MINT_RECORD: &[u8] = b"mint-record";
+pub const PREFIX_MINT_LIMITS: &[u8] = b"mint-limits";
+pub const MINT_LIMIT_PER_WINDOW_KEY: &[u8] = b"mint-limit-per-window";
+pub const MINT_WINDOW_BLOCKS_KEY: &[u8] = b"mint-window-blocks";
+pub const PREFIX_MINT_APPROVALS: &[u8] = b"mint-approvals";
+pub const MINT_THRESHOLD_KEY: &[u8] = b"mint-threshold";
+
+/// `mint_threshold` when never configured: a single minter's submission
+/// mints immediately, matching this contract's behavior before
+/// `HandleMsg::SetMintThreshold` existed.
+pub const DEFAULT_MINT_THRESHOLD: u32 = 1;
+pub const PREFIX_REVOKED_PROOFS: &[u8] = b"revoked-proofs";
+pub const PREFIX_IMPORTED_PROOF_KEYS: &[u8] = b"imported-proof-keys";
+pub const PENDING_ADMIN_KEY: &[u8] = b"pending-admin";
+pub const FEE_BPS_KEY: &[u8] = b"fee-bps";
+pub const SHORTFALL_DEBT_KEY: &[u8] = b"shortfall-debt";
+pub const ORACLE_KEY: &[u8] = b"oracle";
+pub const MONERO_DUST_LIMIT_KEY: &[u8] = b"monero-dust-limit";
+pub const MAX_MINTERS_KEY: &[u8] = b"max-minters";
+pub const TIMELOCK_BLOCKS_KEY: &[u8] = b"timelock-blocks";
+pub const NEXT_PENDING_ACTION_ID_KEY: &[u8] = b"next-pending-action-id";
+pub const PREFIX_PENDING_ACTIONS: &[u8] = b"pending-actions";
+pub const BRIDGE_VIEWING_KEY_KEY: &[u8] = b"bridge-viewing-key";
+pub const PREV_BRIDGE_VIEWING_KEY_KEY: &[u8] = b"prev-bridge-viewing-key";
+pub const PREFIX_SWAP_STATUS_INDEX: &[u8] = b"swap-status-index";
+pub const PREFIX_PROOF_INDEX: &[u8] = b"proof-index";
+
+/// Default cap on the minter list when `max_minters` has never been
+/// configured, to bound attack surface and `minters()` query cost without
+/// requiring every deployment to set one explicitly.
+pub const DEFAULT_MAX_MINTERS: u32 = 10;
+
+/// Longest label a swap owner may attach via `SetSwapLabel`.
+pub const MAX_SWAP_LABEL_LEN: usize = 64;
+
+/// Highest `fee_bps` a `HandleMsg::SetFee` is allowed to set (10%), so a
+/// misconfigured or malicious admin call can't deduct most of a user's burn.
+pub const MAX_FEE_BPS: u16 = 1000;
+
+pub const REQUIRE_KNOWN_RECIPIENT_KEY: &[u8] = b"require-known-recipient";
+pub const ENFORCE_MONOTONIC_PROOF_ORDER_KEY: &[u8] = b"enforce-monotonic-proof-order";
+pub const LAST_PROOF_HEIGHT_KEY: &[u8] = b"last-proof-height";
+pub const INCLUDE_TOKEN_INFO_IN_RESULT_KEY: &[u8] = b"include-token-info-in-result";
+pub const SWAP_TTL_SECONDS_KEY: &[u8] = b"swap-ttl-seconds";
+pub const MAX_SWAP_AMOUNT_KEY: &[u8] = b"max-swap-amount";
+pub const MAX_DESTINATIONS_PER_BURN_KEY: &[u8] = b"max-destinations-per-burn";
+pub const AUTO_VK_ON_MINT_KEY: &[u8] = b"auto-vk-on-mint";
+pub const REGISTERED_CODE_HASH_KEY: &[u8] = b"registered-code-hash";
+pub const ENFORCE_DECIMAL_ALIGNMENT_KEY: &[u8] = b"enforce-decimal-alignment";
+pub const SXMR_DECIMALS_KEY: &[u8] = b"sxmr-decimals";
+pub const PENDING_LIABILITY_KEY: &[u8] = b"pending-liability";
+pub const MIN_MINT_AMOUNT_KEY: &[u8] = b"min-mint-amount";
+pub const MAX_MINT_AMOUNT_KEY: &[u8] = b"max-mint-amount";
+pub const UNIT_GRANULARITY_KEY: &[u8] = b"unit-granularity";
+pub const STATISTICS_KEY: &[u8] = b"statistics";
+
+/// Monero's atomic unit count (piconero), used to format raw sXMR amounts
+/// (which mirror XMR's own decimals) as human XMR strings for display.
+pub const MONERO_DECIMALS: u32 = 12;
+
+/// O(1) breakdown of swaps by lifecycle status, kept in sync on every
+/// transition so operators don't have to scan the append store.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct SwapCounts {
+    pub pending: u64,
+    pub fulfilled: u64,
+    pub refunded: u64,
+    pub expired: u64,
+}
+
+/// Running totals for `QueryMsg::Statistics`, incremented by checked
+/// arithmetic in `mint_sxmr`, `burn_sxmr`, and `refund_swap` so an overflow
+/// is reported as an error rather than silently wrapping.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Statistics {
+    pub total_minted: Uint128,
+    pub total_burned: Uint128,
+    pub total_swap_count: u64,
+    pub total_refunded: Uint128,
+}
+
+/// The sXMR token this bridge mints and burns against.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenInfo {
+    pub address: CanonicalAddr,
+    pub contract_hash: String,
+    /// The decimal scale this token was recorded with when it was set (at
+    /// init, or via a `SetSxmrToken` timelocked action). Compared against the
+    /// currently-active `Config::sxmr_decimals` in `burn_sxmr` so a `Receive`
+    /// arriving after the two have drifted apart (e.g. a reconfiguration that
+    /// swapped the token without updating the active scale) is rejected
+    /// rather than mis-scaled.
+    pub decimals: u8,
+}
+
+/// Immutable-at-init (but admin-rotatable) bridge parameters.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Constants {
+    pub admin: CanonicalAddr,
+    pub sxmr: TokenInfo,
+    pub bridge_minter: CanonicalAddr,
+    pub monero_wallets: Vec<String>,
+    pub min_swap_amount: Uint128,
+    pub prng_seed: Vec<u8>,
+    /// Distinct from `admin`: the only address allowed to enter or exit
+    /// `ContractStatusLevel::Emergency`, so a routine-pause-capable admin key
+    /// can't also trigger (or lift) a hard lockdown.
+    pub emergency_admin: CanonicalAddr,
+    /// Gates `HandleMsg::TestMint`. Set once at init and never changed
+    /// afterward — see `InitMsg::testnet_mode`'s doc comment for how `init`
+    /// keeps this off mainnet.
+    pub testnet_mode: bool,
+    /// This contract's own address, captured at init since `query()` is
+    /// never given an `Env` in this cosmwasm version. Needed by
+    /// `query_solvency_check` to ask the sXMR token for the bridge's own
+    /// balance.
+    pub bridge_address: CanonicalAddr,
+}
+
+pub struct Config<'a, S: Storage> {
+    storage: PrefixedStorage<'a, S>,
+}
+
+impl<'a, S: Storage> Config<'a, S> {
+    pub fn from_storage(storage: &'a mut S) -> Self {
+        Self {
+            storage: PrefixedStorage::new(PREFIX_CONFIG, storage),
+        }
+    }
+
+    pub fn constants(&self) -> StdResult<Constants> {
+        let bytes = self
+            .storage
+            .get(CONSTANTS_KEY)
+            .ok_or_else(|| StdError::generic_err("config not initialized"))?;
+        bincode2::deserialize::<Constants>(&bytes)
+            .map_err(|_| StdError::generic_err("failed to deserialize constants"))
+    }
+
+    pub fn set_constants(&mut self, constants: &Constants) -> StdResult<()> {
+        self.storage.set(
+            CONSTANTS_KEY,
+            &bincode2::serialize(constants)
+                .map_err(|_| StdError::generic_err("failed to serialize constants"))?,
+        );
+        Ok(())
+    }
+
+    pub fn contract_status(&self) -> ContractStatusLevel {
+        self.storage
+            .get(CONTRACT_STATUS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or(ContractStatusLevel::Running)
+    }
+
+    pub fn set_contract_status(&mut self, status: ContractStatusLevel) {
+        self.storage
+            .set(CONTRACT_STATUS_KEY, &bincode2::serialize(&status).unwrap());
+    }
+
+    pub fn minters(&self) -> Vec<CanonicalAddr> {
+        self.storage
+            .get(MINTERS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_minters(&mut self, minters: Vec<CanonicalAddr>) {
+        self.storage
+            .set(MINTERS_KEY, &bincode2::serialize(&minters).unwrap());
+    }
+
+    /// Cap on the minter list enforced by `set_minters`, defaulting to
+    /// `DEFAULT_MAX_MINTERS` when never configured.
+    pub fn max_minters(&self) -> u32 {
+        self.storage
+            .get(MAX_MINTERS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or(DEFAULT_MAX_MINTERS)
+    }
+
+    pub fn set_max_minters(&mut self, max_minters: u32) {
+        self.storage
+            .set(MAX_MINTERS_KEY, &bincode2::serialize(&max_minters).unwrap());
+    }
+
+    pub fn swap_counts(&self) -> SwapCounts {
+        self.storage
+            .get(SWAP_COUNTS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_swap_counts(&mut self, counts: &SwapCounts) {
+        self.storage
+            .set(SWAP_COUNTS_KEY, &bincode2::serialize(counts).unwrap());
+    }
+
+    /// `None` until a swap has ever been saved, so `SwapDetailsStore::save`
+    /// can tell a fresh contract apart from one upgraded from before this
+    /// counter existed (and bootstrap the latter from the append length).
+    pub fn next_swap_nonce(&self) -> Option<u32> {
+        self.storage
+            .get(NEXT_SWAP_NONCE_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+    }
+
+    pub fn set_next_swap_nonce(&mut self, nonce: u32) {
+        self.storage
+            .set(NEXT_SWAP_NONCE_KEY, &bincode2::serialize(&nonce).unwrap());
+    }
+
+    pub fn set_fee_collector(&mut self, collector: &CanonicalAddr) {
+        self.storage
+            .set(FEE_COLLECTOR_KEY, &bincode2::serialize(collector).unwrap());
+    }
+
+    pub fn fee_collector(&self) -> Option<CanonicalAddr> {
+        self.storage
+            .get(FEE_COLLECTOR_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    pub fn accumulated_fees(&self) -> Uint128 {
+        self.storage
+            .get(ACCUMULATED_FEES_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_accumulated_fees(&mut self, fees: Uint128) {
+        self.storage.set(
+            ACCUMULATED_FEES_KEY,
+            &bincode2::serialize(&fees.u128()).unwrap(),
+        );
+    }
+
+    pub fn fee_sweep_threshold(&self) -> Uint128 {
+        self.storage
+            .get(FEE_SWEEP_THRESHOLD_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_fee_sweep_threshold(&mut self, threshold: Uint128) {
+        self.storage.set(
+            FEE_SWEEP_THRESHOLD_KEY,
+            &bincode2::serialize(&threshold.u128()).unwrap(),
+        );
+    }
+
+    pub fn set_oracle(&mut self, oracle: &CanonicalAddr) {
+        self.storage.set(ORACLE_KEY, &bincode2::serialize(oracle).unwrap());
+    }
+
+    /// Defaults to zero (no enforcement) when never configured.
+    pub fn monero_dust_limit(&self) -> Uint128 {
+        self.storage
+            .get(MONERO_DUST_LIMIT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_monero_dust_limit(&mut self, limit: Uint128) {
+        self.storage.set(
+            MONERO_DUST_LIMIT_KEY,
+            &bincode2::serialize(&limit.u128()).unwrap(),
+        );
+    }
+
+    /// Defaults to false (no enforcement) when never configured.
+    pub fn require_known_recipient(&self) -> bool {
+        self.storage
+            .get(REQUIRE_KNOWN_RECIPIENT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn set_require_known_recipient(&mut self, require: bool) {
+        self.storage.set(
+            REQUIRE_KNOWN_RECIPIENT_KEY,
+            &bincode2::serialize(&require).unwrap(),
+        );
+    }
+
+    pub fn enforce_monotonic_proof_order(&self) -> bool {
+        self.storage
+            .get(ENFORCE_MONOTONIC_PROOF_ORDER_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn set_enforce_monotonic_proof_order(&mut self, enforce: bool) {
+        self.storage.set(
+            ENFORCE_MONOTONIC_PROOF_ORDER_KEY,
+            &bincode2::serialize(&enforce).unwrap(),
+        );
+    }
+
+    pub fn last_proof_height(&self) -> u64 {
+        self.storage
+            .get(LAST_PROOF_HEIGHT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_last_proof_height(&mut self, height: u64) {
+        self.storage
+            .set(LAST_PROOF_HEIGHT_KEY, &bincode2::serialize(&height).unwrap());
+    }
+
+    pub fn include_token_info_in_result(&self) -> bool {
+        self.storage
+            .get(INCLUDE_TOKEN_INFO_IN_RESULT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn set_include_token_info_in_result(&mut self, include: bool) {
+        self.storage.set(
+            INCLUDE_TOKEN_INFO_IN_RESULT_KEY,
+            &bincode2::serialize(&include).unwrap(),
+        );
+    }
+
+    /// Defaults to zero, meaning pending swaps never expire.
+    pub fn swap_ttl_seconds(&self) -> u64 {
+        self.storage
+            .get(SWAP_TTL_SECONDS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_swap_ttl_seconds(&mut self, ttl: u64) {
+        self.storage
+            .set(SWAP_TTL_SECONDS_KEY, &bincode2::serialize(&ttl).unwrap());
+    }
+
+    /// Defaults to zero (no cap) when never configured.
+    pub fn max_swap_amount(&self) -> Uint128 {
+        self.storage
+            .get(MAX_SWAP_AMOUNT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_max_swap_amount(&mut self, limit: Uint128) {
+        self.storage.set(
+            MAX_SWAP_AMOUNT_KEY,
+            &bincode2::serialize(&limit.u128()).unwrap(),
+        );
+    }
+
+    /// Defaults to zero (no floor) when never configured. See
+    /// `HandleMsg::SetMintBounds`.
+    pub fn min_mint_amount(&self) -> Uint128 {
+        self.storage
+            .get(MIN_MINT_AMOUNT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_min_mint_amount(&mut self, min: Uint128) {
+        self.storage.set(
+            MIN_MINT_AMOUNT_KEY,
+            &bincode2::serialize(&min.u128()).unwrap(),
+        );
+    }
+
+    /// Defaults to zero (no ceiling) when never configured. See
+    /// `HandleMsg::SetMintBounds`.
+    pub fn max_mint_amount(&self) -> Uint128 {
+        self.storage
+            .get(MAX_MINT_AMOUNT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_max_mint_amount(&mut self, max: Uint128) {
+        self.storage.set(
+            MAX_MINT_AMOUNT_KEY,
+            &bincode2::serialize(&max.u128()).unwrap(),
+        );
+    }
+
+    /// Defaults to zero (no enforcement) when never configured. See
+    /// `validate_unit_granularity`.
+    pub fn unit_granularity(&self) -> Uint128 {
+        self.storage
+            .get(UNIT_GRANULARITY_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_unit_granularity(&mut self, granularity: Uint128) {
+        self.storage.set(
+            UNIT_GRANULARITY_KEY,
+            &bincode2::serialize(&granularity.u128()).unwrap(),
+        );
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        self.storage
+            .get(STATISTICS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<Statistics>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn set_statistics(&mut self, statistics: &Statistics) {
+        self.storage
+            .set(STATISTICS_KEY, &bincode2::serialize(statistics).unwrap());
+    }
+
+    /// Adds `amount` to `total_minted`, erroring instead of wrapping on
+    /// overflow.
+    pub fn record_mint(&mut self, amount: Uint128) -> StdResult<()> {
+        let mut statistics = self.statistics();
+        statistics.total_minted = Uint128(
+            statistics
+                .total_minted
+                .u128()
+                .checked_add(amount.u128())
+                .ok_or_else(|| StdError::generic_err("total_minted statistic overflowed"))?,
+        );
+        self.set_statistics(&statistics);
+        Ok(())
+    }
+
+    /// Adds `amount` to `total_burned` and increments `total_swap_count`,
+    /// erroring instead of wrapping on overflow.
+    pub fn record_burn(&mut self, amount: Uint128) -> StdResult<()> {
+        let mut statistics = self.statistics();
+        statistics.total_burned = Uint128(
+            statistics
+                .total_burned
+                .u128()
+                .checked_add(amount.u128())
+                .ok_or_else(|| StdError::generic_err("total_burned statistic overflowed"))?,
+        );
+        statistics.total_swap_count = statistics
+            .total_swap_count
+            .checked_add(1)
+            .ok_or_else(|| StdError::generic_err("total_swap_count statistic overflowed"))?;
+        self.set_statistics(&statistics);
+        Ok(())
+    }
+
+    /// Adds `amount` to `total_refunded`, erroring instead of wrapping on
+    /// overflow.
+    pub fn record_refund(&mut self, amount: Uint128) -> StdResult<()> {
+        let mut statistics = self.statistics();
+        statistics.total_refunded = Uint128(
+            statistics
+                .total_refunded
+                .u128()
+                .checked_add(amount.u128())
+                .ok_or_else(|| StdError::generic_err("total_refunded statistic overflowed"))?,
+        );
+        self.set_statistics(&statistics);
+        Ok(())
+    }
+
+    /// Defaults to zero (no limit) when never configured. See
+    /// `HandleMsg::SetMintRateLimit`.
+    pub fn mint_limit_per_window(&self) -> Uint128 {
+        self.storage
+            .get(MINT_LIMIT_PER_WINDOW_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_mint_limit_per_window(&mut self, limit: Uint128) {
+        self.storage.set(
+            MINT_LIMIT_PER_WINDOW_KEY,
+            &bincode2::serialize(&limit.u128()).unwrap(),
+        );
+    }
+
+    /// Defaults to zero (no limit, since `mint_limit_per_window` also
+    /// defaults to zero) when never configured.
+    pub fn mint_window_blocks(&self) -> u64 {
+        self.storage
+            .get(MINT_WINDOW_BLOCKS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_mint_window_blocks(&mut self, blocks: u64) {
+        self.storage
+            .set(MINT_WINDOW_BLOCKS_KEY, &bincode2::serialize(&blocks).unwrap());
+    }
+
+    /// Defaults to `DEFAULT_MINT_THRESHOLD` when never configured. See
+    /// `HandleMsg::SetMintThreshold`.
+    pub fn mint_threshold(&self) -> u32 {
+        self.storage
+            .get(MINT_THRESHOLD_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or(DEFAULT_MINT_THRESHOLD)
+    }
+
+    pub fn set_mint_threshold(&mut self, threshold: u32) {
+        self.storage
+            .set(MINT_THRESHOLD_KEY, &bincode2::serialize(&threshold).unwrap());
+    }
+
+    /// Caps how many destinations a `BurnDestination::Multi` burn may list,
+    /// so a burn can't saddle the relayer with an unbounded number of tiny
+    /// payouts. Defaults to zero (no cap) when never configured.
+    pub fn max_destinations_per_burn(&self) -> u32 {
+        self.storage
+            .get(MAX_DESTINATIONS_PER_BURN_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_max_destinations_per_burn(&mut self, max: u32) {
+        self.storage.set(
+            MAX_DESTINATIONS_PER_BURN_KEY,
+            &bincode2::serialize(&max).unwrap(),
+        );
+    }
+
+    /// Defaults to false (no auto-generated viewing keys) when never
+    /// configured.
+    pub fn auto_vk_on_mint(&self) -> bool {
+        self.storage
+            .get(AUTO_VK_ON_MINT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn set_auto_vk_on_mint(&mut self, enabled: bool) {
+        self.storage
+            .set(AUTO_VK_ON_MINT_KEY, &bincode2::serialize(&enabled).unwrap());
+    }
+
+    /// The code hash last registered with the sXMR token via
+    /// `register_receive`, kept for reference after a code migration.
+    pub fn registered_code_hash(&self) -> Option<String> {
+        self.storage
+            .get(REGISTERED_CODE_HASH_KEY)
+            .and_then(|bytes| bincode2::deserialize::<String>(&bytes).ok())
+    }
+
+    pub fn set_registered_code_hash(&mut self, code_hash: &str) {
+        self.storage.set(
+            REGISTERED_CODE_HASH_KEY,
+            &bincode2::serialize(code_hash).unwrap(),
+        );
+    }
+
+    /// Defaults to false (no alignment check) when never configured.
+    pub fn enforce_decimal_alignment(&self) -> bool {
+        self.storage
+            .get(ENFORCE_DECIMAL_ALIGNMENT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn set_enforce_decimal_alignment(&mut self, enabled: bool) {
+        self.storage.set(
+            ENFORCE_DECIMAL_ALIGNMENT_KEY,
+            &bincode2::serialize(&enabled).unwrap(),
+        );
+    }
+
+    /// Defaults to `MONERO_DECIMALS`, i.e. sXMR mirrors XMR's own decimals
+    /// and every atomic amount aligns exactly, when never configured.
+    pub fn sxmr_decimals(&self) -> u8 {
+        self.storage
+            .get(SXMR_DECIMALS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u8>(&bytes).ok())
+            .unwrap_or(MONERO_DECIMALS as u8)
+    }
+
+    pub fn set_sxmr_decimals(&mut self, decimals: u8) {
+        self.storage
+            .set(SXMR_DECIMALS_KEY, &bincode2::serialize(&decimals).unwrap());
+    }
+
+    /// The total sXMR outstanding (minted minus burned), i.e. the XMR the
+    /// bridge's wallets are on the hook for if every holder redeemed at
+    /// once. Defaults to zero when never configured.
+    pub fn pending_liability(&self) -> Uint128 {
+        self.storage
+            .get(PENDING_LIABILITY_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_pending_liability(&mut self, liability: Uint128) {
+        self.storage.set(
+            PENDING_LIABILITY_KEY,
+            &bincode2::serialize(&liability.u128()).unwrap(),
+        );
+    }
+
+    /// Running total of deposits the bridge has written off via
+    /// `RevertMint` because the minted sXMR could not be forcibly recovered
+    /// (this token has no admin/forced-burn message, only self-burn).
+    /// Defaults to zero when never configured.
+    pub fn shortfall_debt(&self) -> Uint128 {
+        self.storage
+            .get(SHORTFALL_DEBT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn set_shortfall_debt(&mut self, debt: Uint128) {
+        self.storage.set(
+            SHORTFALL_DEBT_KEY,
+            &bincode2::serialize(&debt.u128()).unwrap(),
+        );
+    }
+
+    /// The address proposed via `ProposeAdmin`, awaiting its own
+    /// `AcceptAdmin` to take effect. `None` when no transfer is pending.
+    pub fn pending_admin(&self) -> Option<CanonicalAddr> {
+        self.storage
+            .get(PENDING_ADMIN_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    pub fn set_pending_admin(&mut self, pending_admin: Option<&CanonicalAddr>) {
+        self.storage.set(
+            PENDING_ADMIN_KEY,
+            &bincode2::serialize(&pending_admin).unwrap(),
+        );
+    }
+
+    /// Fee in basis points deducted from a burn's gross amount by
+    /// `burn_sxmr` (see `SwapDetails::fee_taken`), stamped onto each swap at
+    /// creation via `fee_bps_at_creation` so later rate changes don't alter
+    /// the record of what a past swap was actually charged. Defaults to zero
+    /// when never configured. Settable via `HandleMsg::SetFee`, capped there
+    /// at `MAX_FEE_BPS`.
+    pub fn fee_bps(&self) -> u16 {
+        self.storage
+            .get(FEE_BPS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u16>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.storage
+            .set(FEE_BPS_KEY, &bincode2::serialize(&fee_bps).unwrap());
+    }
+
+    /// A `[start_block, end_block]` window (inclusive) during which
+    /// `burn_sxmr` refuses new burns for scheduled Monero wallet
+    /// maintenance. `None` when no window is configured.
+    pub fn maintenance_window(&self) -> Option<(u64, u64)> {
+        self.storage
+            .get(MAINTENANCE_WINDOW_KEY)
+            .and_then(|bytes| bincode2::deserialize::<Option<(u64, u64)>>(&bytes).ok())
+            .flatten()
+    }
+
+    pub fn set_maintenance_window(&mut self, window: Option<(u64, u64)>) {
+        self.storage.set(
+            MAINTENANCE_WINDOW_KEY,
+            &bincode2::serialize(&window).unwrap(),
+        );
+    }
+
+    /// Defaults to false (the SNIP-20 `sender`/`from` are not compared) when
+    /// never configured.
+    pub fn require_sender_equals_from(&self) -> bool {
+        self.storage
+            .get(REQUIRE_SENDER_EQUALS_FROM_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn set_require_sender_equals_from(&mut self, enabled: bool) {
+        self.storage.set(
+            REQUIRE_SENDER_EQUALS_FROM_KEY,
+            &bincode2::serialize(&enabled).unwrap(),
+        );
+    }
+
+    /// Minimum number of blocks a `TimelockedAction` queued via
+    /// `PendingActionStore::queue` must wait before it can be executed.
+    /// Defaults to `0`, which makes the timelock a no-op, so it's opt-in.
+    pub fn timelock_blocks(&self) -> u64 {
+        self.storage
+            .get(TIMELOCK_BLOCKS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or(0)
+    }
+
+    pub fn set_timelock_blocks(&mut self, blocks: u64) {
+        self.storage
+            .set(TIMELOCK_BLOCKS_KEY, &bincode2::serialize(&blocks).unwrap());
+    }
+
+    /// Monotonic id source for `PendingActionStore`, mirroring
+    /// `next_swap_nonce`.
+    pub fn next_pending_action_id(&self) -> u32 {
+        self.storage
+            .get(NEXT_PENDING_ACTION_ID_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or(0)
+    }
+
+    pub fn set_next_pending_action_id(&mut self, id: u32) {
+        self.storage.set(
+            NEXT_PENDING_ACTION_ID_KEY,
+            &bincode2::serialize(&id).unwrap(),
+        );
+    }
+
+    /// The viewing key this contract presents to the sXMR token when
+    /// querying its own balance for `query_solvency_check`. `None` until
+    /// `SetBridgeViewingKey` is called.
+    pub fn bridge_viewing_key(&self) -> Option<String> {
+        self.storage
+            .get(BRIDGE_VIEWING_KEY_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    pub fn set_bridge_viewing_key(&mut self, key: &str) {
+        self.storage.set(
+            BRIDGE_VIEWING_KEY_KEY,
+            &bincode2::serialize(&key).unwrap(),
+        );
+    }
+
+    /// The viewing key `bridge_viewing_key` replaced, and the block height
+    /// after which it should no longer be accepted. Kept around during a
+    /// rotation so `query_sxmr_balance` has a fallback while the
+    /// `SetViewingKey` message pushed to the token is still in flight. See
+    /// `HandleMsg::SetBridgeViewingKey`.
+    pub fn prev_bridge_viewing_key(&self) -> Option<(String, u64)> {
+        self.storage
+            .get(PREV_BRIDGE_VIEWING_KEY_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    pub fn set_prev_bridge_viewing_key(&mut self, entry: Option<(String, u64)>) {
+        match entry {
+            Some(entry) => self.storage.set(
+                PREV_BRIDGE_VIEWING_KEY_KEY,
+                &bincode2::serialize(&entry).unwrap(),
+            ),
+            None => self.storage.remove(PREV_BRIDGE_VIEWING_KEY_KEY),
+        }
+    }
+}
+
+pub struct ReadonlyConfig<'a, S: Storage> {
+    storage: ReadonlyPrefixedStorage<'a, S>,
+}
+
+impl<'a, S: Storage> ReadonlyConfig<'a, S> {
+    pub fn from_storage(storage: &'a S) -> Self {
+        Self {
+            storage: ReadonlyPrefixedStorage::new(PREFIX_CONFIG, storage),
+        }
+    }
+
+    pub fn constants(&self) -> StdResult<Constants> {
+        let bytes = self
+            .storage
+            .get(CONSTANTS_KEY)
+            .ok_or_else(|| StdError::generic_err("config not initialized"))?;
+        bincode2::deserialize::<Constants>(&bytes)
+            .map_err(|_| StdError::generic_err("failed to deserialize constants"))
+    }
+
+    pub fn contract_status(&self) -> ContractStatusLevel {
+        self.storage
+            .get(CONTRACT_STATUS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or(ContractStatusLevel::Running)
+    }
+
+    pub fn minters(&self) -> Vec<CanonicalAddr> {
+        self.storage
+            .get(MINTERS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Defaults to `DEFAULT_MAX_MINTERS` when never configured.
+    pub fn max_minters(&self) -> u32 {
+        self.storage
+            .get(MAX_MINTERS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or(DEFAULT_MAX_MINTERS)
+    }
+
+    pub fn swap_counts(&self) -> SwapCounts {
+        self.storage
+            .get(SWAP_COUNTS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn fee_collector(&self) -> Option<CanonicalAddr> {
+        self.storage
+            .get(FEE_COLLECTOR_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    pub fn accumulated_fees(&self) -> Uint128 {
+        self.storage
+            .get(ACCUMULATED_FEES_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn fee_sweep_threshold(&self) -> Uint128 {
+        self.storage
+            .get(FEE_SWEEP_THRESHOLD_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn oracle(&self) -> Option<CanonicalAddr> {
+        self.storage
+            .get(ORACLE_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    pub fn monero_dust_limit(&self) -> Uint128 {
+        self.storage
+            .get(MONERO_DUST_LIMIT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    pub fn require_known_recipient(&self) -> bool {
+        self.storage
+            .get(REQUIRE_KNOWN_RECIPIENT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn enforce_monotonic_proof_order(&self) -> bool {
+        self.storage
+            .get(ENFORCE_MONOTONIC_PROOF_ORDER_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn last_proof_height(&self) -> u64 {
+        self.storage
+            .get(LAST_PROOF_HEIGHT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn include_token_info_in_result(&self) -> bool {
+        self.storage
+            .get(INCLUDE_TOKEN_INFO_IN_RESULT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn swap_ttl_seconds(&self) -> u64 {
+        self.storage
+            .get(SWAP_TTL_SECONDS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Defaults to zero (no cap) when never configured.
+    pub fn max_swap_amount(&self) -> Uint128 {
+        self.storage
+            .get(MAX_SWAP_AMOUNT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    /// Defaults to zero (no cap) when never configured.
+    pub fn max_destinations_per_burn(&self) -> u32 {
+        self.storage
+            .get(MAX_DESTINATIONS_PER_BURN_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// See `Config::min_mint_amount`.
+    pub fn min_mint_amount(&self) -> Uint128 {
+        self.storage
+            .get(MIN_MINT_AMOUNT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    /// See `Config::max_mint_amount`.
+    pub fn max_mint_amount(&self) -> Uint128 {
+        self.storage
+            .get(MAX_MINT_AMOUNT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    /// See `Config::unit_granularity`.
+    pub fn unit_granularity(&self) -> Uint128 {
+        self.storage
+            .get(UNIT_GRANULARITY_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    /// See `Config::statistics`.
+    pub fn statistics(&self) -> Statistics {
+        self.storage
+            .get(STATISTICS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<Statistics>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// See `Config::mint_limit_per_window`.
+    pub fn mint_limit_per_window(&self) -> Uint128 {
+        self.storage
+            .get(MINT_LIMIT_PER_WINDOW_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    /// See `Config::mint_window_blocks`.
+    pub fn mint_window_blocks(&self) -> u64 {
+        self.storage
+            .get(MINT_WINDOW_BLOCKS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// See `Config::mint_threshold`.
+    pub fn mint_threshold(&self) -> u32 {
+        self.storage
+            .get(MINT_THRESHOLD_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or(DEFAULT_MINT_THRESHOLD)
+    }
+
+    /// Defaults to false (no auto-generated viewing keys) when never
+    /// configured.
+    pub fn auto_vk_on_mint(&self) -> bool {
+        self.storage
+            .get(AUTO_VK_ON_MINT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    pub fn registered_code_hash(&self) -> Option<String> {
+        self.storage
+            .get(REGISTERED_CODE_HASH_KEY)
+            .and_then(|bytes| bincode2::deserialize::<String>(&bytes).ok())
+    }
+
+    /// Defaults to false (no alignment check) when never configured.
+    pub fn enforce_decimal_alignment(&self) -> bool {
+        self.storage
+            .get(ENFORCE_DECIMAL_ALIGNMENT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    /// Defaults to `MONERO_DECIMALS` when never configured.
+    pub fn sxmr_decimals(&self) -> u8 {
+        self.storage
+            .get(SXMR_DECIMALS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u8>(&bytes).ok())
+            .unwrap_or(MONERO_DECIMALS as u8)
+    }
+
+    pub fn pending_liability(&self) -> Uint128 {
+        self.storage
+            .get(PENDING_LIABILITY_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    /// Defaults to zero when never configured.
+    pub fn shortfall_debt(&self) -> Uint128 {
+        self.storage
+            .get(SHORTFALL_DEBT_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .map(Uint128)
+            .unwrap_or_default()
+    }
+
+    /// `None` when no admin transfer is pending.
+    pub fn pending_admin(&self) -> Option<CanonicalAddr> {
+        self.storage
+            .get(PENDING_ADMIN_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    /// Defaults to zero when never configured.
+    pub fn fee_bps(&self) -> u16 {
+        self.storage
+            .get(FEE_BPS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u16>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// A `[start_block, end_block]` window (inclusive) during which
+    /// `burn_sxmr` refuses new burns. `None` when no window is configured.
+    pub fn maintenance_window(&self) -> Option<(u64, u64)> {
+        self.storage
+            .get(MAINTENANCE_WINDOW_KEY)
+            .and_then(|bytes| bincode2::deserialize::<Option<(u64, u64)>>(&bytes).ok())
+            .flatten()
+    }
+
+    /// Defaults to false when never configured.
+    pub fn require_sender_equals_from(&self) -> bool {
+        self.storage
+            .get(REQUIRE_SENDER_EQUALS_FROM_KEY)
+            .and_then(|bytes| bincode2::deserialize::<bool>(&bytes).ok())
+            .unwrap_or(false)
+    }
+
+    /// Defaults to `0` (timelock disabled) when never configured.
+    pub fn timelock_blocks(&self) -> u64 {
+        self.storage
+            .get(TIMELOCK_BLOCKS_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+            .unwrap_or(0)
+    }
+
+    pub fn next_pending_action_id(&self) -> u32 {
+        self.storage
+            .get(NEXT_PENDING_ACTION_ID_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+            .unwrap_or(0)
+    }
+
+    /// `None` until `SetBridgeViewingKey` is called.
+    pub fn bridge_viewing_key(&self) -> Option<String> {
+        self.storage
+            .get(BRIDGE_VIEWING_KEY_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    /// See `Config::prev_bridge_viewing_key`.
+    pub fn prev_bridge_viewing_key(&self) -> Option<(String, u64)> {
+        self.storage
+            .get(PREV_BRIDGE_VIEWING_KEY_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+}
+
+/// True if minting `amount` piconero-equivalent units as sXMR (which has
+/// `sxmr_decimals` decimals, at most `MONERO_DECIMALS`) loses no precision —
+/// i.e. `amount` is an exact multiple of the scale between the two decimal
+/// counts. Always true when `sxmr_decimals >= MONERO_DECIMALS`.
+pub fn is_decimal_aligned(amount: Uint128, sxmr_decimals: u8) -> bool {
+    let sxmr_decimals = sxmr_decimals as u32;
+    if sxmr_decimals >= MONERO_DECIMALS {
+        return true;
+    }
+    let scale = 10u128.pow(MONERO_DECIMALS - sxmr_decimals);
+    amount.u128() % scale == 0
+}
+
+/// Converts an XMR atomic-unit (piconero, `MONERO_DECIMALS` decimals) amount
+/// to the equivalent sXMR amount at `sxmr_decimals`. Truncates toward zero
+/// when `sxmr_decimals < MONERO_DECIMALS`, matching `is_decimal_aligned`'s
+/// notion of what counts as a lossless conversion.
+pub fn scale_xmr_to_sxmr(xmr_atomic_amount: u64, sxmr_decimals: u8) -> Uint128 {
+    let sxmr_decimals = sxmr_decimals as u32;
+    let amount = xmr_atomic_amount as u128;
+    if sxmr_decimals >= MONERO_DECIMALS {
+        let scale = 10u128.pow(sxmr_decimals - MONERO_DECIMALS);
+        Uint128(amount.saturating_mul(scale))
+    } else {
+        let scale = 10u128.pow(MONERO_DECIMALS - sxmr_decimals);
+        Uint128(amount / scale)
+    }
+}
+
+/// Converts a net sXMR amount (`sxmr_decimals` decimals) to the equivalent
+/// XMR atomic-unit (piconero, `MONERO_DECIMALS` decimals) amount that
+/// should actually be paid out. Errors instead of truncating when
+/// `sxmr_decimals > MONERO_DECIMALS` and `amount` isn't an exact multiple
+/// of the scale between the two — that would otherwise silently drop the
+/// low digits the Monero payout can't express.
+pub fn scale_sxmr_to_xmr(amount: Uint128, sxmr_decimals: u8) -> StdResult<u64> {
+    let sxmr_decimals = sxmr_decimals as u32;
+    let amount = amount.u128();
+    let atomic = if sxmr_decimals >= MONERO_DECIMALS {
+        let scale = 10u128.pow(sxmr_decimals - MONERO_DECIMALS);
+        if amount % scale != 0 {
+            return Err(StdError::generic_err(
+                "amount does not divide evenly into XMR atomic units; would silently lose precision on payout",
+            ));
+        }
+        amount / scale
+    } else {
+        let scale = 10u128.pow(MONERO_DECIMALS - sxmr_decimals);
+        amount.saturating_mul(scale)
+    };
+    if atomic > u64::MAX as u128 {
+        return Err(StdError::generic_err(
+            "converted XMR atomic amount overflows u64",
+        ));
+    }
+    Ok(atomic as u64)
+}
+
+/// Rejects `amount` if it isn't an exact multiple of `granularity`, so
+/// bridged amounts stay clean multiples of a chosen unit (e.g. 10^9 atomic
+/// XMR) instead of accumulating sub-granularity dust. `granularity == 0`
+/// disables the check, matching this config's zero-means-disabled
+/// convention.
+pub fn validate_unit_granularity(amount: Uint128, granularity: Uint128) -> StdResult<()> {
+    let granularity = granularity.u128();
+    if granularity == 0 {
+        return Ok(());
+    }
+    let amount = amount.u128();
+    if amount % granularity == 0 {
+        return Ok(());
+    }
+    let floor = (amount / granularity) * granularity;
+    let ceil = floor + granularity;
+    Err(StdError::generic_err(format!(
+        "amount {} is not a multiple of the configured unit granularity of {}; nearest valid amounts are {} and {}",
+        amount, granularity, floor, ceil
+    )))
+}
+
+/// Formats a raw amount as a fixed-point decimal string with `decimals`
+/// fractional digits, without going through floating point (and so without
+/// losing precision for amounts near `u128::MAX`).
+pub fn format_units(amount: Uint128, decimals: u32) -> String {
+    let raw = amount.u128();
+    let base = 10u128.pow(decimals);
+    let whole = raw / base;
+    let fraction = raw % base;
+    format!("{}.{:0width$}", whole, fraction, width = decimals as usize)
+}
+
+/// True once `address` has set a viewing key, the cheapest signal that it
+/// has interacted with the bridge before.
+pub fn is_known_recipient<S: Storage>(storage: &S, address: &CanonicalAddr) -> bool {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_VIEWING_KEY, storage);
+    store.get(address.as_slice()).is_some()
+}
+
+/// An oracle's attestation that it independently verified a Monero deposit,
+/// recorded separately from the minter that actually submits the mint.
+pub struct OracleAttestationsStore {}
+
+impl OracleAttestationsStore {
+    pub fn save<S: Storage>(storage: &mut S, tx_id: &str) -> StdResult<()> {
+        let mut store = PrefixedStorage::new(PREFIX_ORACLE_ATTESTATIONS, storage);
+        store.set(tx_id.as_bytes(), &[1u8]);
+        Ok(())
+    }
+
+    pub fn has_attestation<S: Storage>(storage: &S, tx_id: &str) -> bool {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_ORACLE_ATTESTATIONS, storage);
+        store.get(tx_id.as_bytes()).is_some()
+    }
+}
+
+/// Per-caller destinations that have already passed format validation, so a
+/// repeat burn to the same address can skip re-validating it.
+pub struct WhitelistedDestinationsStore {}
+
+impl WhitelistedDestinationsStore {
+    fn key(owner: &CanonicalAddr, to_monero_address: &str) -> Vec<u8> {
+        [owner.as_slice(), to_monero_address.as_bytes()].concat()
+    }
+
+    pub fn whitelist<S: Storage>(storage: &mut S, owner: &CanonicalAddr, to_monero_address: &str) {
+        let mut store = PrefixedStorage::new(PREFIX_WHITELISTED_DESTINATIONS, storage);
+        store.set(&Self::key(owner, to_monero_address), &[1u8]);
+    }
+
+    pub fn is_whitelisted<S: Storage>(
+        storage: &S,
+        owner: &CanonicalAddr,
+        to_monero_address: &str,
+    ) -> bool {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_WHITELISTED_DESTINATIONS, storage);
+        store.get(&Self::key(owner, to_monero_address)).is_some()
+    }
+}
+
+/// Destinations blocked globally (e.g. by compliance action), checked for
+/// every burn regardless of whether the caller whitelisted it.
+pub struct BlockedDestinationsStore {}
+
+impl BlockedDestinationsStore {
+    pub fn block<S: Storage>(storage: &mut S, to_monero_address: &str) {
+        let mut store = PrefixedStorage::new(PREFIX_BLOCKED_DESTINATIONS, storage);
+        store.set(to_monero_address.as_bytes(), &[1u8]);
+    }
+
+    pub fn is_blocked<S: Storage>(storage: &S, to_monero_address: &str) -> bool {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_BLOCKED_DESTINATIONS, storage);
+        store.get(to_monero_address.as_bytes()).is_some()
+    }
+}
+
+/// Secret addresses blocked from initiating a burn (e.g. by compliance
+/// action). Mirrors `BlockedDestinationsStore`; unlike it, nothing currently
+/// consults this during `burn_sxmr`, so it's exposed for transparency and
+/// off-chain enforcement ahead of that check being wired in.
+pub struct BlockedSendersStore {}
+
+impl BlockedSendersStore {
+    pub fn block<S: Storage>(storage: &mut S, sender: &CanonicalAddr) {
+        let mut store = PrefixedStorage::new(PREFIX_BLOCKED_SENDERS, storage);
+        store.set(sender.as_slice(), &[1u8]);
+    }
+
+    pub fn is_blocked<S: Storage>(storage: &S, sender: &CanonicalAddr) -> bool {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_BLOCKED_SENDERS, storage);
+        store.get(sender.as_slice()).is_some()
+    }
+}
+
+/// An admin-sensitive change queued behind `Config::timelock_blocks`. See
+/// `queue_timelocked_action`/`execute_pending_action`/`cancel_pending_action`
+/// in `contract.rs`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TimelockedAction {
+    SetMinters { minters: Vec<CanonicalAddr> },
+    /// Repoints the bridge at a different sXMR token contract (a "token
+    /// swap"), e.g. after a code migration leaves the old address behind.
+    SetSxmrToken {
+        address: CanonicalAddr,
+        code_hash: String,
+        /// The new token's decimal scale, recorded so `execute_pending_action`
+        /// can populate `TokenInfo::decimals` with it instead of carrying
+        /// over the outgoing token's scale.
+        decimals: u8,
+    },
+    /// Replaces the list of Monero wallets the bridge monitors for deposits
+    /// and pays swaps out from (a "wallet rotation").
+    SetMoneroWallets { wallets: Vec<String> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAction {
+    pub id: u32,
+    pub action: TimelockedAction,
+    /// The block height at which `execute_pending_action` will accept this.
+    pub ready_at_block: u64,
+}
+
+/// Queue of `TimelockedAction`s awaiting their `ready_at_block`, keyed by a
+/// monotonic id (`Config::next_pending_action_id`). An executed or
+/// cancelled action is removed rather than flagged, unlike
+/// `MoneroProofsStore`'s tombstones — a pending action isn't a replay target,
+/// so there's nothing worth remembering once it's gone.
+pub struct PendingActionStore {}
+
+impl PendingActionStore {
+    fn key(id: u32) -> [u8; 4] {
+        id.to_be_bytes()
+    }
+
+    pub fn queue<S: Storage>(
+        storage: &mut S,
+        action: TimelockedAction,
+        ready_at_block: u64,
+    ) -> StdResult<u32> {
+        let id = {
+            let mut config = Config::from_storage(storage);
+            let id = config.next_pending_action_id();
+            config.set_next_pending_action_id(id + 1);
+            id
+        };
+        let pending = PendingAction {
+            id,
+            action,
+            ready_at_block,
+        };
+        let mut store = PrefixedStorage::new(PREFIX_PENDING_ACTIONS, storage);
+        store.set(
+            &Self::key(id),
+            &bincode2::serialize(&pending)
+                .map_err(|_| StdError::generic_err("failed to serialize pending action"))?,
+        );
+        Ok(id)
+    }
+
+    pub fn get<S: Storage>(storage: &S, id: u32) -> Option<PendingAction> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_PENDING_ACTIONS, storage);
+        store
+            .get(&Self::key(id))
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+    }
+
+    pub fn remove<S: Storage>(storage: &mut S, id: u32) {
+        let mut store = PrefixedStorage::new(PREFIX_PENDING_ACTIONS, storage);
+        store.remove(&Self::key(id));
+    }
+
+    /// Lists every currently-queued action, for the `PendingActions` query.
+    /// Queued actions are rare and short-lived, so scanning the full id
+    /// range (rather than maintaining a paged append-store) is acceptable
+    /// here, unlike the swap/proof stores.
+    pub fn all<S: Storage>(storage: &S, next_id: u32) -> Vec<PendingAction> {
+        (0..next_id)
+            .filter_map(|id| Self::get(storage, id))
+            .collect()
+    }
+}
+
+/// A swap's lifecycle state, tracked independently of the legacy `resolved`
+/// flag. `resolved` governs whether `sweep_expired`/`AttachPayoutTx` still
+/// treat the swap as outstanding; `status` is the operator-facing record of
+/// what actually happened to it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum SwapStatus {
+    Pending,
+    Completed { monero_tx_id: String },
+    Refunded,
+    /// Rolled into another swap via `ConsolidateSwaps` rather than paid out
+    /// or refunded on its own; `into_nonce` is the swap it was merged into.
+    Consolidated { into_nonce: u32 },
+    /// Swept by `SweepExpiredSwaps` after its TTL passed with no relayer
+    /// payout; the sXMR was re-minted to the owner, same as `Refunded`, just
+    /// via a different trigger (a timeout instead of an explicit refund).
+    Expired,
+    /// Set by `HandleMsg::MarkSwapProcessing` once a relayer has claimed the
+    /// swap to pay it out, so a racing `HandleMsg::CancelSwap` can't refund
+    /// it out from under an in-flight Monero payout. The relayer still
+    /// calls `CompleteSwap` (or `RefundSwap` if the payout fails)
+    /// afterward; this is only the in-between window.
+    Processing,
+}
+
+impl Default for SwapStatus {
+    fn default() -> Self {
+        SwapStatus::Pending
+    }
+}
+
+/// The `SwapsByStatus` query's wire-level status selector: a caller asking
+/// "give me all `Completed` swaps" has no `monero_tx_id` to supply, so this
+/// mirrors `SwapStatus`'s variants without their associated data.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapStatusFilter {
+    Pending,
+    Completed,
+    Refunded,
+    Consolidated,
+    Expired,
+    Processing,
+}
+
+impl SwapStatusFilter {
+    fn tag(self) -> u8 {
+        match self {
+            SwapStatusFilter::Pending => 0,
+            SwapStatusFilter::Completed => 1,
+            SwapStatusFilter::Refunded => 2,
+            SwapStatusFilter::Consolidated => 3,
+            SwapStatusFilter::Expired => 4,
+            SwapStatusFilter::Processing => 5,
+        }
+    }
+}
+
+/// A pending or resolved Secret-to-Monero swap (a burn of sXMR destined for a
+/// Monero payout).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapDetails {
+    pub from_secret_address: CanonicalAddr,
+    pub to_monero_address: String,
+    /// An integrated-address payment id recorded against this swap when the
+    /// burn supplied one via `BurnDestination::SingleWithPaymentId` (rejected
+    /// outright if `to_monero_address` is itself an integrated address,
+    /// since that already embeds a payment id). `None` otherwise.
+    pub payment_id: Option<String>,
+    /// An optional client-supplied note forwarded into the Monero
+    /// transaction's payout, set at burn time via `BurnDestination::WithMemo`'s
+    /// `memo` field and capped at 256 bytes (see `burn_sxmr`). `None` for a swap
+    /// created before this field existed, via `#[serde(default)]`.
+    #[serde(default)]
+    pub memo: Option<String>,
+    /// The net amount, after `fee_taken` is deducted from the gross amount
+    /// the user sent, that the Monero payout should actually deliver.
+    pub amount: Uint128,
+    /// `amount` converted to XMR atomic units (piconero) at the
+    /// `sxmr_decimals` in effect when this swap was created, i.e. what the
+    /// off-chain payout should actually send. See `scale_sxmr_to_xmr`.
+    /// `0` for a swap created before this field existed, via
+    /// `#[serde(default)]`.
+    #[serde(default)]
+    pub xmr_atomic_amount: u64,
+    /// The fee deducted from the burn's gross amount at `fee_bps_at_creation`,
+    /// in the same sXMR base units as `amount`. Zero when `fee_bps_at_creation`
+    /// was zero.
+    pub fee_taken: Uint128,
+    /// The Monero payout transaction hash, once known. Normally attached by
+    /// whatever flow actually resolves the swap; `AttachPayoutTx` exists to
+    /// backfill it retroactively when a relayer resolved the swap without
+    /// one (e.g. an older relayer version).
+    pub monero_tx_id: Option<String>,
+    /// Optional split of the burned net amount across several Monero
+    /// destinations. Empty for a plain single-destination swap, in which
+    /// case `to_monero_address`/`amount` above are authoritative.
+    pub destinations: Vec<(String, Uint128)>,
+    /// A short client-supplied tag ("rent payment"), set after the fact via
+    /// `SetSwapLabel`. `None` until the owner labels it.
+    pub label: Option<String>,
+    /// Block time the burn was recorded, used to judge TTL expiry.
+    pub created_at: u64,
+    /// Set once the swap leaves the pending state (fulfilled by an off-chain
+    /// payout or swept back as expired), so it isn't swept twice.
+    pub resolved: bool,
+    /// The authoritative nonce assigned by `SwapDetailsStore::save` from the
+    /// monotonic `next_swap_nonce` counter. Ordinarily equal to this swap's
+    /// position in the append store, but kept as an explicit field so a
+    /// corrupted or misreported append length can't cause two swaps to be
+    /// assigned the same external nonce. Callers constructing a `SwapDetails`
+    /// before it's saved should set this to `0`; `save` overwrites it.
+    pub nonce: u32,
+    /// A stable, hash-derived identifier (hex SHA-256 of the bridge's
+    /// `prng_seed` and the nonce at assignment time), set by
+    /// `SwapDetailsStore::save`. Unlike `nonce` — this swap's position in the
+    /// append store, which would shift if the store were ever compacted —
+    /// `swap_id` is safe to hand out as a durable external reference. Callers
+    /// constructing a `SwapDetails` before it's saved should leave this
+    /// empty; `save` overwrites it.
+    pub swap_id: String,
+    /// The fee, in basis points, in effect when this swap was created (see
+    /// `Config::fee_bps`). Recorded so a later rate change doesn't change
+    /// how a past swap's net amount is explained to its owner. Callers
+    /// constructing a `SwapDetails` before it's saved should set this to
+    /// `0`; `save` overwrites it.
+    pub fee_bps_at_creation: u16,
+    /// `sxmr_decimals` in effect when this swap was created (see
+    /// `Config::sxmr_decimals`), i.e. the scale used to interpret `amount`.
+    /// Recorded for the same reason as `fee_bps_at_creation`. Callers
+    /// constructing a `SwapDetails` before it's saved should set this to
+    /// `0`; `save` overwrites it.
+    pub scale_at_creation: u8,
+    /// The swap's operator-facing lifecycle state. `Pending` at creation;
+    /// flipped to `Completed` by `CompleteSwap` once the XMR payout is
+    /// confirmed sent, or `Refunded` if the sXMR was returned instead. See
+    /// `SwapStatus`.
+    #[serde(default)]
+    pub status: SwapStatus,
+}
+
+impl SwapDetails {
+    /// A hex-encoded SHA-256 digest of the swap's fields, handed to the user
+    /// at burn time and re-derivable for client-side integrity checks.
+    pub fn receipt_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(self.from_secret_address.as_slice());
+        hasher.update(self.to_monero_address.as_bytes());
+        hasher.update(&self.amount.u128().to_be_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// A single-byte tag for each `SwapStatus` variant, ignoring its associated
+/// data (e.g. `Completed`'s `monero_tx_id`) — `SwapsByStatus` only needs to
+/// know which bucket a swap is in, not the bucket's payload.
+fn status_tag(status: &SwapStatus) -> u8 {
+    match status {
+        SwapStatus::Pending => 0,
+        SwapStatus::Completed { .. } => 1,
+        SwapStatus::Refunded => 2,
+        SwapStatus::Consolidated { .. } => 3,
+        SwapStatus::Expired => 4,
+        SwapStatus::Processing => 5,
+    }
+}
+
+/// A per-status index of swap nonces, maintained alongside `SwapDetails` so
+/// `SwapsByStatus` can page one status's swaps directly instead of scanning
+/// every swap ever recorded. Keys are `[status_tag, nonce_be_bytes]`, so
+/// ranging over a single tag's byte prefix yields that status's nonces in
+/// ascending order for free.
+struct StatusIndex;
+
+impl StatusIndex {
+    fn key(tag: u8, nonce: u32) -> Vec<u8> {
+        [&[tag][..], &nonce.to_be_bytes()[..]].concat()
+    }
+
+    fn add<S: Storage>(storage: &mut S, status: &SwapStatus, nonce: u32) {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_STATUS_INDEX, storage);
+        store.set(&Self::key(status_tag(status), nonce), &[1]);
+    }
+
+    fn remove<S: Storage>(storage: &mut S, status: &SwapStatus, nonce: u32) {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_STATUS_INDEX, storage);
+        store.remove(&Self::key(status_tag(status), nonce));
+    }
+
+    /// Moves `nonce` from `from`'s list to `to`'s list; a no-op if `from`
+    /// and `to` tag the same bucket.
+    fn transition<S: Storage>(storage: &mut S, from: &SwapStatus, to: &SwapStatus, nonce: u32) {
+        if status_tag(from) == status_tag(to) {
+            return;
+        }
+        Self::remove(storage, from, nonce);
+        Self::add(storage, to, nonce);
+    }
+
+    fn page<S: Storage>(storage: &S, tag: u8, page: u32, page_size: u32) -> Vec<u32> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_STATUS_INDEX, storage);
+        let start = [tag];
+        let end = [tag + 1];
+        let skip = page as u64 * page_size as u64;
+        store
+            .range(Some(&start), Some(&end), Order::Ascending)
+            .skip(skip as usize)
+            .take(page_size as usize)
+            .filter_map(|(key, _)| {
+                let nonce_bytes: [u8; 4] = key.get(1..5)?.try_into().ok()?;
+                Some(u32::from_be_bytes(nonce_bytes))
+            })
+            .collect()
+    }
+}
+
+pub struct SwapDetailsStore {}
+
+impl SwapDetailsStore {
+    /// Appends a new swap and returns its nonce, drawn from the monotonic
+    /// `next_swap_nonce` counter rather than the append store's length, so a
+    /// partially corrupted store can't misreport its length and hand out a
+    /// colliding nonce.
+    pub fn save<S: Storage>(storage: &mut S, swap: &SwapDetails) -> StdResult<u32> {
+        let nonce = {
+            let mut config = Config::from_storage(storage);
+            // Bootstraps contracts upgraded from before this counter existed
+            // by picking up where the append store's length left off.
+            let nonce = config
+                .next_swap_nonce()
+                .unwrap_or_else(|| Self::current_len(storage));
+            config.set_next_swap_nonce(nonce + 1);
+            nonce
+        };
+
+        let (prng_seed, fee_bps, sxmr_decimals) = {
+            let config = Config::from_storage(storage);
+            (config.constants()?.prng_seed, config.fee_bps(), config.sxmr_decimals())
+        };
+
+        let mut swap = swap.clone();
+        swap.nonce = nonce;
+        swap.swap_id = Self::derive_swap_id(&prng_seed, nonce);
+        swap.fee_bps_at_creation = fee_bps;
+        swap.scale_at_creation = sxmr_decimals;
+
+        SwapIdIndexStore::save(storage, &swap.swap_id, nonce)?;
+        StatusIndex::add(storage, &swap.status, nonce);
+
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::attach_or_create(&mut store)?;
+        store.push(&swap)?;
+        Ok(nonce)
+    }
+
+    /// Hex SHA-256 of the bridge's `prng_seed` and the nonce, giving a
+    /// UUID-like identifier that's stable across append-store compaction
+    /// (unlike the nonce, which is a position).
+    fn derive_swap_id(prng_seed: &[u8], nonce: u32) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(prng_seed);
+        hasher.update(&nonce.to_be_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    fn current_len<S: Storage>(storage: &S) -> u32 {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        match AppendStore::<SwapDetails, _>::attach(&store) {
+            Some(Ok(store)) => store.len(),
+            _ => 0,
+        }
+    }
+
+    /// Overwrites the label of the swap at `nonce` owned by `owner`.
+    pub fn set_label<S: Storage>(
+        storage: &mut S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+        label: String,
+    ) -> StdResult<()> {
+        let label = sxmr_token::memo::validate_and_normalize_memo(&label, 0, MAX_SWAP_LABEL_LEN)?;
+
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+        let mut swap = store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))?;
+        if &swap.from_secret_address != owner {
+            return Err(StdError::generic_err("swap not found"));
+        }
+        swap.label = Some(label);
+        store.set_at(nonce, &swap)
+    }
+
+    /// Attaches `monero_tx_id` to the swap at `nonce` owned by `owner`,
+    /// marking it resolved if it wasn't already. Returns whether this call
+    /// is what first resolved it, so the caller can decide whether to bump
+    /// `SwapCounts::fulfilled`. Refuses to overwrite an existing hash unless
+    /// `force` is set (reserved for admin callers).
+    pub fn attach_payout_tx<S: Storage>(
+        storage: &mut S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+        monero_tx_id: String,
+        force: bool,
+    ) -> StdResult<bool> {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+        let mut swap = store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))?;
+        if &swap.from_secret_address != owner {
+            return Err(StdError::generic_err("swap not found"));
+        }
+        if swap.monero_tx_id.is_some() && !force {
+            return Err(StdError::generic_err(
+                "swap already has a payout tx hash attached; only the admin may overwrite it",
+            ));
+        }
+        let newly_resolved = !swap.resolved;
+        swap.monero_tx_id = Some(monero_tx_id);
+        swap.resolved = true;
+        store.set_at(nonce, &swap)?;
+        Ok(newly_resolved)
+    }
+
+    /// Flips the swap at `nonce` owned by `owner` to `SwapStatus::Completed`,
+    /// recording `monero_tx_id`. Errors if the swap is already `Completed`.
+    /// Returns whether this call is what first resolved the swap, so the
+    /// caller can decide whether to bump `SwapCounts::fulfilled`.
+    pub fn complete_swap<S: Storage>(
+        storage: &mut S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+        monero_tx_id: String,
+    ) -> StdResult<bool> {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+        let mut swap = store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))?;
+        if &swap.from_secret_address != owner {
+            return Err(StdError::generic_err("swap not found"));
+        }
+        if let SwapStatus::Completed { .. } = swap.status {
+            return Err(StdError::generic_err("swap is already completed"));
+        }
+        let newly_resolved = !swap.resolved;
+        let old_status = swap.status.clone();
+        swap.status = SwapStatus::Completed {
+            monero_tx_id: monero_tx_id.clone(),
+        };
+        swap.monero_tx_id = Some(monero_tx_id);
+        swap.resolved = true;
+        store.set_at(nonce, &swap)?;
+        StatusIndex::transition(storage, &old_status, &swap.status, nonce);
+        Ok(newly_resolved)
+    }
+
+    /// Flips the swap at `nonce` owned by `owner` to `SwapStatus::Refunded`,
+    /// so the caller can re-mint `SwapDetails.amount` back to the owner.
+    /// Errors if the swap is already `Completed` or already `Refunded`.
+    pub fn refund_swap<S: Storage>(
+        storage: &mut S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+    ) -> StdResult<SwapDetails> {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+        let mut swap = store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))?;
+        if &swap.from_secret_address != owner {
+            return Err(StdError::generic_err("swap not found"));
+        }
+        match swap.status {
+            SwapStatus::Completed { .. } => {
+                return Err(StdError::generic_err("swap is already completed; cannot refund"))
+            }
+            SwapStatus::Refunded => {
+                return Err(StdError::generic_err("swap has already been refunded"))
+            }
+            SwapStatus::Consolidated { .. } => {
+                return Err(StdError::generic_err(
+                    "swap has already been consolidated into another swap",
+                ))
+            }
+            SwapStatus::Expired => {
+                return Err(StdError::generic_err(
+                    "swap has already expired and been refunded",
+                ))
+            }
+            // A relayer/admin claimed this swap via `MarkSwapProcessing` but
+            // apparently couldn't complete the payout; they're the only
+            // ones who could have set `Processing` in the first place, so
+            // they're trusted to unwind it here.
+            SwapStatus::Processing | SwapStatus::Pending => {}
+        }
+        let old_status = swap.status.clone();
+        swap.status = SwapStatus::Refunded;
+        swap.resolved = true;
+        store.set_at(nonce, &swap)?;
+        StatusIndex::transition(storage, &old_status, &swap.status, nonce);
+        Ok(swap)
+    }
+
+    /// Flips the swap at `nonce` owned by `owner` to `SwapStatus::Processing`.
+    /// Errors unless it's currently `Pending`. See `SwapStatus::Processing`.
+    pub fn mark_processing<S: Storage>(
+        storage: &mut S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+    ) -> StdResult<()> {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+        let mut swap = store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))?;
+        if &swap.from_secret_address != owner {
+            return Err(StdError::generic_err("swap not found"));
+        }
+        if swap.status != SwapStatus::Pending {
+            return Err(StdError::generic_err(
+                "swap must be pending to be marked processing",
+            ));
+        }
+        let old_status = swap.status.clone();
+        swap.status = SwapStatus::Processing;
+        store.set_at(nonce, &swap)?;
+        StatusIndex::transition(storage, &old_status, &swap.status, nonce);
+        Ok(())
+    }
+
+    /// Flips the caller-owned swap at `nonce` to `SwapStatus::Refunded`, the
+    /// same terminal state `refund_swap` produces, so it can be re-minted
+    /// back to `owner`. Unlike `refund_swap` this is meant for the owner
+    /// cancelling their own swap, so it's stricter: only `Pending` is
+    /// accepted, which also blocks `Processing` — a swap a relayer has
+    /// already claimed for payout can't be cancelled out from under it.
+    pub fn cancel_swap<S: Storage>(
+        storage: &mut S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+    ) -> StdResult<SwapDetails> {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+        let mut swap = store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))?;
+        if &swap.from_secret_address != owner {
+            return Err(StdError::generic_err("swap not found"));
+        }
+        match swap.status {
+            SwapStatus::Pending => {}
+            SwapStatus::Processing => {
+                return Err(StdError::generic_err(
+                    "swap is already being processed by a relayer; it cannot be cancelled",
+                ))
+            }
+            SwapStatus::Completed { .. } => {
+                return Err(StdError::generic_err("swap is already completed; cannot cancel"))
+            }
+            SwapStatus::Refunded => {
+                return Err(StdError::generic_err("swap has already been refunded"))
+            }
+            SwapStatus::Consolidated { .. } => {
+                return Err(StdError::generic_err(
+                    "swap has already been consolidated into another swap",
+                ))
+            }
+            SwapStatus::Expired => {
+                return Err(StdError::generic_err(
+                    "swap has already expired and been refunded",
+                ))
+            }
+        }
+        let old_status = swap.status.clone();
+        swap.status = SwapStatus::Refunded;
+        swap.resolved = true;
+        store.set_at(nonce, &swap)?;
+        StatusIndex::transition(storage, &old_status, &swap.status, nonce);
+        Ok(swap)
+    }
+
+    /// Merges the `Pending` swaps at `nonces` (all owned by `owner` and
+    /// targeting `to_monero_address`) into one new swap for their summed
+    /// `amount`, and returns it. The merged-away swaps are flipped to
+    /// `SwapStatus::Consolidated`, pointing at the new swap's nonce. Errors
+    /// if `nonces` is empty, any swap isn't owned by `owner`, isn't
+    /// `Pending`, or doesn't target `to_monero_address`.
+    pub fn consolidate<S: Storage>(
+        storage: &mut S,
+        owner: &CanonicalAddr,
+        nonces: &[u32],
+        to_monero_address: String,
+        now: u64,
+    ) -> StdResult<SwapDetails> {
+        if nonces.is_empty() {
+            return Err(StdError::generic_err(
+                "must list at least one swap to consolidate",
+            ));
+        }
+
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+
+        let mut swaps = Vec::with_capacity(nonces.len());
+        for &nonce in nonces {
+            let swap = store
+                .get_at(nonce)
+                .map_err(|_| StdError::generic_err("swap not found"))?;
+            if &swap.from_secret_address != owner {
+                return Err(StdError::generic_err("swap not found"));
+            }
+            if swap.status != SwapStatus::Pending {
+                return Err(StdError::generic_err(
+                    "only a pending swap can be consolidated",
+                ));
+            }
+            if swap.to_monero_address != to_monero_address {
+                return Err(StdError::generic_err(
+                    "all consolidated swaps must target the same destination",
+                ));
+            }
+            swaps.push(swap);
+        }
+
+        let total = swaps
+            .iter()
+            .try_fold(0u128, |acc, swap| acc.checked_add(swap.amount.u128()))
+            .ok_or_else(|| StdError::generic_err("consolidated amount overflows"))?;
+        let xmr_atomic_total = swaps
+            .iter()
+            .try_fold(0u64, |acc, swap| acc.checked_add(swap.xmr_atomic_amount))
+            .ok_or_else(|| StdError::generic_err("consolidated XMR amount overflows"))?;
+
+        let consolidated = SwapDetails {
+            from_secret_address: owner.clone(),
+            to_monero_address,
+            payment_id: None,
+            memo: None,
+            amount: Uint128(total),
+            xmr_atomic_amount: xmr_atomic_total,
+            fee_taken: Uint128::zero(),
+            monero_tx_id: None,
+            destinations: vec![],
+            label: None,
+            created_at: now,
+            resolved: false,
+            nonce: 0,
+            swap_id: String::new(),
+            fee_bps_at_creation: 0,
+            scale_at_creation: 0,
+            status: SwapStatus::Pending,
+        };
+        drop(store);
+        let new_nonce = Self::save(storage, &consolidated)?;
+
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+        let mut transitions = Vec::with_capacity(nonces.len());
+        for &nonce in nonces {
+            let mut swap = store.get_at(nonce)?;
+            let old_status = swap.status.clone();
+            swap.status = SwapStatus::Consolidated { into_nonce: new_nonce };
+            swap.resolved = true;
+            store.set_at(nonce, &swap)?;
+            transitions.push((old_status, swap.status, nonce));
+        }
+        drop(store);
+        for (old_status, new_status, nonce) in transitions {
+            StatusIndex::transition(storage, &old_status, &new_status, nonce);
+        }
+
+        ReadonlySwapDetailsStore::fetch_by_nonce(storage, new_nonce)
+    }
+
+    /// Scans up to `limit` unresolved swaps at least `ttl_seconds` old and
+    /// marks them resolved, returning each one alongside its nonce so the
+    /// caller can re-mint the owner's sXMR. Does nothing when `ttl_seconds`
+    /// is zero (TTL enforcement disabled).
+    pub fn sweep_expired<S: Storage>(
+        storage: &mut S,
+        now: u64,
+        ttl_seconds: u64,
+        limit: u32,
+    ) -> StdResult<Vec<(u32, SwapDetails)>> {
+        if ttl_seconds == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let mut store = AppendStoreMut::<SwapDetails, _>::attach_or_create(&mut store)?;
+
+        let mut swept = vec![];
+        for nonce in 0..store.len() {
+            if swept.len() as u32 >= limit {
+                break;
+            }
+            let swap = store.get_at(nonce)?;
+            if swap.resolved || now < swap.created_at.saturating_add(ttl_seconds) {
+                continue;
+            }
+            let mut expired = swap.clone();
+            expired.resolved = true;
+            expired.status = SwapStatus::Expired;
+            store.set_at(nonce, &expired)?;
+            swept.push((nonce, swap));
+        }
+        drop(store);
+        for (nonce, swap) in &swept {
+            StatusIndex::transition(storage, &swap.status, &SwapStatus::Expired, *nonce);
+        }
+        Ok(swept)
+    }
+}
+
+pub struct ReadonlySwapDetailsStore {}
+
+impl ReadonlySwapDetailsStore {
+    pub fn fetch_swap_details<S: Storage>(
+        storage: &S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+    ) -> StdResult<SwapDetails> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let store = AppendStore::<SwapDetails, _>::attach(&store)
+            .ok_or_else(|| StdError::generic_err("no swaps recorded yet"))??;
+        let swap = store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))?;
+        if &swap.from_secret_address != owner {
+            return Err(crate::error::swap_does_not_belong_to_caller());
+        }
+        Ok(swap)
+    }
+
+    /// Fetches a swap by nonce without checking ownership, for admin
+    /// lookups that have already authenticated some other way.
+    pub fn fetch_by_nonce<S: Storage>(storage: &S, nonce: u32) -> StdResult<SwapDetails> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let store = AppendStore::<SwapDetails, _>::attach(&store)
+            .ok_or_else(|| StdError::generic_err("no swaps recorded yet"))??;
+        store
+            .get_at(nonce)
+            .map_err(|_| StdError::generic_err("swap not found"))
+    }
+
+    /// Resolves a swap from its `swap_id`, without checking ownership, via
+    /// `SwapIdIndexStore`.
+    pub fn fetch_by_swap_id<S: Storage>(storage: &S, swap_id: &str) -> StdResult<SwapDetails> {
+        let nonce = SwapIdIndexStore::lookup(storage, swap_id)
+            .ok_or_else(|| StdError::generic_err("swap not found"))?;
+        Self::fetch_by_nonce(storage, nonce)
+    }
+
+    /// Lists `owner`'s swaps newest-first, applying `page`/`page_size` over
+    /// that ordering. Returns an empty vec rather than an error when the
+    /// store doesn't exist yet (no swaps have ever been recorded).
+    pub fn fetch_user_swaps<S: Storage>(
+        storage: &S,
+        owner: &CanonicalAddr,
+        page: u32,
+        page_size: u32,
+    ) -> StdResult<Vec<SwapDetails>> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let store = match AppendStore::<SwapDetails, _>::attach(&store) {
+            Some(store) => store?,
+            None => return Ok(vec![]),
+        };
+        let skip = page as u64 * page_size as u64;
+        Ok(store
+            .iter()
+            .rev()
+            .filter_map(|swap| swap.ok())
+            .filter(|swap| &swap.from_secret_address == owner)
+            .skip(skip as usize)
+            .take(page_size as usize)
+            .collect())
+    }
+
+    /// Lists every swap still `SwapStatus::Pending` across all owners,
+    /// oldest-first, applying `page`/`page_size` over that ordering. For
+    /// operators polling the whole book rather than one user's swaps (see
+    /// `fetch_user_swaps`). Returns an empty vec rather than an error when
+    /// the store doesn't exist yet.
+    pub fn fetch_all_pending<S: Storage>(
+        storage: &S,
+        page: u32,
+        page_size: u32,
+    ) -> StdResult<Vec<SwapDetails>> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let store = match AppendStore::<SwapDetails, _>::attach(&store) {
+            Some(store) => store?,
+            None => return Ok(vec![]),
+        };
+        let skip = page as u64 * page_size as u64;
+        Ok(store
+            .iter()
+            .filter_map(|swap| swap.ok())
+            .filter(|swap| matches!(swap.status, SwapStatus::Pending))
+            .skip(skip as usize)
+            .take(page_size as usize)
+            .collect())
+    }
+
+    /// Lists swaps from `start_nonce` (inclusive) ascending, up to `limit`
+    /// entries, for `QueryMsg::ExportSwaps`. Indexed by nonce directly via
+    /// `get_at` rather than `page`/`page_size` skip-and-take, so a migration
+    /// script can resume from the last nonce it saw even if swaps were
+    /// created in between pages. Returns an empty vec once `start_nonce` is
+    /// past the end of the store, rather than an error, so a caller doesn't
+    /// need to know the store's length up front to know when to stop.
+    pub fn fetch_range_from_nonce<S: Storage>(
+        storage: &S,
+        start_nonce: u32,
+        limit: u32,
+    ) -> StdResult<Vec<SwapDetails>> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let store = match AppendStore::<SwapDetails, _>::attach(&store) {
+            Some(store) => store?,
+            None => return Ok(vec![]),
+        };
+        let len = store.len();
+        let mut swaps = Vec::new();
+        let mut nonce = start_nonce;
+        while nonce < len && swaps.len() < limit as usize {
+            swaps.push(store.get_at(nonce)?);
+            nonce += 1;
+        }
+        Ok(swaps)
+    }
+
+    /// Pages the nonces indexed under `status` (see `StatusIndex`) and
+    /// resolves each to its `SwapDetails`, for `SwapsByStatus`.
+    pub fn fetch_by_status<S: Storage>(
+        storage: &S,
+        status: SwapStatusFilter,
+        page: u32,
+        page_size: u32,
+    ) -> StdResult<Vec<SwapDetails>> {
+        StatusIndex::page(storage, status.tag(), page, page_size)
+            .into_iter()
+            .map(|nonce| Self::fetch_by_nonce(storage, nonce))
+            .collect()
+    }
+
+    /// Full scan for `Pending` swaps whose amount falls outside
+    /// `[min, max]`, for `SetMaxSwap`'s heads-up count. An admin-triggered,
+    /// rarely-called scan, unlike the hot paths elsewhere in this store that
+    /// go through `StatusIndex` to avoid one.
+    pub fn count_pending_outside_bounds<S: Storage>(
+        storage: &S,
+        min: Uint128,
+        max: Uint128,
+    ) -> StdResult<u32> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_DETAILS, storage);
+        let store = match AppendStore::<SwapDetails, _>::attach(&store) {
+            Some(store) => store?,
+            None => return Ok(0),
+        };
+        let mut count = 0u32;
+        for swap in store.iter() {
+            let swap = swap?;
+            if swap.status == SwapStatus::Pending && (swap.amount < min || swap.amount > max) {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Resolves a swap owned by `owner`, preferring `swap_id` (stable across
+    /// compaction) when given and falling back to `nonce` otherwise.
+    pub fn resolve<S: Storage>(
+        storage: &S,
+        owner: &CanonicalAddr,
+        nonce: u32,
+        swap_id: Option<&str>,
+    ) -> StdResult<SwapDetails> {
+        let swap = match swap_id {
+            Some(swap_id) => Self::fetch_by_swap_id(storage, swap_id)?,
+            None => return Self::fetch_swap_details(storage, owner, nonce),
+        };
+        if &swap.from_secret_address != owner {
+            return Err(crate::error::swap_does_not_belong_to_caller());
+        }
+        Ok(swap)
+    }
+}
+
+/// Maps a swap's `swap_id` to its nonce, maintained at save time so the
+/// stable id can be resolved to the append store position it currently
+/// occupies even if that position moved due to compaction.
+pub struct SwapIdIndexStore {}
+
+impl SwapIdIndexStore {
+    pub fn save<S: Storage>(storage: &mut S, swap_id: &str, nonce: u32) -> StdResult<()> {
+        let mut store = PrefixedStorage::new(PREFIX_SWAP_ID_INDEX, storage);
+        store.set(
+            swap_id.as_bytes(),
+            &bincode2::serialize(&nonce)
+                .map_err(|_| StdError::generic_err("failed to serialize swap id index entry"))?,
+        );
+        Ok(())
+    }
+
+    pub fn lookup<S: Storage>(storage: &S, swap_id: &str) -> Option<u32> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_SWAP_ID_INDEX, storage);
+        store
+            .get(swap_id.as_bytes())
+            .and_then(|bytes| bincode2::deserialize::<u32>(&bytes).ok())
+    }
+}
+
+/// Maps a swap's receipt hash to its `(owner, nonce)`, maintained at burn
+/// time so support staff can resolve a swap from a receipt hash alone
+/// without needing the owner's viewing key.
+pub struct ReceiptIndexStore {}
+
+impl ReceiptIndexStore {
+    pub fn save<S: Storage>(
+        storage: &mut S,
+        receipt_hash: &str,
+        owner: &CanonicalAddr,
+        nonce: u32,
+    ) -> StdResult<()> {
+        let mut store = PrefixedStorage::new(PREFIX_RECEIPT_INDEX, storage);
+        store.set(
+            receipt_hash.as_bytes(),
+            &bincode2::serialize(&(owner, nonce))
+                .map_err(|_| StdError::generic_err("failed to serialize receipt index entry"))?,
+        );
+        Ok(())
+    }
+
+    pub fn lookup<S: Storage>(storage: &S, receipt_hash: &str) -> Option<(CanonicalAddr, u32)> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_RECEIPT_INDEX, storage);
+        store
+            .get(receipt_hash.as_bytes())
+            .and_then(|bytes| bincode2::deserialize::<(CanonicalAddr, u32)>(&bytes).ok())
+    }
+}
+
+/// A proof-of-deposit for XMR that funded a mint of sXMR.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MoneroProof {
+    pub tx_id: String,
+    pub tx_key: String,
+    pub address: String,
+    /// Block height the deposit confirmed at, used to enforce monotonic
+    /// processing order when that policy is enabled.
+    pub block_height: u64,
+    /// Which output of the Monero tx funded this deposit. A single tx can
+    /// carry several outputs, so dedup keys on `(tx_id, output_index)`
+    /// rather than `tx_id` alone.
+    pub output_index: u32,
+    /// The deposit's amount in XMR atomic units (piconero), if the relayer
+    /// supplied one. When present, `mint_sxmr` requires the minted amount to
+    /// equal `scale_xmr_to_sxmr` of this value exactly, catching a relayer
+    /// miscalculation before it mints the wrong amount. `None` for relayers
+    /// that don't supply it, which skips the check entirely.
+    #[serde(default)]
+    pub xmr_atomic_amount: Option<u64>,
+    /// The sXMR amount this deposit minted. Stamped by `mint_sxmr` from its
+    /// own `amount` parameter when the proof is persisted, overwriting
+    /// whatever the caller set here, so an auditor reading the proof back
+    /// (e.g. via `fetch_by_tx_id`) always sees the real minted amount. See
+    /// `MintRecordStore` for the older recipient+amount side record this
+    /// overlaps with; that one stays for `RevertMint`'s write-off lookup.
+    #[serde(default)]
+    pub amount: Uint128,
+}
+
+pub struct MoneroProofsStore {}
+
+impl MoneroProofsStore {
+    /// Appends `proof` to the ordered history and records its
+    /// `(output_index, position)` under `PREFIX_PROOF_INDEX`, keyed by
+    /// `tx_id`, so `is_duplicate`/`fetch_by_tx_id` never have to scan the
+    /// append store.
+    pub fn save<S: Storage>(storage: &mut S, proof: &MoneroProof) -> StdResult<()> {
+        let position = {
+            let store = ReadonlyPrefixedStorage::new(PREFIX_MONERO_PROOFS, storage);
+            match AppendStore::<MoneroProof, _>::attach(&store) {
+                Some(store) => store?.len(),
+                None => 0,
+            }
+        };
+
+        let mut index = Self::proof_index(storage, &proof.tx_id);
+        index.push((proof.output_index, position));
+        let mut index_store = PrefixedStorage::new(PREFIX_PROOF_INDEX, storage);
+        index_store.set(
+            proof.tx_id.as_bytes(),
+            &bincode2::serialize(&index)
+                .map_err(|_| StdError::generic_err("failed to serialize proof index entry"))?,
+        );
+
+        let mut store = PrefixedStorage::new(PREFIX_MONERO_PROOFS, storage);
+        let mut store = AppendStoreMut::attach_or_create(&mut store)?;
+        store.push(proof)
+    }
+
+    /// Every `(output_index, position)` recorded against `tx_id`, empty if
+    /// none. `position` indexes into the `PREFIX_MONERO_PROOFS` append store.
+    fn proof_index<S: Storage>(storage: &S, tx_id: &str) -> Vec<(u32, u32)> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_PROOF_INDEX, storage);
+        store
+            .get(tx_id.as_bytes())
+            .and_then(|bytes| bincode2::deserialize::<Vec<(u32, u32)>>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// O(1) lookup via `PREFIX_PROOF_INDEX` instead of scanning every proof
+    /// ever recorded. Dedups on `(tx_id, output_index)`, since a single
+    /// Monero tx can fund the bridge with more than one output.
+    pub fn is_duplicate<S: Storage>(storage: &S, tx_id: &str, output_index: u32) -> StdResult<bool> {
+        if Self::is_revoked(storage, tx_id, output_index) {
+            return Ok(false);
+        }
+        if Self::is_imported_key(storage, tx_id, output_index) {
+            return Ok(true);
+        }
+        Ok(Self::proof_index(storage, tx_id)
+            .iter()
+            .any(|(index, _)| *index == output_index))
+    }
+
+    /// O(1) lookup via `PREFIX_PROOF_INDEX` for the first proof recorded
+    /// against `tx_id`, for an auditor reconciling a single deposit's
+    /// recorded amount against what it minted.
+    pub fn fetch_by_tx_id<S: Storage>(storage: &S, tx_id: &str) -> StdResult<Option<MoneroProof>> {
+        let position = match Self::proof_index(storage, tx_id).first() {
+            Some((_, position)) => *position,
+            None => return Ok(None),
+        };
+        let store = ReadonlyPrefixedStorage::new(PREFIX_MONERO_PROOFS, storage);
+        let store = match AppendStore::<MoneroProof, _>::attach(&store) {
+            Some(store) => store?,
+            None => return Ok(None),
+        };
+        Ok(Some(store.get_at(position)?))
+    }
+
+    fn imported_key(tx_id: &str, output_index: u32) -> Vec<u8> {
+        [tx_id.as_bytes(), &output_index.to_be_bytes()].concat()
+    }
+
+    fn is_imported_key<S: Storage>(storage: &S, tx_id: &str, output_index: u32) -> bool {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_IMPORTED_PROOF_KEYS, storage);
+        store.get(&Self::imported_key(tx_id, output_index)).is_some()
+    }
+
+    /// Pages the `(tx_id, output_index)` dedup keys of every non-revoked
+    /// proof, for migrating the replay-protection set to a successor
+    /// contract via `ImportProofs`. Deliberately omits `tx_key` and
+    /// `block_height` — a successor only needs enough to block replay, not
+    /// the full proof.
+    pub fn export_keys<S: Storage>(
+        storage: &S,
+        page: u32,
+        page_size: u32,
+    ) -> StdResult<Vec<(String, u32)>> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_MONERO_PROOFS, storage);
+        let store = match AppendStore::<MoneroProof, _>::attach(&store) {
+            Some(store) => store?,
+            None => return Ok(vec![]),
+        };
+
+        let start = (page as u64).saturating_mul(page_size as u64);
+        let mut keys = Vec::new();
+        for (i, proof) in store.iter().enumerate() {
+            if (i as u64) < start {
+                continue;
+            }
+            if keys.len() >= page_size as usize {
+                break;
+            }
+            let proof = proof?;
+            if Self::is_revoked(storage, &proof.tx_id, proof.output_index) {
+                continue;
+            }
+            keys.push((proof.tx_id, proof.output_index));
+        }
+        Ok(keys)
+    }
+
+    /// Marks `(tx_id, output_index)` as already used, without recording a
+    /// full `MoneroProof` for it (see `export_keys`'s doc comment for why
+    /// the export intentionally carries less than the original proof).
+    pub fn import_key<S: Storage>(storage: &mut S, tx_id: &str, output_index: u32) {
+        let mut store = PrefixedStorage::new(PREFIX_IMPORTED_PROOF_KEYS, storage);
+        store.set(&Self::imported_key(tx_id, output_index), &[1]);
+    }
+
+    /// Marks `(tx_id, output_index)` as reverted (see `RevertMint`), so
+    /// `is_duplicate` no longer blocks reusing that specific output, without
+    /// rewriting the append-only proof log itself. A single Monero tx can
+    /// fund several outputs and only one of them may be the one a reorg
+    /// orphaned, so this never touches the other outputs of `tx_id`.
+    pub fn revoke<S: Storage>(storage: &mut S, tx_id: &str, output_index: u32) {
+        let mut store = PrefixedStorage::new(PREFIX_REVOKED_PROOFS, storage);
+        store.set(&Self::imported_key(tx_id, output_index), &[1]);
+    }
+
+    pub fn is_revoked<S: Storage>(storage: &S, tx_id: &str, output_index: u32) -> bool {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_REVOKED_PROOFS, storage);
+        store.get(&Self::imported_key(tx_id, output_index)).is_some()
+    }
+
+    /// Linear scan for proofs confirmed within `[from, to]`, for auditors
+    /// reconciling against the Monero chain. `page`/`page_size` paginate the
+    /// *matching* proofs (not the full underlying store).
+    pub fn by_block_range<S: Storage>(
+        storage: &S,
+        from: u64,
+        to: u64,
+        page: u32,
+        page_size: u32,
+    ) -> StdResult<Vec<MoneroProof>> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_MONERO_PROOFS, storage);
+        let store = match AppendStore::<MoneroProof, _>::attach(&store) {
+            Some(store) => store?,
+            None => return Ok(vec![]),
+        };
+
+        let skip = (page as u64) * (page_size as u64);
+        let mut matching = vec![];
+        let mut seen = 0u64;
+        for proof in store.iter() {
+            let proof = proof?;
+            if proof.block_height < from || proof.block_height > to {
+                continue;
+            }
+            if seen >= skip && (matching.len() as u32) < page_size {
+                matching.push(proof);
+            }
+            seen += 1;
+        }
+        Ok(matching)
+    }
+}
+
+/// Records the recipient and amount a given deposit's proof minted, keyed
+/// by `(tx_id, output_index)` — the same key `MoneroProofsStore` dedups on,
+/// since a single Monero tx can fund several outputs and each mints (and
+/// can later be reverted) independently. `MoneroProof` itself carries the
+/// minted amount but no recipient (that is a separate `MintSecretMonero`
+/// handler parameter, never persisted on the proof), so `RevertMint` needs
+/// this side record to know whose balance to write off.
+pub struct MintRecordStore {}
+
+impl MintRecordStore {
+    fn key(tx_id: &str, output_index: u32) -> Vec<u8> {
+        [tx_id.as_bytes(), &output_index.to_be_bytes()].concat()
+    }
+
+    pub fn save<S: Storage>(
+        storage: &mut S,
+        tx_id: &str,
+        output_index: u32,
+        recipient: &CanonicalAddr,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        let mut store = PrefixedStorage::new(PREFIX_MINT_RECORD, storage);
+        store.set(
+            &Self::key(tx_id, output_index),
+            &bincode2::serialize(&(recipient, amount.u128()))
+                .map_err(|_| StdError::generic_err("failed to serialize mint record"))?,
+        );
+        Ok(())
+    }
+
+    pub fn lookup<S: Storage>(
+        storage: &S,
+        tx_id: &str,
+        output_index: u32,
+    ) -> Option<(CanonicalAddr, Uint128)> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RECORD, storage);
+        store
+            .get(&Self::key(tx_id, output_index))
+            .and_then(|bytes| bincode2::deserialize::<(CanonicalAddr, u128)>(&bytes).ok())
+            .map(|(recipient, amount)| (recipient, Uint128(amount)))
+    }
+}
+
+/// Tracks a rolling per-recipient mint total for `HandleMsg::SetMintRateLimit`,
+/// keyed by the recipient's canonical address. `window_start` resets (along
+/// with `minted`) the first time a mint lands `mint_window_blocks` or more
+/// blocks after it, rather than on a fixed calendar boundary.
+pub struct MintLimitsStore {}
+
+impl MintLimitsStore {
+    fn window<S: Storage>(storage: &S, recipient: &CanonicalAddr) -> Option<(u64, Uint128)> {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_MINT_LIMITS, storage);
+        store
+            .get(recipient.as_slice())
+            .and_then(|bytes| bincode2::deserialize::<(u64, u128)>(&bytes).ok())
+            .map(|(window_start, minted)| (window_start, Uint128(minted)))
+    }
+
+    fn set_window<S: Storage>(
+        storage: &mut S,
+        recipient: &CanonicalAddr,
+        window_start: u64,
+        minted: Uint128,
+    ) {
+        let mut store = PrefixedStorage::new(PREFIX_MINT_LIMITS, storage);
+        store.set(
+            recipient.as_slice(),
+            &bincode2::serialize(&(window_start, minted.u128())).unwrap(),
+        );
+    }
+
+    /// Rejects `amount` if, added to what `recipient` has already minted in
+    /// the current `mint_window_blocks`-long window, it would exceed
+    /// `limit_per_window`, then records the mint. `limit_per_window == 0`
+    /// disables the check without touching the stored window, so turning the
+    /// limit back on later resumes from whatever window was already in
+    /// progress.
+    pub fn charge<S: Storage>(
+        storage: &mut S,
+        recipient: &CanonicalAddr,
+        amount: Uint128,
+        limit_per_window: Uint128,
+        window_blocks: u64,
+        current_height: u64,
+    ) -> StdResult<()> {
+        if limit_per_window.u128() == 0 {
+            return Ok(());
+        }
+
+        let (window_start, minted_in_window) = match Self::window(storage, recipient) {
+            Some((window_start, minted)) if current_height < window_start + window_blocks => {
+                (window_start, minted)
+            }
+            _ => (current_height, Uint128::zero()),
+        };
+
+        let new_total = minted_in_window.u128() + amount.u128();
+        if new_total > limit_per_window.u128() {
+            return Err(StdError::generic_err(
+                "mint would exceed this recipient's rate limit for the current window",
+            ));
+        }
+
+        Self::set_window(storage, recipient, window_start, Uint128(new_total));
+        Ok(())
+    }
+}
+
+/// A single `tx_id`'s partial approvals toward `Config::mint_threshold`.
+#[derive(Serialize, Deserialize)]
+struct MintApproval {
+    recipient: CanonicalAddr,
+    amount: u128,
+    approvers: Vec<CanonicalAddr>,
+}
+
+/// Tracks which minters have approved minting a given `(tx_id, recipient,
+/// amount)`, keyed by `tx_id`, so `mint_sxmr` can require `threshold`
+/// distinct minters to agree before it actually mints. See
+/// `HandleMsg::SetMintThreshold`.
+pub struct MintApprovalsStore {}
+
+impl MintApprovalsStore {
+    /// Records `approver`'s vote for minting `amount` to `recipient` against
+    /// `tx_id`. Rejects a vote that disagrees with an already-recorded
+    /// `(recipient, amount)` for the same `tx_id`, so a minter can't steer a
+    /// deposit someone else already started approving toward a different
+    /// recipient or a mismatched amount. A repeat vote from the same
+    /// approver is a no-op, not double-counted. Returns the number of
+    /// distinct approvers recorded so far, including this one.
+    pub fn record_approval<S: Storage>(
+        storage: &mut S,
+        tx_id: &str,
+        recipient: &CanonicalAddr,
+        amount: Uint128,
+        approver: &CanonicalAddr,
+    ) -> StdResult<u32> {
+        let mut store = PrefixedStorage::new(PREFIX_MINT_APPROVALS, storage);
+        let mut approval = store
+            .get(tx_id.as_bytes())
+            .and_then(|bytes| bincode2::deserialize::<MintApproval>(&bytes).ok())
+            .unwrap_or_else(|| MintApproval {
+                recipient: recipient.clone(),
+                amount: amount.u128(),
+                approvers: vec![],
+            });
+
+        if &approval.recipient != recipient || approval.amount != amount.u128() {
+            return Err(StdError::generic_err(
+                "this tx_id already has a recorded mint approval for a different recipient or amount",
+            ));
+        }
+
+        if !approval.approvers.contains(approver) {
+            approval.approvers.push(approver.clone());
+        }
+        let count = approval.approvers.len() as u32;
+        store.set(
+            tx_id.as_bytes(),
+            &bincode2::serialize(&approval)
+                .map_err(|_| StdError::generic_err("failed to serialize mint approval"))?,
+        );
+        Ok(count)
+    }
+
+    /// Drops `tx_id`'s approval record once the mint it was tracking toward
+    /// has gone through (or been otherwise superseded); harmless to call on
+    /// a `tx_id` with no record.
+    pub fn clear<S: Storage>(storage: &mut S, tx_id: &str) {
+        let mut store = PrefixedStorage::new(PREFIX_MINT_APPROVALS, storage);
+        store.remove(tx_id.as_bytes());
+    }
+}
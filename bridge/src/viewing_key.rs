@@ -0,0 +1,38 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::rand::new_seed;
+
+const VIEWING_KEY_PREFIX: &str = "api_key_";
+
+/// A viewing key, stored on-chain only as its SHA-256 hash. The plaintext is
+/// handed back to the caller once (in the handler response) and never again.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ViewingKey(pub String);
+
+impl ViewingKey {
+    pub fn check_viewing_key(&self, hashed_key: &[u8]) -> bool {
+        let mine_hashed = Sha256::digest(self.0.as_bytes());
+        bool::from(mine_hashed.ct_eq(hashed_key))
+    }
+
+    pub fn new(prng_seed: &[u8], entropy: &[u8]) -> Self {
+        let seed = new_seed(prng_seed, entropy);
+        let hashed = Sha256::digest(&seed);
+        Self(VIEWING_KEY_PREFIX.to_string() + &base64::encode(hashed))
+    }
+
+    pub fn to_hashed(&self) -> Vec<u8> {
+        Sha256::digest(self.0.as_bytes()).to_vec()
+    }
+}
+
+impl fmt::Display for ViewingKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
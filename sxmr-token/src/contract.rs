@@ -0,0 +1,1664 @@
+use cosmwasm_std::{
+    log, to_binary, Api, Binary, CosmosMsg, Env, Extern, HandleResponse, InitResponse, Querier,
+    StdResult, Storage, WasmMsg,
+};
+
+use cosmwasm_std::HumanAddr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{HandleAnswer, HandleMsg, InitMsg, QueryAnswer, QueryMsg};
+use crate::state::{
+    append_tx, bump_tx_count, get_txs, read_allowance, read_allowance_checked, read_receiver_hash,
+    read_viewing_key, spender_list, store_burn, store_mint, store_transfer, write_allowance,
+    write_receiver_hash, write_viewing_key, Config, Constants, KeyScope, ReadonlyConfig, Tx,
+};
+use crate::viewing_key::ViewingKey;
+
+/// The receiver-side of the SNIP-20 `Send`/`RegisterReceive` handshake, as
+/// seen by whatever contract `Send`'s `recipient` points at. This token
+/// doesn't know that contract's own `HandleMsg` shape, so it constructs this
+/// minimal, structurally-compatible message by hand instead of depending on
+/// the receiver's crate.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum ReceiverHandleMsg {
+    Receive {
+        sender: HumanAddr,
+        from: HumanAddr,
+        amount: cosmwasm_std::Uint128,
+        msg: Option<Binary>,
+    },
+}
+
+pub fn init<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    msg: InitMsg,
+) -> StdResult<InitResponse> {
+    let admin = match msg.admin {
+        Some(addr) => deps.api.canonical_address(&addr)?,
+        None => deps.api.canonical_address(&env.message.sender)?,
+    };
+
+    let constants = Constants {
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+        admin,
+        prng_seed: msg.prng_seed.as_slice().to_vec(),
+        mix_block_entropy: msg.mix_block_entropy,
+        max_supply: msg.max_supply.map(|amount| amount.u128()),
+    };
+
+    let mut config = Config::from_storage(&mut deps.storage);
+    config.set_constants(&constants)?;
+    config.set_total_supply(0);
+
+    let minters_canonical = msg
+        .minters
+        .iter()
+        .map(|m| deps.api.canonical_address(m))
+        .collect::<StdResult<Vec<_>>>()?;
+    config.set_minters(minters_canonical);
+
+    Ok(InitResponse::default())
+}
+
+pub fn handle<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    msg: HandleMsg,
+) -> StdResult<HandleResponse> {
+    match msg {
+        HandleMsg::Transfer {
+            recipient,
+            amount,
+            memo,
+            ..
+        } => try_transfer(deps, env, recipient, amount, memo),
+        HandleMsg::Send {
+            recipient,
+            recipient_code_hash,
+            amount,
+            msg,
+            memo,
+            ..
+        } => try_send(deps, env, recipient, recipient_code_hash, amount, msg, memo),
+        HandleMsg::RegisterReceive { code_hash, .. } => try_register_receive(deps, env, code_hash),
+        HandleMsg::Mint {
+            recipient,
+            amount,
+            memo,
+            ..
+        } => try_mint(deps, env, recipient, amount, memo),
+        HandleMsg::Burn { amount, memo, .. } => try_burn(deps, env, amount, memo),
+        HandleMsg::SetViewingKey { key, .. } => try_set_viewing_key(deps, env, key),
+        HandleMsg::CreateViewingKey { entropy, .. } => try_create_viewing_key(deps, env, entropy),
+        HandleMsg::SetObserverKey { key, .. } => try_set_observer_key(deps, env, key),
+        HandleMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expiration,
+            ..
+        } => try_increase_allowance(deps, env, spender, amount, expiration),
+        HandleMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expiration,
+            ..
+        } => try_decrease_allowance(deps, env, spender, amount, expiration),
+        HandleMsg::BurnFrom {
+            owner,
+            amount,
+            memo,
+            ..
+        } => try_burn_from(deps, env, owner, amount, memo),
+        _ => Err(cosmwasm_std::StdError::generic_err("not yet implemented")),
+    }
+}
+
+/// Shortest a token tx memo may be once validated; `0` allows an empty memo.
+const MIN_MEMO_LEN: usize = 0;
+/// Longest a token tx memo may be, in bytes.
+const MAX_MEMO_LEN: usize = 256;
+
+fn normalize_memo(memo: Option<String>) -> StdResult<Option<String>> {
+    memo.map(|memo| crate::memo::validate_and_normalize_memo(&memo, MIN_MEMO_LEN, MAX_MEMO_LEN))
+        .transpose()
+}
+
+fn try_transfer<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    recipient: cosmwasm_std::HumanAddr,
+    amount: cosmwasm_std::Uint128,
+    memo: Option<String>,
+) -> StdResult<HandleResponse> {
+    let memo = normalize_memo(memo)?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let recipient_canonical = deps.api.canonical_address(&recipient)?;
+    store_transfer(&mut deps.storage, &sender, &recipient_canonical, amount.u128())?;
+    bump_tx_count(&mut deps.storage, &sender);
+    bump_tx_count(&mut deps.storage, &recipient_canonical);
+
+    let tx = Tx {
+        action: "transfer".to_string(),
+        from: sender.clone(),
+        to: recipient_canonical.clone(),
+        amount: amount.u128(),
+        memo,
+        burner: None,
+    };
+    append_tx(&mut deps.storage, &sender, &tx)?;
+    append_tx(&mut deps.storage, &recipient_canonical, &tx)?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "transfer")],
+        data: Some(to_binary(&HandleAnswer::Transfer {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Like `try_transfer`, but also dispatches a `Receive` callback to
+/// `recipient` when it's a contract that's registered a code hash (or one is
+/// given directly via `recipient_code_hash`), so `recipient` can react to the
+/// incoming funds in the same transaction — the only way in SNIP-20 to move
+/// tokens into a contract and have it notice atomically. Falls back to a
+/// plain transfer with no callback if neither is available, matching the
+/// reference SNIP-20 behavior for sending to a plain wallet.
+fn try_send<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    recipient: HumanAddr,
+    recipient_code_hash: Option<String>,
+    amount: cosmwasm_std::Uint128,
+    msg: Option<Binary>,
+    memo: Option<String>,
+) -> StdResult<HandleResponse> {
+    let memo = normalize_memo(memo)?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let recipient_canonical = deps.api.canonical_address(&recipient)?;
+    store_transfer(&mut deps.storage, &sender, &recipient_canonical, amount.u128())?;
+    bump_tx_count(&mut deps.storage, &sender);
+    bump_tx_count(&mut deps.storage, &recipient_canonical);
+
+    let tx = Tx {
+        action: "send".to_string(),
+        from: sender.clone(),
+        to: recipient_canonical.clone(),
+        amount: amount.u128(),
+        memo,
+        burner: None,
+    };
+    append_tx(&mut deps.storage, &sender, &tx)?;
+    append_tx(&mut deps.storage, &recipient_canonical, &tx)?;
+
+    let code_hash = recipient_code_hash.or_else(|| read_receiver_hash(&deps.storage, &recipient_canonical));
+    let messages = match code_hash {
+        Some(code_hash) => vec![receiver_callback_msg(
+            code_hash,
+            recipient,
+            env.message.sender,
+            amount,
+            msg,
+        )?],
+        None => vec![],
+    };
+
+    Ok(HandleResponse {
+        messages,
+        log: vec![log("action", "send")],
+        data: Some(to_binary(&HandleAnswer::Send {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Builds the `Receive` callback `try_send` fires into `recipient`. This
+/// token has no `SendFrom`, so `sender` and `from` are always the same
+/// caller; both are still passed through since that's what the SNIP-20
+/// receiver interface expects.
+fn receiver_callback_msg(
+    code_hash: String,
+    recipient: HumanAddr,
+    sender: HumanAddr,
+    amount: cosmwasm_std::Uint128,
+    msg: Option<Binary>,
+) -> StdResult<CosmosMsg> {
+    let receive_msg = ReceiverHandleMsg::Receive {
+        sender: sender.clone(),
+        from: sender,
+        amount,
+        msg,
+    };
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: recipient,
+        callback_code_hash: code_hash,
+        msg: to_binary(&receive_msg)?,
+        send: vec![],
+    }))
+}
+
+/// Records `code_hash` so a future `Send` naming `env.message.sender` as its
+/// recipient can dispatch a `Receive` callback without the sender having to
+/// supply `recipient_code_hash` itself.
+fn try_register_receive<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    code_hash: String,
+) -> StdResult<HandleResponse> {
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    write_receiver_hash(&mut deps.storage, &sender, code_hash);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::RegisterReceive {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn try_mint<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    recipient: cosmwasm_std::HumanAddr,
+    amount: cosmwasm_std::Uint128,
+    memo: Option<String>,
+) -> StdResult<HandleResponse> {
+    let memo = normalize_memo(memo)?;
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let minters = ReadonlyConfig::from_storage(&deps.storage).minters();
+    if !minters.contains(&sender) {
+        return Err(cosmwasm_std::StdError::generic_err("not authorized to mint"));
+    }
+
+    let recipient_canonical = deps.api.canonical_address(&recipient)?;
+    store_mint(&mut deps.storage, &recipient_canonical, amount.u128())?;
+    bump_tx_count(&mut deps.storage, &recipient_canonical);
+    append_tx(
+        &mut deps.storage,
+        &recipient_canonical,
+        &Tx {
+            action: "mint".to_string(),
+            from: sender,
+            to: recipient_canonical.clone(),
+            amount: amount.u128(),
+            memo,
+            burner: None,
+        },
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "mint")],
+        data: Some(to_binary(&HandleAnswer::Mint {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn try_burn<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    amount: cosmwasm_std::Uint128,
+    memo: Option<String>,
+) -> StdResult<HandleResponse> {
+    let memo = normalize_memo(memo)?;
+    let owner = deps.api.canonical_address(&env.message.sender)?;
+    store_burn(&mut deps.storage, &owner, amount.u128(), None)?;
+    bump_tx_count(&mut deps.storage, &owner);
+    append_tx(
+        &mut deps.storage,
+        &owner,
+        &Tx {
+            action: "burn".to_string(),
+            from: owner.clone(),
+            to: owner.clone(),
+            amount: amount.u128(),
+            memo,
+            burner: None,
+        },
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "burn")],
+        data: Some(to_binary(&HandleAnswer::Burn {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Like `try_burn`, but burns from `owner`'s balance against the allowance
+/// `owner` granted the caller instead of the caller's own balance.
+fn try_burn_from<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    owner: HumanAddr,
+    amount: cosmwasm_std::Uint128,
+    memo: Option<String>,
+) -> StdResult<HandleResponse> {
+    let memo = normalize_memo(memo)?;
+    let owner_canonical = deps.api.canonical_address(&owner)?;
+    let spender = deps.api.canonical_address(&env.message.sender)?;
+
+    let mut allowance =
+        read_allowance_checked(&deps.storage, &owner_canonical, &spender, env.block.time);
+    allowance.amount = allowance
+        .amount
+        .checked_sub(amount.u128())
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("insufficient allowance"))?;
+    write_allowance(&mut deps.storage, &owner_canonical, &spender, &allowance);
+
+    store_burn(&mut deps.storage, &owner_canonical, amount.u128(), Some(&spender))?;
+    bump_tx_count(&mut deps.storage, &owner_canonical);
+    append_tx(
+        &mut deps.storage,
+        &owner_canonical,
+        &Tx {
+            action: "burn_from".to_string(),
+            from: owner_canonical.clone(),
+            to: owner_canonical.clone(),
+            amount: amount.u128(),
+            memo,
+            burner: Some(spender),
+        },
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "burn_from")],
+        data: Some(to_binary(&HandleAnswer::BurnFrom {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn try_set_viewing_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    key: String,
+) -> StdResult<HandleResponse> {
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let vk = ViewingKey(key);
+    write_viewing_key(&mut deps.storage, &sender, vk.to_hashed(), KeyScope::Full);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetViewingKey {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+/// Generates a viewing key server-side instead of taking the caller's own, as
+/// `try_set_viewing_key` does. The seed material fed to `ViewingKey::new`
+/// depends on `Constants::mix_block_entropy`: when set, `entropy` is mixed
+/// with `env.block.height`/`.time` so the key is unpredictable even across
+/// repeated calls; when unset, the key is derived purely from `prng_seed` and
+/// the caller's address, so it's reproducible (useful for testing).
+fn try_create_viewing_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    entropy: String,
+) -> StdResult<HandleResponse> {
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let constants = Config::from_storage(&mut deps.storage).constants()?;
+
+    let seed_material: Vec<u8> = if constants.mix_block_entropy {
+        let mut material = entropy.into_bytes();
+        material.extend_from_slice(&env.block.height.to_be_bytes());
+        material.extend_from_slice(&env.block.time.to_be_bytes());
+        material
+    } else {
+        sender.as_slice().to_vec()
+    };
+
+    let key = ViewingKey::new(&constants.prng_seed, &seed_material);
+    write_viewing_key(&mut deps.storage, &sender, key.to_hashed(), KeyScope::Full);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::CreateViewingKey {
+            key: key.to_string(),
+        })?),
+    })
+}
+
+/// Like `try_set_viewing_key`, but grants the `BalanceOnly` scope instead of
+/// `Full`, for issuing a least-privilege key to a monitoring service.
+fn try_set_observer_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    key: String,
+) -> StdResult<HandleResponse> {
+    let sender = deps.api.canonical_address(&env.message.sender)?;
+    let vk = ViewingKey(key);
+    write_viewing_key(
+        &mut deps.storage,
+        &sender,
+        vk.to_hashed(),
+        KeyScope::BalanceOnly,
+    );
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetObserverKey {
+            status: "success".to_string(),
+        })?),
+    })
+}
+
+fn try_increase_allowance<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    spender: HumanAddr,
+    amount: cosmwasm_std::Uint128,
+    expiration: Option<u64>,
+) -> StdResult<HandleResponse> {
+    let owner = deps.api.canonical_address(&env.message.sender)?;
+    let spender_canonical = deps.api.canonical_address(&spender)?;
+
+    let mut allowance = read_allowance(&deps.storage, &owner, &spender_canonical);
+    allowance.amount = allowance
+        .amount
+        .checked_add(amount.u128())
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("allowance overflow"))?;
+    if expiration.is_some() {
+        allowance.expiration = expiration;
+    }
+    write_allowance(&mut deps.storage, &owner, &spender_canonical, &allowance);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "increase_allowance")],
+        data: Some(to_binary(&HandleAnswer::IncreaseAllowance {
+            spender,
+            allowance: cosmwasm_std::Uint128(allowance.amount),
+        })?),
+    })
+}
+
+fn try_decrease_allowance<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    spender: HumanAddr,
+    amount: cosmwasm_std::Uint128,
+    expiration: Option<u64>,
+) -> StdResult<HandleResponse> {
+    let owner = deps.api.canonical_address(&env.message.sender)?;
+    let spender_canonical = deps.api.canonical_address(&spender)?;
+
+    let mut allowance = read_allowance(&deps.storage, &owner, &spender_canonical);
+    allowance.amount = allowance.amount.saturating_sub(amount.u128());
+    if expiration.is_some() {
+        allowance.expiration = expiration;
+    }
+    write_allowance(&mut deps.storage, &owner, &spender_canonical, &allowance);
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("action", "decrease_allowance")],
+        data: Some(to_binary(&HandleAnswer::DecreaseAllowance {
+            spender,
+            allowance: cosmwasm_std::Uint128(allowance.amount),
+        })?),
+    })
+}
+
+pub fn query<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::TokenInfo {} => query_token_info(deps),
+        QueryMsg::Balance { address, key } => query_balance(deps, address, key),
+        QueryMsg::Account { address, key } => query_account(deps, address, key),
+        QueryMsg::AllowancesGiven {
+            owner,
+            key,
+            page,
+            page_size,
+        } => query_allowances_given(deps, owner, key, page, page_size),
+        QueryMsg::AllAllowances {
+            owner,
+            key,
+            page,
+            page_size,
+        } => query_all_allowances(deps, owner, key, page, page_size),
+        QueryMsg::TransactionById { address, key, id } => {
+            query_transaction_by_id(deps, address, key, id)
+        }
+        QueryMsg::TransactionHistory {
+            address,
+            key,
+            page,
+            page_size,
+            filter,
+        } => query_transaction_history(deps, address, key, page, page_size, filter),
+        QueryMsg::TxCountFor { address, key } => query_tx_count_for(deps, address, key),
+        QueryMsg::Minters {} => query_minters(deps),
+    }
+}
+
+/// Checks `key` against the viewing key stored for `account` and that its
+/// scope covers `required`, returning the account's canonical address on
+/// success. A `Full` key satisfies any `required` scope; a scoped key (e.g.
+/// `BalanceOnly`) only satisfies a matching one.
+fn authenticate<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    account: &HumanAddr,
+    key: String,
+    required: KeyScope,
+) -> StdResult<cosmwasm_std::CanonicalAddr> {
+    let canonical = deps.api.canonical_address(account)?;
+    let stored = read_viewing_key(&deps.storage, &canonical)
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("unauthorized"))?;
+    if !ViewingKey(key).check_viewing_key(&stored.hashed) {
+        return Err(cosmwasm_std::StdError::generic_err("unauthorized"));
+    }
+    if stored.scope != KeyScope::Full && stored.scope != required {
+        return Err(cosmwasm_std::StdError::generic_err("unauthorized"));
+    }
+    Ok(canonical)
+}
+
+fn query_token_info<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let config = ReadonlyConfig::from_storage(&deps.storage);
+    let constants = config.constants()?;
+    to_binary(&QueryAnswer::TokenInfo {
+        name: constants.name,
+        symbol: constants.symbol,
+        decimals: constants.decimals,
+        total_supply: Some(cosmwasm_std::Uint128(config.total_supply())),
+    })
+}
+
+fn query_minters<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> StdResult<Binary> {
+    let minters = ReadonlyConfig::from_storage(&deps.storage)
+        .minters()
+        .iter()
+        .map(|m| deps.api.human_address(m))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&QueryAnswer::Minters { minters })
+}
+
+fn query_balance<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    key: String,
+) -> StdResult<Binary> {
+    use crate::state::read_balance;
+
+    let account = authenticate(deps, &address, key, KeyScope::BalanceOnly)?;
+    to_binary(&QueryAnswer::Balance {
+        amount: cosmwasm_std::Uint128(read_balance(&deps.storage, &account)),
+    })
+}
+
+fn query_account<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    key: String,
+) -> StdResult<Binary> {
+    use crate::state::{read_balance, read_tx_count};
+
+    let account = authenticate(deps, &address, key, KeyScope::Full)?;
+    to_binary(&QueryAnswer::Account {
+        balance: cosmwasm_std::Uint128(read_balance(&deps.storage, &account)),
+        tx_count: read_tx_count(&deps.storage, &account),
+    })
+}
+
+/// Shared pagination/lookup for `query_allowances_given`/`query_all_allowances`:
+/// pages `spenders` most-recently-granted first and resolves each to its
+/// current allowance, carrying `expiration` along even for the caller that
+/// ends up dropping it, so the pagination math only has to be right once.
+fn paged_spender_allowances<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    owner_canonical: &cosmwasm_std::CanonicalAddr,
+    spenders: &[cosmwasm_std::CanonicalAddr],
+    page: Option<u32>,
+    page_size: u32,
+) -> StdResult<Vec<(HumanAddr, cosmwasm_std::Uint128, Option<u64>)>> {
+    let page = page.unwrap_or(0) as usize;
+    let page_size = page_size as usize;
+    let start = page.saturating_mul(page_size);
+
+    spenders
+        .iter()
+        .rev()
+        .skip(start)
+        .take(page_size)
+        .map(|spender| {
+            let allowance = read_allowance(&deps.storage, owner_canonical, spender);
+            let human = deps.api.human_address(spender)?;
+            Ok((human, cosmwasm_std::Uint128(allowance.amount), allowance.expiration))
+        })
+        .collect::<StdResult<Vec<_>>>()
+}
+
+fn query_allowances_given<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    owner: HumanAddr,
+    key: String,
+    page: Option<u32>,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let owner_canonical = authenticate(deps, &owner, key, KeyScope::Full)?;
+    let spenders = spender_list(&deps.storage, &owner_canonical);
+
+    let allowances = paged_spender_allowances(deps, &owner_canonical, &spenders, page, page_size)?
+        .into_iter()
+        .map(|(human, amount, _)| (human, amount))
+        .collect();
+
+    to_binary(&QueryAnswer::AllowancesGiven {
+        owner,
+        allowances,
+        count: spenders.len() as u32,
+    })
+}
+
+fn query_all_allowances<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    owner: HumanAddr,
+    key: String,
+    page: Option<u32>,
+    page_size: u32,
+) -> StdResult<Binary> {
+    let owner_canonical = authenticate(deps, &owner, key, KeyScope::Full)?;
+    let spenders = spender_list(&deps.storage, &owner_canonical);
+
+    let allowances = paged_spender_allowances(deps, &owner_canonical, &spenders, page, page_size)?;
+
+    to_binary(&QueryAnswer::AllAllowances {
+        owner,
+        allowances,
+        count: spenders.len() as u32,
+    })
+}
+
+fn query_transaction_by_id<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    key: String,
+    id: u32,
+) -> StdResult<Binary> {
+    use crate::state::read_tx;
+
+    let account = authenticate(deps, &address, key, KeyScope::Full)?;
+    let tx = read_tx(&deps.storage, &account, id)
+        .map(|tx| {
+            Ok(crate::msg::Tx {
+                action: tx.action,
+                from: deps.api.human_address(&tx.from)?,
+                to: deps.api.human_address(&tx.to)?,
+                amount: cosmwasm_std::Uint128(tx.amount),
+                memo: tx.memo,
+                burner: tx
+                    .burner
+                    .as_ref()
+                    .map(|burner| deps.api.human_address(burner))
+                    .transpose()?,
+            })
+        })
+        .transpose()?;
+
+    to_binary(&QueryAnswer::TransactionById { tx })
+}
+
+fn query_transaction_history<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    key: String,
+    page: u32,
+    page_size: u32,
+    filter: Option<crate::state::TxActionKind>,
+) -> StdResult<Binary> {
+    let account = authenticate(deps, &address, key, KeyScope::Full)?;
+    let txs = get_txs(&deps.storage, &account, page, page_size, filter)
+        .into_iter()
+        .map(|tx| {
+            Ok(crate::msg::Tx {
+                action: tx.action,
+                from: deps.api.human_address(&tx.from)?,
+                to: deps.api.human_address(&tx.to)?,
+                amount: cosmwasm_std::Uint128(tx.amount),
+                memo: tx.memo,
+                burner: tx
+                    .burner
+                    .as_ref()
+                    .map(|burner| deps.api.human_address(burner))
+                    .transpose()?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&QueryAnswer::TransactionHistory { txs })
+}
+
+fn query_tx_count_for<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: HumanAddr,
+    key: String,
+) -> StdResult<Binary> {
+    authenticate(deps, &address, key, KeyScope::Full)?;
+    let count = crate::state::get_tx_count_for(&deps.api, &deps.storage, &address)?;
+    to_binary(&QueryAnswer::TxCountFor { count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{Binary, Uint128};
+
+    fn init_helper() -> Extern<
+        cosmwasm_std::testing::MockStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    > {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            name: "Secret Monero".to_string(),
+            symbol: "SXMR".to_string(),
+            decimals: 12,
+            admin: None,
+            minters: vec![HumanAddr("minter".to_string())],
+            prng_seed: Binary::from(b"seed".to_vec()),
+            mix_block_entropy: true,
+            max_supply: None,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+        try_set_viewing_key(&mut deps, mock_env("alice", &[]), "alice-key".to_string()).unwrap();
+        deps
+    }
+
+    #[test]
+    fn minters_query_returns_empty_on_an_uninitialized_key() {
+        let deps = mock_dependencies(20, &[]);
+        // No init() call, so MINTERS_KEY has never been written.
+        let result = query_minters(&deps).unwrap();
+        assert_eq!(
+            result,
+            to_binary(&QueryAnswer::Minters { minters: vec![] }).unwrap()
+        );
+    }
+
+    #[test]
+    fn observer_key_can_query_balance_but_is_denied_history() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+        try_set_observer_key(&mut deps, mock_env("alice", &[]), "observer-key".to_string())
+            .unwrap();
+
+        let balance = query_balance(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "observer-key".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            balance,
+            to_binary(&QueryAnswer::Balance {
+                amount: Uint128(100)
+            })
+            .unwrap()
+        );
+
+        let history = query_transaction_by_id(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "observer-key".to_string(),
+            0,
+        );
+        assert!(history.is_err());
+    }
+
+    #[test]
+    fn deterministic_create_viewing_key_is_reproducible_across_blocks() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            name: "Secret Monero".to_string(),
+            symbol: "SXMR".to_string(),
+            decimals: 12,
+            admin: None,
+            minters: vec![HumanAddr("minter".to_string())],
+            prng_seed: Binary::from(b"seed".to_vec()),
+            mix_block_entropy: false,
+            max_supply: None,
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 100;
+        env.block.time = 1_000;
+        let first = try_create_viewing_key(&mut deps, env, "entropy".to_string()).unwrap();
+
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 200;
+        env.block.time = 2_000;
+        let second = try_create_viewing_key(&mut deps, env, "different-entropy".to_string()).unwrap();
+
+        assert_eq!(first.data, second.data);
+    }
+
+    #[test]
+    fn entropy_mixed_create_viewing_key_differs_across_blocks() {
+        let mut deps = init_helper();
+
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 100;
+        env.block.time = 1_000;
+        let first = try_create_viewing_key(&mut deps, env, "entropy".to_string()).unwrap();
+
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 200;
+        env.block.time = 2_000;
+        let second = try_create_viewing_key(&mut deps, env, "entropy".to_string()).unwrap();
+
+        assert_ne!(first.data, second.data);
+    }
+
+    #[test]
+    fn granted_allowance_appears_in_allowances_given() {
+        let mut deps = init_helper();
+        try_increase_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+
+        let response = query_allowances_given(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            None,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryAnswer::AllowancesGiven {
+                owner: HumanAddr("alice".to_string()),
+                allowances: vec![(HumanAddr("bob".to_string()), Uint128(100))],
+                count: 1,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn account_query_matches_stored_balance_and_tx_count() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        try_transfer(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(300),
+            None,
+        )
+        .unwrap();
+
+        let response = query_account(&deps, HumanAddr("alice".to_string()), "alice-key".to_string())
+            .unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryAnswer::Account {
+                balance: Uint128(700),
+                tx_count: 2,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn fully_revoked_allowance_drops_out_of_allowances_given() {
+        let mut deps = init_helper();
+        try_increase_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+        try_decrease_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+
+        let response = query_allowances_given(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            None,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryAnswer::AllowancesGiven {
+                owner: HumanAddr("alice".to_string()),
+                allowances: vec![],
+                count: 0,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn transaction_by_id_looks_up_a_known_transaction() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(500),
+            None,
+        )
+        .unwrap();
+
+        let response = query_transaction_by_id(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryAnswer::TransactionById {
+                tx: Some(crate::msg::Tx {
+                    action: "mint".to_string(),
+                    from: HumanAddr("minter".to_string()),
+                    to: HumanAddr("alice".to_string()),
+                    amount: Uint128(500),
+                    memo: None,
+                    burner: None,
+                }),
+            })
+            .unwrap()
+        );
+
+        let missing = query_transaction_by_id(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            1,
+        )
+        .unwrap();
+        assert_eq!(
+            missing,
+            to_binary(&QueryAnswer::TransactionById { tx: None }).unwrap()
+        );
+    }
+
+    #[test]
+    fn a_valid_memo_is_trimmed_and_stored_on_the_tx() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(500),
+            Some("  rent for June  ".to_string()),
+        )
+        .unwrap();
+
+        let response = query_transaction_by_id(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            0,
+        )
+        .unwrap();
+        let tx = match cosmwasm_std::from_binary(&response).unwrap() {
+            QueryAnswer::TransactionById { tx } => tx.unwrap(),
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(tx.memo, Some("rent for June".to_string()));
+    }
+
+    #[test]
+    fn a_memo_over_the_byte_limit_is_rejected() {
+        let mut deps = init_helper();
+        let result = try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(500),
+            Some("a".repeat(MAX_MEMO_LEN + 1)),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn a_memo_with_control_characters_is_rejected() {
+        let mut deps = init_helper();
+        let result = try_transfer(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            Some("hi\nthere".to_string()),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_from_debits_the_allowance_and_the_owners_balance() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        try_increase_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(400),
+            None,
+        )
+        .unwrap();
+
+        let result = try_burn_from(
+            &mut deps,
+            mock_env("bob", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(300),
+            None,
+        );
+        assert!(result.is_ok());
+
+        let alice = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let bob = deps.api.canonical_address(&HumanAddr("bob".to_string())).unwrap();
+        assert_eq!(crate::state::read_balance(&deps.storage, &alice), 700);
+        assert_eq!(
+            read_allowance(&deps.storage, &alice, &bob).amount,
+            100
+        );
+    }
+
+    #[test]
+    fn burn_from_rejects_an_expired_allowance() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        try_increase_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(400),
+            Some(1_000),
+        )
+        .unwrap();
+
+        let mut env = mock_env("bob", &[]);
+        env.block.time = 1_000;
+        let result = try_burn_from(
+            &mut deps,
+            env,
+            HumanAddr("alice".to_string()),
+            Uint128(100),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn burn_from_rejects_an_amount_over_the_allowance() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        try_increase_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+
+        let result = try_burn_from(
+            &mut deps,
+            mock_env("bob", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(200),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_allowance_checked_is_intact_just_before_expiration() {
+        let mut deps = init_helper();
+        let owner = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let spender = deps.api.canonical_address(&HumanAddr("bob".to_string())).unwrap();
+        write_allowance(
+            &mut deps.storage,
+            &owner,
+            &spender,
+            &crate::state::Allowance {
+                amount: 500,
+                expiration: Some(1_000),
+            },
+        );
+
+        let allowance = read_allowance_checked(&deps.storage, &owner, &spender, 999);
+        assert_eq!(allowance.amount, 500);
+    }
+
+    #[test]
+    fn read_allowance_checked_is_zeroed_at_expiration() {
+        let mut deps = init_helper();
+        let owner = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let spender = deps.api.canonical_address(&HumanAddr("bob".to_string())).unwrap();
+        write_allowance(
+            &mut deps.storage,
+            &owner,
+            &spender,
+            &crate::state::Allowance {
+                amount: 500,
+                expiration: Some(1_000),
+            },
+        );
+
+        let allowance = read_allowance_checked(&deps.storage, &owner, &spender, 1_000);
+        assert_eq!(allowance.amount, 0);
+    }
+
+    #[test]
+    fn read_allowance_checked_stays_zeroed_after_expiration() {
+        let mut deps = init_helper();
+        let owner = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let spender = deps.api.canonical_address(&HumanAddr("bob".to_string())).unwrap();
+        write_allowance(
+            &mut deps.storage,
+            &owner,
+            &spender,
+            &crate::state::Allowance {
+                amount: 500,
+                expiration: Some(1_000),
+            },
+        );
+
+        let allowance = read_allowance_checked(&deps.storage, &owner, &spender, 1_001);
+        assert_eq!(allowance.amount, 0);
+    }
+
+    #[test]
+    fn transaction_history_filters_by_action_kind() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        try_transfer(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+        try_burn(&mut deps, mock_env("alice", &[]), Uint128(50), None).unwrap();
+        try_transfer(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(25),
+            None,
+        )
+        .unwrap();
+
+        let response = query_transaction_history(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            0,
+            10,
+            Some(crate::state::TxActionKind::Transfer),
+        )
+        .unwrap();
+        let txs = match cosmwasm_std::from_binary::<QueryAnswer>(&response).unwrap() {
+            QueryAnswer::TransactionHistory { txs } => txs,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(txs.len(), 2);
+        assert!(txs.iter().all(|tx| tx.action == "transfer"));
+        assert_eq!(txs[0].amount, Uint128(25));
+
+        let unfiltered = match cosmwasm_std::from_binary::<QueryAnswer>(
+            &query_transaction_history(
+                &deps,
+                HumanAddr("alice".to_string()),
+                "alice-key".to_string(),
+                0,
+                10,
+                None,
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        {
+            QueryAnswer::TransactionHistory { txs } => txs,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(unfiltered.len(), 4);
+    }
+
+    #[test]
+    fn transaction_history_paginates_over_the_filtered_set() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        for amount in [10u128, 20, 30] {
+            try_transfer(
+                &mut deps,
+                mock_env("alice", &[]),
+                HumanAddr("bob".to_string()),
+                Uint128(amount),
+                None,
+            )
+            .unwrap();
+        }
+
+        let page = match cosmwasm_std::from_binary::<QueryAnswer>(
+            &query_transaction_history(
+                &deps,
+                HumanAddr("alice".to_string()),
+                "alice-key".to_string(),
+                0,
+                2,
+                Some(crate::state::TxActionKind::Transfer),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        {
+            QueryAnswer::TransactionHistory { txs } => txs,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].amount, Uint128(30));
+        assert_eq!(page[1].amount, Uint128(20));
+    }
+
+    #[test]
+    fn tx_count_for_reflects_history_store_length() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        try_transfer(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+        try_burn(&mut deps, mock_env("alice", &[]), Uint128(50), None).unwrap();
+
+        let response = query_tx_count_for(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryAnswer::TxCountFor { count: 3 }).unwrap()
+        );
+
+        try_set_viewing_key(&mut deps, mock_env("carol", &[]), "carol-key".to_string()).unwrap();
+        let response = query_tx_count_for(
+            &deps,
+            HumanAddr("carol".to_string()),
+            "carol-key".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryAnswer::TxCountFor { count: 0 }).unwrap()
+        );
+    }
+
+    fn init_helper_with_max_supply(max_supply: u128) -> Extern<
+        cosmwasm_std::testing::MockStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    > {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            name: "Secret Monero".to_string(),
+            symbol: "SXMR".to_string(),
+            decimals: 12,
+            admin: None,
+            minters: vec![HumanAddr("minter".to_string())],
+            prng_seed: Binary::from(b"seed".to_vec()),
+            mix_block_entropy: true,
+            max_supply: Some(Uint128(max_supply)),
+        };
+        init(&mut deps, mock_env("admin", &[]), init_msg).unwrap();
+        deps
+    }
+
+    #[test]
+    fn mint_succeeds_up_to_the_max_supply_cap() {
+        let mut deps = init_helper_with_max_supply(1_000);
+        let result = try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1_000),
+            None,
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).total_supply(),
+            1_000
+        );
+    }
+
+    #[test]
+    fn mint_rejects_crossing_the_max_supply_cap() {
+        let mut deps = init_helper_with_max_supply(1_000);
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(900),
+            None,
+        )
+        .unwrap();
+
+        let result = try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(101),
+            None,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            ReadonlyConfig::from_storage(&deps.storage).total_supply(),
+            900
+        );
+    }
+
+    #[test]
+    fn store_transfers_pays_out_a_three_recipient_batch() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+
+        let alice = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let bob = deps.api.canonical_address(&HumanAddr("bob".to_string())).unwrap();
+        let carol = deps.api.canonical_address(&HumanAddr("carol".to_string())).unwrap();
+        let dave = deps.api.canonical_address(&HumanAddr("dave".to_string())).unwrap();
+
+        let result = crate::state::store_transfers(
+            &mut deps.storage,
+            &alice,
+            &[
+                (bob.clone(), 100, None),
+                (carol.clone(), 200, Some("rent".to_string())),
+                (dave.clone(), 300, None),
+            ],
+        );
+        assert!(result.is_ok());
+
+        assert_eq!(crate::state::read_balance(&deps.storage, &alice), 400);
+        assert_eq!(crate::state::read_balance(&deps.storage, &bob), 100);
+        assert_eq!(crate::state::read_balance(&deps.storage, &carol), 200);
+        assert_eq!(crate::state::read_balance(&deps.storage, &dave), 300);
+    }
+
+    #[test]
+    fn store_transfers_writes_nothing_when_the_sum_exceeds_the_balance() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(500),
+            None,
+        )
+        .unwrap();
+
+        let alice = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let bob = deps.api.canonical_address(&HumanAddr("bob".to_string())).unwrap();
+        let carol = deps.api.canonical_address(&HumanAddr("carol".to_string())).unwrap();
+
+        let result = crate::state::store_transfers(
+            &mut deps.storage,
+            &alice,
+            &[(bob.clone(), 300, None), (carol.clone(), 300, None)],
+        );
+        assert!(result.is_err());
+
+        assert_eq!(crate::state::read_balance(&deps.storage, &alice), 500);
+        assert_eq!(crate::state::read_balance(&deps.storage, &bob), 0);
+        assert_eq!(crate::state::read_balance(&deps.storage, &carol), 0);
+    }
+
+    #[test]
+    fn all_allowances_lists_a_live_and_an_expired_grant() {
+        let mut deps = init_helper();
+        try_increase_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("bob".to_string()),
+            Uint128(100),
+            None,
+        )
+        .unwrap();
+        try_increase_allowance(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("carol".to_string()),
+            Uint128(200),
+            Some(500),
+        )
+        .unwrap();
+
+        let response = query_all_allowances(
+            &deps,
+            HumanAddr("alice".to_string()),
+            "alice-key".to_string(),
+            None,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            to_binary(&QueryAnswer::AllAllowances {
+                owner: HumanAddr("alice".to_string()),
+                allowances: vec![
+                    (HumanAddr("carol".to_string()), Uint128(200), Some(500)),
+                    (HumanAddr("bob".to_string()), Uint128(100), None),
+                ],
+                count: 2,
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn send_without_a_registered_receiver_transfers_with_no_callback() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+
+        let response = try_send(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("some-contract".to_string()),
+            None,
+            Uint128(300),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(response.messages.is_empty());
+
+        let alice = deps.api.canonical_address(&HumanAddr("alice".to_string())).unwrap();
+        let recipient = deps
+            .api
+            .canonical_address(&HumanAddr("some-contract".to_string()))
+            .unwrap();
+        assert_eq!(crate::state::read_balance(&deps.storage, &alice), 700);
+        assert_eq!(crate::state::read_balance(&deps.storage, &recipient), 300);
+    }
+
+    #[test]
+    fn send_to_a_registered_receiver_dispatches_a_receive_callback() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+        try_register_receive(
+            &mut deps,
+            mock_env("some-contract", &[]),
+            "some-contract-hash".to_string(),
+        )
+        .unwrap();
+
+        let response = try_send(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("some-contract".to_string()),
+            None,
+            Uint128(300),
+            Some(Binary::from(b"payload".to_vec())),
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.messages.len(), 1);
+        match &response.messages[0] {
+            cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr,
+                callback_code_hash,
+                ..
+            }) => {
+                assert_eq!(contract_addr, &HumanAddr("some-contract".to_string()));
+                assert_eq!(callback_code_hash, "some-contract-hash");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn send_with_an_explicit_code_hash_overrides_a_missing_registration() {
+        let mut deps = init_helper();
+        try_mint(
+            &mut deps,
+            mock_env("minter", &[]),
+            HumanAddr("alice".to_string()),
+            Uint128(1000),
+            None,
+        )
+        .unwrap();
+
+        let response = try_send(
+            &mut deps,
+            mock_env("alice", &[]),
+            HumanAddr("some-contract".to_string()),
+            Some("explicit-hash".to_string()),
+            Uint128(300),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.messages.len(), 1);
+        match &response.messages[0] {
+            cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                callback_code_hash,
+                ..
+            }) => {
+                assert_eq!(callback_code_hash, "explicit-hash");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+}
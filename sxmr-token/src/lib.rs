@@ -0,0 +1,9 @@
+pub mod contract;
+pub mod memo;
+pub mod msg;
+pub mod rand;
+pub mod state;
+pub mod viewing_key;
+
+#[cfg(target_arch = "wasm32")]
+cosmwasm_std::create_entry_points!(contract);
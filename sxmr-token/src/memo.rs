@@ -0,0 +1,21 @@
+use cosmwasm_std::{StdError, StdResult};
+
+/// Trims surrounding whitespace and enforces that `memo` is between
+/// `min_len` and `max_len` bytes and free of control characters. `memo` is
+/// already guaranteed valid UTF-8 by Rust's `String` type, so this only
+/// re-validates the parts that type doesn't: length and character set.
+pub fn validate_and_normalize_memo(memo: &str, min_len: usize, max_len: usize) -> StdResult<String> {
+    let memo = memo.trim().to_string();
+    if memo.len() < min_len || memo.len() > max_len {
+        return Err(StdError::generic_err(format!(
+            "memo must be between {} and {} bytes",
+            min_len, max_len
+        )));
+    }
+    if memo.chars().any(|c| c.is_control()) {
+        return Err(StdError::generic_err(
+            "memo must not contain control characters",
+        ));
+    }
+    Ok(memo)
+}
@@ -0,0 +1,237 @@
+use cosmwasm_std::{Binary, HumanAddr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InitMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub admin: Option<HumanAddr>,
+    pub minters: Vec<HumanAddr>,
+    pub prng_seed: Binary,
+    /// Whether `CreateViewingKey` mixes `env.block.height`/`.time` into the
+    /// generated key (unpredictable) or derives it purely from `prng_seed`
+    /// plus the caller's address (deterministic, reproducible — useful for
+    /// testing). Should be `true` in production.
+    pub mix_block_entropy: bool,
+    /// The hard ceiling `total_supply` may never cross. `None` means
+    /// unlimited, matching the historical behavior. Set for a bridge token
+    /// like sXMR, where supply must never exceed the XMR actually locked.
+    pub max_supply: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleMsg {
+    Transfer {
+        recipient: HumanAddr,
+        amount: Uint128,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+    Send {
+        recipient: HumanAddr,
+        recipient_code_hash: Option<String>,
+        amount: Uint128,
+        msg: Option<Binary>,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+    Burn {
+        amount: Uint128,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+    Mint {
+        recipient: HumanAddr,
+        amount: Uint128,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+    SetViewingKey {
+        key: String,
+        padding: Option<String>,
+    },
+    CreateViewingKey {
+        entropy: String,
+        padding: Option<String>,
+    },
+    /// Grants `key` the `BalanceOnly` scope instead of `SetViewingKey`'s
+    /// unrestricted one, for handing a monitoring service a key that can
+    /// check balance but not transaction history or allowances.
+    SetObserverKey {
+        key: String,
+        padding: Option<String>,
+    },
+    SetMinters {
+        minters: Vec<HumanAddr>,
+        padding: Option<String>,
+    },
+    ChangeAdmin {
+        address: HumanAddr,
+        padding: Option<String>,
+    },
+    RegisterReceive {
+        code_hash: String,
+        padding: Option<String>,
+    },
+    IncreaseAllowance {
+        spender: HumanAddr,
+        amount: Uint128,
+        expiration: Option<u64>,
+        padding: Option<String>,
+    },
+    DecreaseAllowance {
+        spender: HumanAddr,
+        amount: Uint128,
+        expiration: Option<u64>,
+        padding: Option<String>,
+    },
+    /// Burns `amount` from `owner`'s balance against the allowance `owner`
+    /// granted the caller, like `Transfer`/`Send`'s relationship to a
+    /// hypothetical `TransferFrom`. Rejected if the allowance is expired or
+    /// smaller than `amount`.
+    BurnFrom {
+        owner: HumanAddr,
+        amount: Uint128,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleAnswer {
+    Transfer { status: String },
+    Send { status: String },
+    Burn { status: String },
+    Mint { status: String },
+    SetViewingKey { status: String },
+    CreateViewingKey { key: String },
+    SetObserverKey { status: String },
+    SetMinters { status: String },
+    ChangeAdmin { status: String },
+    RegisterReceive { status: String },
+    IncreaseAllowance { spender: HumanAddr, allowance: Uint128 },
+    DecreaseAllowance { spender: HumanAddr, allowance: Uint128 },
+    BurnFrom { status: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    TokenInfo {},
+    Balance {
+        address: HumanAddr,
+        key: String,
+    },
+    /// Balance and transaction count in one call, for wallet home screens
+    /// that would otherwise need two queries.
+    Account {
+        address: HumanAddr,
+        key: String,
+    },
+    /// Lists the spenders `owner` has granted a nonzero allowance to, most
+    /// recently granted first, for wallets auditing their outstanding
+    /// approvals.
+    AllowancesGiven {
+        owner: HumanAddr,
+        key: String,
+        page: Option<u32>,
+        page_size: u32,
+    },
+    /// Like `AllowancesGiven`, but includes each allowance's `expiration`
+    /// alongside its amount. Like `AllowancesGiven`, it doesn't drop an
+    /// already-expired entry from the page — a client auditing approvals
+    /// wants to see a stale one and when it lapsed, not have it silently
+    /// vanish.
+    AllAllowances {
+        owner: HumanAddr,
+        key: String,
+        page: Option<u32>,
+        page_size: u32,
+    },
+    /// Fetches a single transaction from `address`'s history by its global
+    /// id (the position it was appended at), decoupled from pagination, for
+    /// deep-linking to a specific transaction in a wallet.
+    TransactionById {
+        address: HumanAddr,
+        key: String,
+        id: u32,
+    },
+    /// Pages `address`'s transaction history, most recent first, optionally
+    /// restricted to one `TxActionKind` so a client wanting only e.g.
+    /// `Burn`s doesn't have to fetch and filter everything client-side. See
+    /// `crate::state::get_txs`.
+    TransactionHistory {
+        address: HumanAddr,
+        key: String,
+        page: u32,
+        page_size: u32,
+        filter: Option<crate::state::TxActionKind>,
+    },
+    /// The number of entries in `address`'s transaction history, so a
+    /// client can compute `TransactionHistory`'s last page instead of
+    /// paging blindly until it comes up short. See
+    /// `crate::state::get_tx_count_for`.
+    TxCountFor {
+        address: HumanAddr,
+        key: String,
+    },
+    /// The current minter list, for wallets checking who can mint before
+    /// trusting an incoming `Mint` transaction.
+    Minters {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryAnswer {
+    TokenInfo {
+        name: String,
+        symbol: String,
+        decimals: u8,
+        total_supply: Option<Uint128>,
+    },
+    Balance {
+        amount: Uint128,
+    },
+    Account {
+        balance: Uint128,
+        tx_count: u64,
+    },
+    AllowancesGiven {
+        owner: HumanAddr,
+        allowances: Vec<(HumanAddr, Uint128)>,
+        count: u32,
+    },
+    AllAllowances {
+        owner: HumanAddr,
+        allowances: Vec<(HumanAddr, Uint128, Option<u64>)>,
+        count: u32,
+    },
+    TransactionById {
+        tx: Option<Tx>,
+    },
+    TransactionHistory {
+        txs: Vec<Tx>,
+    },
+    TxCountFor {
+        count: u32,
+    },
+    Minters {
+        minters: Vec<HumanAddr>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Tx {
+    pub action: String,
+    pub from: HumanAddr,
+    pub to: HumanAddr,
+    pub amount: Uint128,
+    pub memo: Option<String>,
+    /// The spender that burned on `from`'s behalf via `BurnFrom`. See
+    /// `crate::state::Tx::burner`.
+    pub burner: Option<HumanAddr>,
+}
@@ -0,0 +1,28 @@
+use rand_chacha::ChaChaRng;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use sha2::{Digest, Sha256};
+
+/// Derives a fresh 32-byte seed from the stored `prng_seed` plus caller-supplied
+/// entropy, mirroring the approach used by the SNIP-20 reference implementation
+/// so viewing keys and future entropy-backed features stay consistent.
+pub fn new_seed(prng_seed: &[u8], entropy: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(prng_seed);
+    hasher.update(entropy);
+    let hash = hasher.finalize();
+
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&hash);
+    seed
+}
+
+pub fn rng_from_seed(seed: &[u8; 32]) -> ChaChaRng {
+    ChaChaRng::from_seed(*seed)
+}
+
+pub fn rand_bytes(seed: &[u8; 32]) -> [u8; 32] {
+    let mut rng = rng_from_seed(seed);
+    let mut bytes = [0u8; 32];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
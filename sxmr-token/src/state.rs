@@ -0,0 +1,518 @@
+use cosmwasm_std::{Api, CanonicalAddr, HumanAddr, StdError, StdResult, Storage};
+use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+use schemars::JsonSchema;
+use secret_toolkit::storage::{AppendStore, AppendStoreMut};
+use serde::{Deserialize, Serialize};
+
+pub const PREFIX_CONFIG: &[u8] = b"config";
+pub const PREFIX_BALANCES: &[u8] = b"balances";
+pub const PREFIX_VIEWING_KEY: &[u8] = b"viewing-key";
+pub const PREFIX_RECEIVERS: &[u8] = b"receivers";
+pub const PREFIX_ALLOWANCES: &[u8] = b"allowances";
+pub const PREFIX_ALLOWANCE_SPENDERS: &[u8] = b"allowance-spenders";
+pub const PREFIX_TX_COUNT: &[u8] = b"tx-count";
+pub const PREFIX_TX_HISTORY: &[u8] = b"tx-history";
+
+pub const CONSTANTS_KEY: &[u8] = b"constants";
+pub const TOTAL_SUPPLY_KEY: &[u8] = b"total-supply";
+pub const MINTERS_KEY: &[u8] = b"minters";
+
+/// The query categories a viewing key can be scoped to. `Full` (granted by
+/// `SetViewingKey`/`CreateViewingKey`) satisfies any requirement. `BalanceOnly`
+/// (granted by `SetObserverKey`) satisfies only balance queries, so a
+/// monitoring service holding one can't read transaction history or
+/// allowances, which may reveal counterparties.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub enum KeyScope {
+    Full,
+    BalanceOnly,
+}
+
+/// A stored viewing key together with the scope it was granted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StoredViewingKey {
+    pub hashed: Vec<u8>,
+    pub scope: KeyScope,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Constants {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub admin: CanonicalAddr,
+    pub prng_seed: Vec<u8>,
+    /// See `InitMsg::mix_block_entropy`.
+    pub mix_block_entropy: bool,
+    /// The hard ceiling `total_supply` may never cross, checked by every
+    /// mint path before `set_total_supply`. `None` preserves the historical
+    /// unlimited behavior. See `InitMsg::max_supply`.
+    #[serde(default)]
+    pub max_supply: Option<u128>,
+}
+
+pub struct Config<'a, S: Storage> {
+    storage: PrefixedStorage<'a, S>,
+}
+
+impl<'a, S: Storage> Config<'a, S> {
+    pub fn from_storage(storage: &'a mut S) -> Self {
+        Self {
+            storage: PrefixedStorage::new(PREFIX_CONFIG, storage),
+        }
+    }
+
+    pub fn constants(&self) -> StdResult<Constants> {
+        let bytes = self
+            .storage
+            .get(CONSTANTS_KEY)
+            .ok_or_else(|| StdError::generic_err("config not initialized"))?;
+        bincode2::deserialize::<Constants>(&bytes)
+            .map_err(|_| StdError::generic_err("failed to deserialize constants"))
+    }
+
+    pub fn set_constants(&mut self, constants: &Constants) -> StdResult<()> {
+        self.storage.set(
+            CONSTANTS_KEY,
+            &bincode2::serialize(constants)
+                .map_err(|_| StdError::generic_err("failed to serialize constants"))?,
+        );
+        Ok(())
+    }
+
+    pub fn total_supply(&self) -> u128 {
+        self.storage
+            .get(TOTAL_SUPPLY_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_total_supply(&mut self, supply: u128) {
+        self.storage
+            .set(TOTAL_SUPPLY_KEY, &bincode2::serialize(&supply).unwrap());
+    }
+
+    /// Returns the stored minter list, defaulting to empty.
+    pub fn minters(&self) -> Vec<CanonicalAddr> {
+        self.storage
+            .get(MINTERS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_minters(&mut self, minters: Vec<CanonicalAddr>) {
+        self.storage
+            .set(MINTERS_KEY, &bincode2::serialize(&minters).unwrap());
+    }
+}
+
+pub struct ReadonlyConfig<'a, S: Storage> {
+    storage: ReadonlyPrefixedStorage<'a, S>,
+}
+
+impl<'a, S: Storage> ReadonlyConfig<'a, S> {
+    pub fn from_storage(storage: &'a S) -> Self {
+        Self {
+            storage: ReadonlyPrefixedStorage::new(PREFIX_CONFIG, storage),
+        }
+    }
+
+    pub fn constants(&self) -> StdResult<Constants> {
+        let bytes = self
+            .storage
+            .get(CONSTANTS_KEY)
+            .ok_or_else(|| StdError::generic_err("config not initialized"))?;
+        bincode2::deserialize::<Constants>(&bytes)
+            .map_err(|_| StdError::generic_err("failed to deserialize constants"))
+    }
+
+    pub fn total_supply(&self) -> u128 {
+        self.storage
+            .get(TOTAL_SUPPLY_KEY)
+            .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns the stored minter list, defaulting to empty. Unlike
+    /// `ReadonlyConfigImpl::minters` upstream, this doesn't `.unwrap()` the
+    /// storage read, so it can't panic on an uninitialized key.
+    pub fn minters(&self) -> Vec<CanonicalAddr> {
+        self.storage
+            .get(MINTERS_KEY)
+            .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Persists `account`'s viewing key together with its scope, overwriting
+/// whatever key (and scope) it may have held before.
+pub fn write_viewing_key<S: Storage>(
+    storage: &mut S,
+    account: &CanonicalAddr,
+    hashed: Vec<u8>,
+    scope: KeyScope,
+) {
+    let mut store = PrefixedStorage::new(PREFIX_VIEWING_KEY, storage);
+    store.set(
+        account.as_slice(),
+        &bincode2::serialize(&StoredViewingKey { hashed, scope }).unwrap(),
+    );
+}
+
+/// Fetches `account`'s stored viewing key and scope, or `None` if it has
+/// never set one.
+pub fn read_viewing_key<S: Storage>(storage: &S, account: &CanonicalAddr) -> Option<StoredViewingKey> {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_VIEWING_KEY, storage);
+    store
+        .get(account.as_slice())
+        .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+}
+
+/// Records the code hash a contract passed to `RegisterReceive`, so `Send`
+/// knows how to call back into it. Overwrites whatever hash it registered
+/// before.
+pub fn write_receiver_hash<S: Storage>(storage: &mut S, account: &CanonicalAddr, code_hash: String) {
+    let mut store = PrefixedStorage::new(PREFIX_RECEIVERS, storage);
+    store.set(account.as_slice(), code_hash.as_bytes());
+}
+
+/// Fetches the code hash `account` registered via `RegisterReceive`, or
+/// `None` if it never did (e.g. it's a plain wallet, not a contract).
+pub fn read_receiver_hash<S: Storage>(storage: &S, account: &CanonicalAddr) -> Option<String> {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_RECEIVERS, storage);
+    store
+        .get(account.as_slice())
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+}
+
+pub fn read_balance<S: Storage>(storage: &S, account: &CanonicalAddr) -> u128 {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_BALANCES, storage);
+    store
+        .get(account.as_slice())
+        .and_then(|bytes| bincode2::deserialize::<u128>(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn write_balance<S: Storage>(storage: &mut S, account: &CanonicalAddr, amount: u128) {
+    let mut store = PrefixedStorage::new(PREFIX_BALANCES, storage);
+    store.set(account.as_slice(), &bincode2::serialize(&amount).unwrap());
+}
+
+/// `tx_count` is a plain per-account counter bumped by `bump_tx_count` on
+/// every balance-affecting operation, not a length derived from the history
+/// store (for that, see `get_tx_count_for`).
+pub fn read_tx_count<S: Storage>(storage: &S, account: &CanonicalAddr) -> u64 {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_TX_COUNT, storage);
+    store
+        .get(account.as_slice())
+        .and_then(|bytes| bincode2::deserialize::<u64>(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub fn bump_tx_count<S: Storage>(storage: &mut S, account: &CanonicalAddr) {
+    let count = read_tx_count(storage, account) + 1;
+    let mut store = PrefixedStorage::new(PREFIX_TX_COUNT, storage);
+    store.set(account.as_slice(), &bincode2::serialize(&count).unwrap());
+}
+
+/// A single balance-affecting event recorded against an account's history,
+/// fetchable by its append-store position via `read_tx`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Tx {
+    pub action: String,
+    pub from: CanonicalAddr,
+    pub to: CanonicalAddr,
+    pub amount: u128,
+    pub memo: Option<String>,
+    /// The spender that burned on `from`'s behalf via `BurnFrom`, distinct
+    /// from `from` (the owner whose balance and allowance were debited).
+    /// `None` for every other action, including a plain `Burn`.
+    #[serde(default)]
+    pub burner: Option<CanonicalAddr>,
+}
+
+/// Appends `tx` to `account`'s own history store and returns its id (the
+/// store position), which `read_tx` can later fetch directly without
+/// paginating from the start.
+pub fn append_tx<S: Storage>(storage: &mut S, account: &CanonicalAddr, tx: &Tx) -> StdResult<u32> {
+    let mut store = PrefixedStorage::multilevel(&[PREFIX_TX_HISTORY, account.as_slice()], storage);
+    let mut store = AppendStoreMut::attach_or_create(&mut store)?;
+    store.push(tx)?;
+    Ok(store.len() - 1)
+}
+
+/// Fetches the `Tx` at `id` in `account`'s history, or `None` if `account`
+/// has no history store yet or `id` is out of range.
+pub fn read_tx<S: Storage>(storage: &S, account: &CanonicalAddr, id: u32) -> Option<Tx> {
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_TX_HISTORY, account.as_slice()], storage);
+    match AppendStore::<Tx, _>::attach(&store) {
+        Some(Ok(store)) => store.get_at(id).ok(),
+        _ => None,
+    }
+}
+
+/// The wire-level counterpart of `Tx::action`: a caller filtering
+/// `get_txs` has no `amount`/`memo`/etc. to supply, so this mirrors the
+/// action strings `try_transfer`/`try_mint`/`try_burn`/`try_burn_from`
+/// actually record, without their associated data.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TxActionKind {
+    Transfer,
+    Mint,
+    Burn,
+    BurnFrom,
+}
+
+impl TxActionKind {
+    fn matches(self, action: &str) -> bool {
+        let expected = match self {
+            TxActionKind::Transfer => "transfer",
+            TxActionKind::Mint => "mint",
+            TxActionKind::Burn => "burn",
+            TxActionKind::BurnFrom => "burn_from",
+        };
+        action == expected
+    }
+}
+
+/// Lists `account`'s history, most recent first, optionally keeping only
+/// entries matching `filter`. Pagination via `page`/`page_size` is applied
+/// after filtering, so a caller asking for page 0 of `Burn`s gets the most
+/// recent `page_size` burns rather than the most recent `page_size` txs of
+/// any kind pre-filtered down.
+pub fn get_txs<S: Storage>(
+    storage: &S,
+    account: &CanonicalAddr,
+    page: u32,
+    page_size: u32,
+    filter: Option<TxActionKind>,
+) -> Vec<Tx> {
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_TX_HISTORY, account.as_slice()], storage);
+    let store = match AppendStore::<Tx, _>::attach(&store) {
+        Some(Ok(store)) => store,
+        _ => return vec![],
+    };
+    let skip = page as u64 * page_size as u64;
+    store
+        .iter()
+        .rev()
+        .filter_map(|tx| tx.ok())
+        .filter(|tx| filter.map_or(true, |kind| kind.matches(&tx.action)))
+        .skip(skip as usize)
+        .take(page_size as usize)
+        .collect()
+}
+
+/// The number of entries in `for_address`'s history store, so a client can
+/// compute its last `get_txs` page without paging blindly until it comes up
+/// short. Distinct from `read_tx_count`, which tracks a separately-bumped
+/// counter for the `Account` query; this counts the actual `PREFIX_TX_HISTORY`
+/// append store directly. `0` if `for_address` has no history yet.
+pub fn get_tx_count_for<S: Storage, A: Api>(
+    api: &A,
+    storage: &S,
+    for_address: &HumanAddr,
+) -> StdResult<u32> {
+    let account = api.canonical_address(for_address)?;
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_TX_HISTORY, account.as_slice()], storage);
+    match AppendStore::<Tx, _>::attach(&store) {
+        Some(store) => Ok(store?.len()),
+        None => Ok(0),
+    }
+}
+
+pub fn store_mint<S: Storage>(
+    storage: &mut S,
+    recipient: &CanonicalAddr,
+    amount: u128,
+) -> StdResult<()> {
+    let mut config = Config::from_storage(storage);
+    let total_supply = config
+        .total_supply()
+        .checked_add(amount)
+        .ok_or_else(|| StdError::generic_err("total supply overflow"))?;
+    if let Some(max_supply) = config.constants()?.max_supply {
+        if total_supply > max_supply {
+            return Err(StdError::generic_err("mint would exceed the max supply cap"));
+        }
+    }
+    config.set_total_supply(total_supply);
+
+    let balance = read_balance(storage, recipient);
+    let new_balance = balance
+        .checked_add(amount)
+        .ok_or_else(|| StdError::generic_err("balance overflow"))?;
+    write_balance(storage, recipient, new_balance);
+    Ok(())
+}
+
+pub fn store_transfer<S: Storage>(
+    storage: &mut S,
+    sender: &CanonicalAddr,
+    recipient: &CanonicalAddr,
+    amount: u128,
+) -> StdResult<()> {
+    let sender_balance = read_balance(storage, sender);
+    let sender_balance = sender_balance
+        .checked_sub(amount)
+        .ok_or_else(|| StdError::generic_err("insufficient funds"))?;
+    write_balance(storage, sender, sender_balance);
+
+    let recipient_balance = read_balance(storage, recipient);
+    let recipient_balance = recipient_balance
+        .checked_add(amount)
+        .ok_or_else(|| StdError::generic_err("balance overflow"))?;
+    write_balance(storage, recipient, recipient_balance);
+    Ok(())
+}
+
+/// Batch counterpart to `store_transfer`, for a sender paying out several
+/// recipients atomically. Debits `sender` once for the sum of the whole
+/// batch instead of once per recipient, and appends one `Tx` to each
+/// recipient's history. Fails before writing anything if the sender's
+/// balance can't cover the sum; a failure partway through crediting
+/// recipients still leaves nothing committed, since it surfaces as an
+/// `Err` from `handle()`, which CosmWasm discards in full.
+pub fn store_transfers<S: Storage>(
+    storage: &mut S,
+    sender: &CanonicalAddr,
+    recipients: &[(CanonicalAddr, u128, Option<String>)],
+) -> StdResult<()> {
+    let total = recipients
+        .iter()
+        .try_fold(0u128, |total, (_, amount, _)| total.checked_add(*amount))
+        .ok_or_else(|| StdError::generic_err("transfer total overflow"))?;
+
+    let sender_balance = read_balance(storage, sender);
+    let sender_balance = sender_balance
+        .checked_sub(total)
+        .ok_or_else(|| StdError::generic_err("insufficient funds"))?;
+    write_balance(storage, sender, sender_balance);
+
+    for (recipient, amount, memo) in recipients {
+        let recipient_balance = read_balance(storage, recipient);
+        let recipient_balance = recipient_balance
+            .checked_add(*amount)
+            .ok_or_else(|| StdError::generic_err("balance overflow"))?;
+        write_balance(storage, recipient, recipient_balance);
+
+        append_tx(
+            storage,
+            recipient,
+            &Tx {
+                action: "transfer".to_string(),
+                from: sender.clone(),
+                to: recipient.clone(),
+                amount: *amount,
+                memo: memo.clone(),
+                burner: None,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Allowance {
+    pub amount: u128,
+    pub expiration: Option<u64>,
+}
+
+fn allowance_key(owner: &CanonicalAddr, spender: &CanonicalAddr) -> Vec<u8> {
+    [owner.as_slice(), spender.as_slice()].concat()
+}
+
+pub fn read_allowance<S: Storage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+    spender: &CanonicalAddr,
+) -> Allowance {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_ALLOWANCES, storage);
+    store
+        .get(&allowance_key(owner, spender))
+        .and_then(|bytes| bincode2::deserialize::<Allowance>(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// Like `read_allowance`, but returns a zeroed `Allowance` once
+/// `expiration` has passed `block_time`, instead of the stale amount still
+/// sitting in storage. `read_allowance` itself is left alone for
+/// introspection (e.g. `QueryAnswer::AllowancesGiven`, which should keep
+/// showing what was granted even after it lapses); callers that are about
+/// to actually spend an allowance should use this one instead.
+pub fn read_allowance_checked<S: Storage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+    spender: &CanonicalAddr,
+    block_time: u64,
+) -> Allowance {
+    let allowance = read_allowance(storage, owner, spender);
+    match allowance.expiration {
+        Some(expiration) if block_time >= expiration => Allowance::default(),
+        _ => allowance,
+    }
+}
+
+/// Persists `allowance` for `owner` -> `spender` and keeps the owner's
+/// spender list (used to enumerate allowances) in sync: `spender` is added
+/// when the allowance becomes nonzero and dropped once it returns to zero.
+pub fn write_allowance<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+    spender: &CanonicalAddr,
+    allowance: &Allowance,
+) {
+    let mut store = PrefixedStorage::new(PREFIX_ALLOWANCES, storage);
+    store.set(
+        &allowance_key(owner, spender),
+        &bincode2::serialize(allowance).unwrap(),
+    );
+    drop(store);
+
+    let mut spenders = spender_list(storage, owner);
+    let already_listed = spenders.iter().any(|s| s == spender);
+    if allowance.amount > 0 && !already_listed {
+        spenders.push(spender.clone());
+        set_spender_list(storage, owner, &spenders);
+    } else if allowance.amount == 0 && already_listed {
+        spenders.retain(|s| s != spender);
+        set_spender_list(storage, owner, &spenders);
+    }
+}
+
+/// The spenders currently holding a nonzero allowance from an owner, in
+/// grant order. Used to back the `AllowancesGiven` query.
+pub fn spender_list<S: Storage>(storage: &S, owner: &CanonicalAddr) -> Vec<CanonicalAddr> {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_ALLOWANCE_SPENDERS, storage);
+    store
+        .get(owner.as_slice())
+        .and_then(|bytes| bincode2::deserialize(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn set_spender_list<S: Storage>(storage: &mut S, owner: &CanonicalAddr, spenders: &[CanonicalAddr]) {
+    let mut store = PrefixedStorage::new(PREFIX_ALLOWANCE_SPENDERS, storage);
+    store.set(owner.as_slice(), &bincode2::serialize(spenders).unwrap());
+}
+
+pub fn store_burn<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+    amount: u128,
+    _burner: Option<&CanonicalAddr>,
+) -> StdResult<()> {
+    let balance = read_balance(storage, owner);
+    let balance = balance
+        .checked_sub(amount)
+        .ok_or_else(|| StdError::generic_err("insufficient funds"))?;
+    write_balance(storage, owner, balance);
+
+    let mut config = Config::from_storage(storage);
+    let total_supply = config
+        .total_supply()
+        .checked_sub(amount)
+        .ok_or_else(|| StdError::generic_err("total supply underflow"))?;
+    config.set_total_supply(total_supply);
+    Ok(())
+}